@@ -0,0 +1,32 @@
+use async_std_linux_video::{types::*, Control, Device};
+
+#[test]
+fn values_is_send() {
+    fn is_send<T: Send>() {}
+
+    is_send::<Value<Control>>();
+    is_send::<Values<Control>>();
+    is_send::<ValueData>();
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[async_std::test]
+async fn get_set_control() {
+    let dev = Device::open(env!("VIVID_WEBCAM")).await.unwrap();
+
+    let ctrl = dev.control(CtrlId::Brightness).await.unwrap();
+    let mut value = Value::from(&ctrl);
+
+    dev.get_control(&mut value).await.unwrap();
+    let brightness = *value.try_ref::<i32>().unwrap();
+
+    *value.try_mut::<i32>().unwrap() = brightness + 1;
+    dev.set_control(&value).await.unwrap();
+
+    *value.try_mut::<i32>().unwrap() = 0;
+    dev.get_control(&mut value).await.unwrap();
+    assert_eq!(*value.try_ref::<i32>().unwrap(), brightness + 1);
+
+    *value.try_mut::<i32>().unwrap() = brightness;
+    dev.set_control(&value).await.unwrap();
+}
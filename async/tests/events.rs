@@ -0,0 +1,22 @@
+use linux_video_async::{types::*, Device};
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn control_event() {
+    async_io::block_on(async {
+        let dev = Device::open(env!("VIVID_WEBCAM")).await.unwrap();
+
+        let subscription = EventSubscription::new(
+            EventType::Ctrl,
+            CtrlId::Brightness as _,
+            EventSubFlag::SendInitial,
+        );
+        dev.subscribe_event(&subscription).unwrap();
+
+        let event = dev.next_event().await.unwrap();
+        assert_eq!(event.type_(), Ok(EventType::Ctrl));
+        assert!(event.data::<EventCtrl>().is_some());
+
+        dev.unsubscribe_event(&subscription).unwrap();
+    });
+}
@@ -1,7 +1,251 @@
-use crate::types::*;
-use nix::{ioctl_none, ioctl_read, ioctl_readwrite, ioctl_write_ptr};
+//! Device calls
+//!
+//! All ioctl calls goes through this module so them can be handled by [Backend]
+//! instead of kernel (i.e. by mock devices for testing).
 
-const MAGIC: u8 = b'V';
+use crate::{types::*, Result};
+use nix::libc::{c_int, c_void};
+use std::{
+    os::unix::io::RawFd,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The ioctl request code
+pub type Request = nix::sys::ioctl::ioctl_num_type;
+
+/// Magic number of V4L2 ioctl requests
+pub const MAGIC: u8 = b'V';
+
+/// Get ioctl request number
+pub fn request_nr(request: Request) -> u8 {
+    request as u8
+}
+
+/// Get ioctl request magic
+pub fn request_magic(request: Request) -> u8 {
+    (request >> 8) as u8
+}
+
+/// Get size of ioctl request data
+pub fn request_size(request: Request) -> usize {
+    ((request >> 16) & 0x3fff) as usize
+}
+
+/// Handler of device calls
+pub trait Backend: Send + Sync {
+    /// Handle ioctl call
+    ///
+    /// # Safety
+    ///
+    /// The `data` should point to structure which corresponds to request.
+    unsafe fn ioctl(&self, fd: RawFd, request: Request, data: *mut c_void) -> nix::Result<c_int>;
+
+    /// Map buffer memory
+    fn mmap(&self, fd: RawFd, length: usize, offset: i64) -> nix::Result<*mut c_void>;
+}
+
+/// Kernel calls
+///
+/// Used when no backend is registered for file.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kernel;
+
+impl Backend for Kernel {
+    unsafe fn ioctl(&self, fd: RawFd, request: Request, data: *mut c_void) -> nix::Result<c_int> {
+        nix::errno::Errno::result(nix::libc::ioctl(fd, request, data))
+    }
+
+    fn mmap(&self, fd: RawFd, length: usize, offset: i64) -> nix::Result<*mut c_void> {
+        use nix::sys::mman::{mmap, MapFlags, ProtFlags};
+
+        unsafe {
+            mmap(
+                None,
+                core::num::NonZeroUsize::new(length).ok_or(nix::errno::Errno::EINVAL)?,
+                ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+                MapFlags::MAP_SHARED,
+                fd,
+                offset,
+            )
+        }
+    }
+}
+
+type FileKey = (u64, u64);
+
+static BACKENDS_COUNT: AtomicUsize = AtomicUsize::new(0);
+static BACKENDS: Mutex<Vec<(FileKey, Arc<dyn Backend>)>> = Mutex::new(Vec::new());
+
+fn file_key(fd: RawFd) -> nix::Result<FileKey> {
+    let stat = nix::sys::stat::fstat(fd)?;
+    Ok((stat.st_dev as _, stat.st_ino as _))
+}
+
+/// Handle calls of opened file by backend
+///
+/// The backend is used for all descriptors which refers to the same file.
+pub fn register_backend(fd: RawFd, backend: Arc<dyn Backend>) -> Result<()> {
+    let key = file_key(fd)?;
+    let mut backends = BACKENDS.lock().unwrap();
+    backends.retain(|(other, _)| *other != key);
+    backends.push((key, backend));
+    BACKENDS_COUNT.store(backends.len(), Ordering::SeqCst);
+    Ok(())
+}
+
+/// Stop handling calls of opened file by backend
+pub fn unregister_backend(fd: RawFd) -> Result<()> {
+    let key = file_key(fd)?;
+    let mut backends = BACKENDS.lock().unwrap();
+    backends.retain(|(other, _)| *other != key);
+    BACKENDS_COUNT.store(backends.len(), Ordering::SeqCst);
+    Ok(())
+}
+
+/// Get backend which handles calls of opened file
+pub fn registered_backend(fd: RawFd) -> Option<Arc<dyn Backend>> {
+    // avoid extra syscall when no backends is registered
+    if BACKENDS_COUNT.load(Ordering::SeqCst) == 0 {
+        return None;
+    }
+
+    let key = file_key(fd).ok()?;
+    BACKENDS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(other, _)| *other == key)
+        .map(|(_, backend)| backend.clone())
+}
+
+unsafe fn ioctl(fd: RawFd, request: Request, data: *mut c_void) -> nix::Result<c_int> {
+    if let Some(backend) = registered_backend(fd) {
+        backend.ioctl(fd, request, data)
+    } else {
+        Kernel.ioctl(fd, request, data)
+    }
+}
+
+/// Map buffer memory of device
+pub(crate) unsafe fn mmap(fd: RawFd, length: usize, offset: i64) -> nix::Result<*mut c_void> {
+    if let Some(backend) = registered_backend(fd) {
+        backend.mmap(fd, length, offset)
+    } else {
+        Kernel.mmap(fd, length, offset)
+    }
+}
+
+macro_rules! ioctl_none {
+    ($name:ident, $magic:expr, $nr:expr) => {
+        #[allow(dead_code)]
+        pub(crate) unsafe fn $name(fd: RawFd) -> Result<c_int> {
+            checked_call!(
+                $name,
+                $nr,
+                core::ptr::null::<()>(),
+                traced_call!(
+                    $name,
+                    core::ptr::null::<()>(),
+                    ioctl(
+                        fd,
+                        nix::request_code_none!($magic, $nr) as _,
+                        core::ptr::null_mut()
+                    )
+                )
+            )
+        }
+
+        pub mod $name {
+            /// Request number
+            pub const NR: u8 = $nr;
+        }
+    };
+}
+
+macro_rules! ioctl_read {
+    ($name:ident, $magic:expr, $nr:expr, $type:ty) => {
+        #[allow(dead_code)]
+        pub(crate) unsafe fn $name(fd: RawFd, data: *mut $type) -> Result<c_int> {
+            checked_call!(
+                $name,
+                $nr,
+                data,
+                traced_call!(
+                    $name,
+                    data,
+                    ioctl(
+                        fd,
+                        nix::request_code_read!($magic, $nr, core::mem::size_of::<$type>()) as _,
+                        data as _
+                    )
+                )
+            )
+        }
+
+        pub mod $name {
+            /// Request number
+            pub const NR: u8 = $nr;
+        }
+    };
+}
+
+macro_rules! ioctl_write_ptr {
+    ($name:ident, $magic:expr, $nr:expr, $type:ty) => {
+        #[allow(dead_code)]
+        pub(crate) unsafe fn $name(fd: RawFd, data: *const $type) -> Result<c_int> {
+            checked_call!(
+                $name,
+                $nr,
+                data,
+                traced_call!(
+                    $name,
+                    data,
+                    ioctl(
+                        fd,
+                        nix::request_code_write!($magic, $nr, core::mem::size_of::<$type>()) as _,
+                        data as _
+                    )
+                )
+            )
+        }
+
+        pub mod $name {
+            /// Request number
+            pub const NR: u8 = $nr;
+        }
+    };
+}
+
+macro_rules! ioctl_readwrite {
+    ($name:ident, $magic:expr, $nr:expr, $type:ty) => {
+        #[allow(dead_code)]
+        pub(crate) unsafe fn $name(fd: RawFd, data: *mut $type) -> Result<c_int> {
+            checked_call!(
+                $name,
+                $nr,
+                data,
+                traced_call!(
+                    $name,
+                    data,
+                    ioctl(
+                        fd,
+                        nix::request_code_readwrite!($magic, $nr, core::mem::size_of::<$type>())
+                            as _,
+                        data as _
+                    )
+                )
+            )
+        }
+
+        pub mod $name {
+            /// Request number
+            pub const NR: u8 = $nr;
+        }
+    };
+}
 
 ioctl_read!(query_cap, MAGIC, 0, Capability);
 ioctl_readwrite!(enum_fmt, MAGIC, 2, FmtDesc);
@@ -98,3 +342,4 @@ ioctl_readwrite!(enum_freq_bands, MAGIC, 101, FrequencyBand);
 //ioctl_readwrite!(dbg_g_chip_info, MAGIC, 102, DbgChipInfo);
 
 ioctl_readwrite!(query_ext_ctrl, MAGIC, 103, QueryExtCtrl);
+ioctl_readwrite!(remove_bufs, MAGIC, 104, RemoveBuffers);
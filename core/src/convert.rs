@@ -0,0 +1,645 @@
+//! Pixel format conversion
+//!
+//! Pure-Rust conversion between common uncompressed capture formats which
+//! takes into account Y'CbCr encoding and quantization range of formats.
+
+use crate::{types::*, utils, PlaneLayout, Result};
+
+/// Intermediate full range pixel (R'G'B' or Y'CbCr)
+type Pixel = [u8; 3];
+
+const SHIFT: u32 = 16;
+const HALF: i32 = 1 << (SHIFT - 1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Packing {
+    /// 8-bit R'G'B' with byte offsets of components
+    Rgb {
+        size: u8,
+        r: u8,
+        g: u8,
+        b: u8,
+        a: Option<u8>,
+    },
+    /// 16-bit R'G'B' 5-6-5
+    Rgb565 { be: bool },
+    /// 8-bit luma
+    Grey,
+    /// 16-bit luma
+    Y16 { be: bool },
+    /// Packed 4:2:2 with byte offsets of components in macropixel
+    Packed { y0: u8, y1: u8, u: u8, v: u8 },
+    /// Luma plane followed by plane of interleaved chroma
+    SemiPlanar { vsub: u8, swap: bool },
+    /// Luma plane followed by two 4:2:0 chroma planes
+    Planar { swap: bool },
+}
+
+impl Packing {
+    fn new(fourcc: FourCc) -> Option<Self> {
+        const fn rgb(size: u8, r: u8, g: u8, b: u8, a: Option<u8>) -> Packing {
+            Packing::Rgb { size, r, g, b, a }
+        }
+
+        const fn packed(y0: u8, y1: u8, u: u8, v: u8) -> Packing {
+            Packing::Packed { y0, y1, u, v }
+        }
+
+        Some(match fourcc {
+            FourCc::Rgb24 => rgb(3, 0, 1, 2, None),
+            FourCc::Bgr24 => rgb(3, 2, 1, 0, None),
+            FourCc::Bgr32 | FourCc::Abgr32 => rgb(4, 2, 1, 0, Some(3)),
+            FourCc::Xbgr32 => rgb(4, 2, 1, 0, None),
+            FourCc::Rgb32 | FourCc::Argb32 => rgb(4, 1, 2, 3, Some(0)),
+            FourCc::Xrgb32 => rgb(4, 1, 2, 3, None),
+            FourCc::Rgba32 => rgb(4, 0, 1, 2, Some(3)),
+            FourCc::Rgbx32 => rgb(4, 0, 1, 2, None),
+            FourCc::Bgra32 => rgb(4, 3, 2, 1, Some(0)),
+            FourCc::Bgrx32 => rgb(4, 3, 2, 1, None),
+            FourCc::Rgb565 => Self::Rgb565 { be: false },
+            FourCc::Rgb565x => Self::Rgb565 { be: true },
+            FourCc::Grey => Self::Grey,
+            FourCc::Y16 => Self::Y16 { be: false },
+            FourCc::Y16be => Self::Y16 { be: true },
+            FourCc::Yuyv => packed(0, 2, 1, 3),
+            FourCc::Yvyu => packed(0, 2, 3, 1),
+            FourCc::Uyvy => packed(1, 3, 0, 2),
+            FourCc::Vyuy => packed(1, 3, 2, 0),
+            FourCc::Nv12 => Self::SemiPlanar {
+                vsub: 2,
+                swap: false,
+            },
+            FourCc::Nv21 => Self::SemiPlanar {
+                vsub: 2,
+                swap: true,
+            },
+            FourCc::Nv16 => Self::SemiPlanar {
+                vsub: 1,
+                swap: false,
+            },
+            FourCc::Nv61 => Self::SemiPlanar {
+                vsub: 1,
+                swap: true,
+            },
+            FourCc::Yuv420 => Self::Planar { swap: false },
+            FourCc::Yvu420 => Self::Planar { swap: true },
+            _ => return None,
+        })
+    }
+
+    /// Intermediate pixels is Y'CbCr
+    fn is_ycc(self) -> bool {
+        !matches!(self, Self::Rgb { .. } | Self::Rgb565 { .. })
+    }
+}
+
+/// Conversion parameters of image
+#[derive(Debug, Clone, Copy)]
+struct Params {
+    packing: Packing,
+    full_range: bool,
+    encoding: YcbcrEncoding,
+    layout: PlaneLayout,
+}
+
+impl Params {
+    fn new(pixfmt: &PixFormat) -> Result<Self> {
+        let fourcc = pixfmt.pixel_format();
+        let packing =
+            Packing::new(fourcc).ok_or_else(|| utils::invalid_input("Unsupported pixel format"))?;
+        let layout = PlaneLayout::from_pix_format(pixfmt)
+            .ok_or_else(|| utils::invalid_input("Unsupported pixel format"))?;
+
+        // unknown values is treated as defaults
+        let color_space = pixfmt.color_space().unwrap_or(ColorSpace::Default);
+
+        let full_range = match pixfmt.quantization().unwrap_or(Quantization::Default) {
+            Quantization::Default => {
+                Quantization::new_default(fourcc.is_rgb() || fourcc.is_hsv(), color_space)
+                    == Quantization::FullRange
+            }
+            quantization => quantization == Quantization::FullRange,
+        };
+
+        let encoding = match pixfmt
+            .ycbcr_enc()
+            .and_then(|encoding| encoding.ok())
+            .unwrap_or(YcbcrEncoding::Default)
+        {
+            YcbcrEncoding::Default => color_space.into(),
+            encoding => encoding,
+        };
+
+        Ok(Self {
+            packing,
+            full_range,
+            encoding,
+            layout,
+        })
+    }
+
+    fn width(&self) -> usize {
+        self.layout.width() as _
+    }
+
+    fn height(&self) -> usize {
+        self.layout.height() as _
+    }
+
+    fn row<'a>(&self, data: &'a [u8], plane: usize, line: usize) -> &'a [u8] {
+        let bytes_per_line = self.layout.bytes_per_line(plane) as usize;
+        let offset = self.layout.plane_offset(plane) as usize + bytes_per_line * line;
+        &data[offset..offset + bytes_per_line]
+    }
+
+    fn row_mut<'a>(&self, data: &'a mut [u8], plane: usize, line: usize) -> &'a mut [u8] {
+        let bytes_per_line = self.layout.bytes_per_line(plane) as usize;
+        let offset = self.layout.plane_offset(plane) as usize + bytes_per_line * line;
+        &mut data[offset..offset + bytes_per_line]
+    }
+
+    fn expand_y(&self, value: u8) -> u8 {
+        if self.full_range {
+            value
+        } else {
+            clamp(((value as i32 - 16) * 255 + 109) / 219)
+        }
+    }
+
+    fn expand_c(&self, value: u8) -> u8 {
+        if self.full_range {
+            value
+        } else {
+            clamp((value as i32 - 128) * 255 / 224 + 128)
+        }
+    }
+
+    fn compress_y(&self, value: u8) -> u8 {
+        if self.full_range {
+            value
+        } else {
+            (16 + (value as i32 * 219 + 127) / 255) as _
+        }
+    }
+
+    fn compress_c(&self, value: u8) -> u8 {
+        if self.full_range {
+            value
+        } else {
+            (128 + (value as i32 - 128) * 224 / 255) as _
+        }
+    }
+}
+
+#[inline(always)]
+fn clamp(value: i32) -> u8 {
+    value.clamp(0, 255) as _
+}
+
+#[inline(always)]
+fn fixed(value: f32) -> i32 {
+    (value * (1 << SHIFT) as f32).round() as _
+}
+
+/// Y'CbCr to R'G'B' matrix in fixed point
+#[derive(Debug, Clone, Copy)]
+struct Matrix {
+    kr: i32,
+    kg: i32,
+    kb: i32,
+    cr_r: i32,
+    cb_g: i32,
+    cr_g: i32,
+    cb_b: i32,
+    r_cr: i32,
+    b_cb: i32,
+}
+
+impl From<YcbcrEncoding> for Matrix {
+    fn from(encoding: YcbcrEncoding) -> Self {
+        let (kr, kb) = match encoding {
+            YcbcrEncoding::E709 | YcbcrEncoding::Xv709 => (0.2126, 0.0722),
+            YcbcrEncoding::Bt2020 | YcbcrEncoding::Bt2020ConstLum => (0.2627, 0.0593),
+            YcbcrEncoding::Smpte240M => (0.212, 0.087),
+            _ => (0.299, 0.114),
+        };
+        let kg = 1.0 - kr - kb;
+
+        Self {
+            kr: fixed(kr),
+            kg: fixed(kg),
+            kb: fixed(kb),
+            cr_r: fixed(2.0 * (1.0 - kr)),
+            cb_g: fixed(2.0 * kb * (1.0 - kb) / kg),
+            cr_g: fixed(2.0 * kr * (1.0 - kr) / kg),
+            cb_b: fixed(2.0 * (1.0 - kb)),
+            r_cr: fixed(0.5 / (1.0 - kr)),
+            b_cb: fixed(0.5 / (1.0 - kb)),
+        }
+    }
+}
+
+impl Matrix {
+    fn rgb_from(&self, [y, cb, cr]: Pixel) -> Pixel {
+        let y = y as i32;
+        let cb = cb as i32 - 128;
+        let cr = cr as i32 - 128;
+
+        [
+            clamp(y + ((self.cr_r * cr + HALF) >> SHIFT)),
+            clamp(y - ((self.cb_g * cb + self.cr_g * cr + HALF) >> SHIFT)),
+            clamp(y + ((self.cb_b * cb + HALF) >> SHIFT)),
+        ]
+    }
+
+    fn ycc_from(&self, [r, g, b]: Pixel) -> Pixel {
+        let (r, g, b) = (r as i32, g as i32, b as i32);
+        let y = (self.kr * r + self.kg * g + self.kb * b + HALF) >> SHIFT;
+
+        [
+            clamp(y),
+            clamp(128 + (((b - y) * self.b_cb + HALF) >> SHIFT)),
+            clamp(128 + (((r - y) * self.r_cr + HALF) >> SHIFT)),
+        ]
+    }
+}
+
+/// Check that pixel format is supported by [convert]
+pub fn is_supported(fourcc: FourCc) -> bool {
+    Packing::new(fourcc).is_some()
+}
+
+/// Convert image from one pixel format to another
+///
+/// The `src_fmt` and `dst_fmt` describe the layout of data, so strided
+/// buffers (i.e. [BufferData](crate::BufferData) of stream) may be used
+/// directly. The Y'CbCr encoding and quantization range of formats are
+/// respected. Both images must have the same size, scaling isn't supported.
+pub fn convert(
+    src: impl AsRef<[u8]>,
+    src_fmt: &PixFormat,
+    mut dst: impl AsMut<[u8]>,
+    dst_fmt: &PixFormat,
+) -> Result<()> {
+    let src = src.as_ref();
+    let dst = dst.as_mut();
+
+    let src_params = Params::new(src_fmt)?;
+    let dst_params = Params::new(dst_fmt)?;
+
+    if src_params.width() != dst_params.width() || src_params.height() != dst_params.height() {
+        return Err(utils::invalid_input("Image sizes mismatch"));
+    }
+
+    if src.len() < src_params.layout.size_image() as usize {
+        return Err(utils::invalid_input("Source buffer too small"));
+    }
+
+    if dst.len() < dst_params.layout.size_image() as usize {
+        return Err(utils::invalid_input("Destination buffer too small"));
+    }
+
+    if src_params.packing == dst_params.packing
+        && src_params.full_range == dst_params.full_range
+        && (!src_params.packing.is_ycc() || src_params.encoding == dst_params.encoding)
+    {
+        copy(src, &src_params, dst, &dst_params);
+        return Ok(());
+    }
+
+    let mut pixels = decode(src, &src_params);
+
+    match (src_params.packing.is_ycc(), dst_params.packing.is_ycc()) {
+        (true, false) => {
+            let matrix = Matrix::from(src_params.encoding);
+            for pixel in &mut pixels {
+                *pixel = matrix.rgb_from(*pixel);
+            }
+        }
+        (false, true) => {
+            let matrix = Matrix::from(dst_params.encoding);
+            for pixel in &mut pixels {
+                *pixel = matrix.ycc_from(*pixel);
+            }
+        }
+        (true, true) if src_params.encoding != dst_params.encoding => {
+            let src_matrix = Matrix::from(src_params.encoding);
+            let dst_matrix = Matrix::from(dst_params.encoding);
+            for pixel in &mut pixels {
+                *pixel = dst_matrix.ycc_from(src_matrix.rgb_from(*pixel));
+            }
+        }
+        _ => (),
+    }
+
+    encode(&pixels, dst, &dst_params);
+
+    Ok(())
+}
+
+/// Copy image line by line
+fn copy(src: &[u8], src_params: &Params, dst: &mut [u8], dst_params: &Params) {
+    let info = src_params.layout.info();
+    let height = src_params.layout.height();
+
+    for plane in 0..src_params.layout.num_planes() {
+        let len = info.min_bytes_per_line(plane, src_params.layout.width()) as usize;
+        for line in 0..info.plane_height(plane, height) as usize {
+            dst_params.row_mut(dst, plane, line)[..len]
+                .copy_from_slice(&src_params.row(src, plane, line)[..len]);
+        }
+    }
+}
+
+/// Decode image to full range intermediate pixels
+fn decode(src: &[u8], params: &Params) -> Vec<Pixel> {
+    let (width, height) = (params.width(), params.height());
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for line in 0..height {
+        let row = params.row(src, 0, line);
+
+        match params.packing {
+            Packing::Rgb { size, r, g, b, .. } => {
+                pixels.extend(row.chunks_exact(size as _).take(width).map(|data| {
+                    [
+                        params.expand_y(data[r as usize]),
+                        params.expand_y(data[g as usize]),
+                        params.expand_y(data[b as usize]),
+                    ]
+                }));
+            }
+            Packing::Rgb565 { be } => {
+                pixels.extend(row.chunks_exact(2).take(width).map(|data| {
+                    let data = [data[0], data[1]];
+                    let value = if be {
+                        u16::from_be_bytes(data)
+                    } else {
+                        u16::from_le_bytes(data)
+                    };
+                    let r = (value >> 11) as u8 & 0x1f;
+                    let g = (value >> 5) as u8 & 0x3f;
+                    let b = value as u8 & 0x1f;
+                    [
+                        params.expand_y((r << 3) | (r >> 2)),
+                        params.expand_y((g << 2) | (g >> 4)),
+                        params.expand_y((b << 3) | (b >> 2)),
+                    ]
+                }));
+            }
+            Packing::Grey => {
+                pixels.extend(
+                    row[..width]
+                        .iter()
+                        .map(|value| [params.expand_y(*value), 128, 128]),
+                );
+            }
+            Packing::Y16 { be } => {
+                let msb = if be { 0 } else { 1 };
+                pixels.extend(
+                    row.chunks_exact(2)
+                        .take(width)
+                        .map(|data| [params.expand_y(data[msb]), 128, 128]),
+                );
+            }
+            Packing::Packed { y0, y1, u, v } => {
+                for x in 0..width {
+                    let data = &row[x / 2 * 4..];
+                    let y = if x % 2 == 0 { y0 } else { y1 };
+                    pixels.push([
+                        params.expand_y(data[y as usize]),
+                        params.expand_c(data.get(u as usize).copied().unwrap_or(128)),
+                        params.expand_c(data.get(v as usize).copied().unwrap_or(128)),
+                    ]);
+                }
+            }
+            Packing::SemiPlanar { vsub, swap } => {
+                let chroma = params.row(src, 1, line / vsub as usize);
+                let (u, v) = if swap { (1, 0) } else { (0, 1) };
+                for x in 0..width {
+                    let data = &chroma[x / 2 * 2..];
+                    pixels.push([
+                        params.expand_y(row[x]),
+                        params.expand_c(data[u]),
+                        params.expand_c(data[v]),
+                    ]);
+                }
+            }
+            Packing::Planar { swap } => {
+                let (u, v) = if swap { (2, 1) } else { (1, 2) };
+                let u = params.row(src, u, line / 2);
+                let v = params.row(src, v, line / 2);
+                for x in 0..width {
+                    pixels.push([
+                        params.expand_y(row[x]),
+                        params.expand_c(u[x / 2]),
+                        params.expand_c(v[x / 2]),
+                    ]);
+                }
+            }
+        }
+    }
+
+    pixels
+}
+
+/// Average chroma of pixels in block
+fn chroma(pixels: &[Pixel], width: usize, x: usize, y: usize, hsub: usize, vsub: usize) -> Pixel {
+    let height = pixels.len() / width;
+    let (mut u, mut v, mut n) = (0u32, 0u32, 0u32);
+
+    for y in y..(y + vsub).min(height) {
+        for x in x..(x + hsub).min(width) {
+            let pixel = pixels[y * width + x];
+            u += pixel[1] as u32;
+            v += pixel[2] as u32;
+            n += 1;
+        }
+    }
+
+    [0, ((u + n / 2) / n) as _, ((v + n / 2) / n) as _]
+}
+
+/// Encode full range intermediate pixels to image
+fn encode(pixels: &[Pixel], dst: &mut [u8], params: &Params) {
+    let (width, height) = (params.width(), params.height());
+
+    for line in 0..height {
+        let pixels_row = &pixels[line * width..][..width];
+
+        match params.packing {
+            Packing::Rgb { size, r, g, b, a } => {
+                let row = params.row_mut(dst, 0, line);
+                for (data, pixel) in row.chunks_exact_mut(size as _).zip(pixels_row) {
+                    data[r as usize] = params.compress_y(pixel[0]);
+                    data[g as usize] = params.compress_y(pixel[1]);
+                    data[b as usize] = params.compress_y(pixel[2]);
+                    if let Some(a) = a {
+                        data[a as usize] = 0xff;
+                    }
+                }
+            }
+            Packing::Rgb565 { be } => {
+                let row = params.row_mut(dst, 0, line);
+                for (data, pixel) in row.chunks_exact_mut(2).zip(pixels_row) {
+                    let r = params.compress_y(pixel[0]) as u16 >> 3;
+                    let g = params.compress_y(pixel[1]) as u16 >> 2;
+                    let b = params.compress_y(pixel[2]) as u16 >> 3;
+                    let value = (r << 11) | (g << 5) | b;
+                    data.copy_from_slice(&if be {
+                        value.to_be_bytes()
+                    } else {
+                        value.to_le_bytes()
+                    });
+                }
+            }
+            Packing::Grey => {
+                let row = params.row_mut(dst, 0, line);
+                for (data, pixel) in row.iter_mut().zip(pixels_row) {
+                    *data = params.compress_y(pixel[0]);
+                }
+            }
+            Packing::Y16 { be } => {
+                let row = params.row_mut(dst, 0, line);
+                for (data, pixel) in row.chunks_exact_mut(2).zip(pixels_row) {
+                    let value = params.compress_y(pixel[0]) as u16 * 0x101;
+                    data.copy_from_slice(&if be {
+                        value.to_be_bytes()
+                    } else {
+                        value.to_le_bytes()
+                    });
+                }
+            }
+            Packing::Packed { y0, y1, u, v } => {
+                let row = params.row_mut(dst, 0, line);
+                for x in (0..width).step_by(2) {
+                    let chroma = chroma(pixels, width, x, line, 2, 1);
+                    let data = &mut row[x / 2 * 4..];
+                    data[y0 as usize] = params.compress_y(pixels_row[x][0]);
+                    if let Some(pixel) = pixels_row.get(x + 1) {
+                        data[y1 as usize] = params.compress_y(pixel[0]);
+                    }
+                    if let Some(data) = data.get_mut(u as usize) {
+                        *data = params.compress_c(chroma[1]);
+                    }
+                    if let Some(data) = data.get_mut(v as usize) {
+                        *data = params.compress_c(chroma[2]);
+                    }
+                }
+            }
+            Packing::SemiPlanar { .. } | Packing::Planar { .. } => {
+                let row = params.row_mut(dst, 0, line);
+                for (data, pixel) in row.iter_mut().zip(pixels_row) {
+                    *data = params.compress_y(pixel[0]);
+                }
+            }
+        }
+    }
+
+    match params.packing {
+        Packing::SemiPlanar { vsub, swap } => {
+            let vsub = vsub as usize;
+            let (u, v) = if swap { (1, 0) } else { (0, 1) };
+            for line in 0..height.div_ceil(vsub) {
+                let row = params.row_mut(dst, 1, line);
+                for x in (0..width).step_by(2) {
+                    let chroma = chroma(pixels, width, x, line * vsub, 2, vsub);
+                    row[x + u] = params.compress_c(chroma[1]);
+                    row[x + v] = params.compress_c(chroma[2]);
+                }
+            }
+        }
+        Packing::Planar { swap } => {
+            let (u, v) = if swap { (2, 1) } else { (1, 2) };
+            for line in 0..height.div_ceil(2) {
+                for x in (0..width).step_by(2) {
+                    let chroma = chroma(pixels, width, x, line * 2, 2, 2);
+                    params.row_mut(dst, u, line)[x / 2] = params.compress_c(chroma[1]);
+                    params.row_mut(dst, v, line)[x / 2] = params.compress_c(chroma[2]);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Describe packed R'G'B' image without padding
+#[cfg(feature = "image")]
+fn rgb_format(width: u32, height: u32) -> PixFormat {
+    let mut format = PixFormat::from(FourCc::Rgb24);
+    if let Some(layout) = PlaneLayout::compute(FourCc::Rgb24, width, height, 1) {
+        layout.fill_pix_format(&mut format);
+    }
+    format
+}
+
+/// Convert image to [image::DynamicImage]
+///
+/// Grey and 16-bit luma formats are kept as is, other supported
+/// formats are converted to 8-bit R'G'B' without alpha.
+#[cfg(feature = "image")]
+pub fn to_image(src: impl AsRef<[u8]>, src_fmt: &PixFormat) -> Result<image::DynamicImage> {
+    use image::{DynamicImage, ImageBuffer};
+
+    let src = src.as_ref();
+    let width = src_fmt.width();
+    let height = src_fmt.height();
+
+    let luma_rows = || {
+        crate::FrameFormat::from(*src_fmt)
+            .rows(src, 0)
+            .ok_or_else(|| utils::invalid_input("Source buffer too small"))
+    };
+
+    let luma16 = |be: bool| -> Result<_> {
+        let data = luma_rows()?
+            .flat_map(|row| row.chunks_exact(2))
+            .map(|pair| {
+                let pair = [pair[0], pair[1]];
+                if be {
+                    u16::from_be_bytes(pair)
+                } else {
+                    u16::from_le_bytes(pair)
+                }
+            })
+            .collect();
+        Ok(DynamicImage::ImageLuma16(
+            ImageBuffer::from_raw(width, height, data).unwrap(),
+        ))
+    };
+
+    Ok(match src_fmt.pixel_format() {
+        FourCc::Grey => {
+            let data = luma_rows()?.flatten().copied().collect();
+            DynamicImage::ImageLuma8(ImageBuffer::from_raw(width, height, data).unwrap())
+        }
+        FourCc::Y16 => luma16(false)?,
+        FourCc::Y16be => luma16(true)?,
+        _ => DynamicImage::ImageRgb8(to_rgb_image(src, src_fmt)?),
+    })
+}
+
+/// Convert image to [image::RgbImage]
+#[cfg(feature = "image")]
+pub fn to_rgb_image(src: impl AsRef<[u8]>, src_fmt: &PixFormat) -> Result<image::RgbImage> {
+    let dst_fmt = rgb_format(src_fmt.width(), src_fmt.height());
+    let mut data = vec![0; dst_fmt.size_image() as usize];
+
+    convert(src, src_fmt, &mut data, &dst_fmt)?;
+
+    Ok(image::RgbImage::from_raw(src_fmt.width(), src_fmt.height(), data).unwrap())
+}
+
+/// Fill buffer from [image::RgbImage]
+///
+/// The image is converted to `dst_fmt`, sizes of images must be equal.
+#[cfg(feature = "image")]
+pub fn from_rgb_image(
+    image: &image::RgbImage,
+    dst: impl AsMut<[u8]>,
+    dst_fmt: &PixFormat,
+) -> Result<()> {
+    let src_fmt = rgb_format(image.width(), image.height());
+
+    convert(image.as_raw(), &src_fmt, dst, dst_fmt)
+}
@@ -0,0 +1,408 @@
+//! Structured device errors
+//!
+//! Failed device calls is reported as [std::io::Error] which wraps [Error],
+//! so existing code which checks [std::io::ErrorKind] keeps working.
+//! Use [Error::from] to get the details of failure back.
+//!
+//! Failures which is fully described by error number is reported as plain OS errors,
+//! so [std::io::Error::raw_os_error] works for them too.
+//! Use [Error::errno] to get error number of any failure.
+
+use crate::{calls, types::*};
+use nix::{errno::Errno, libc::c_int};
+use std::io;
+
+/// Category of failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Device is used by another handle or it holds higher priority
+    Busy,
+    /// Operation, format or control is not supported by device
+    Unsupported,
+    /// Invalid argument
+    InvalidArgument,
+    /// Control is read-only
+    ReadOnly,
+    /// Control is write-only
+    WriteOnly,
+    /// Value is out of range
+    OutOfRange,
+    /// Not enough bandwidth or memory for streaming
+    NoSpace,
+    /// Last buffer of stream is dequeued
+    Drained,
+    /// Device is unplugged
+    Disconnected,
+    /// Operation would block
+    WouldBlock,
+    /// Operation is interrupted
+    Interrupted,
+    /// Waiting is cancelled through cancel handle
+    Cancelled,
+    /// Operation is not permitted
+    PermissionDenied,
+    /// Other failure
+    Other,
+}
+
+impl ErrorKind {
+    /// Categorize error number of device call
+    pub fn new(nr: u8, errno: Errno) -> Self {
+        match errno {
+            Errno::EBUSY => Self::Busy,
+            Errno::ENODEV | Errno::ENXIO => Self::Disconnected,
+            Errno::ENOTTY | Errno::ENOSYS | Errno::EOPNOTSUPP => Self::Unsupported,
+            Errno::EINVAL => match nr {
+                calls::s_fmt::NR
+                | calls::try_fmt::NR
+                | calls::s_parm::NR
+                | calls::s_std::NR
+                | calls::s_input::NR
+                | calls::s_output::NR
+                | calls::enum_fmt::NR
+                | calls::enum_frame_sizes::NR
+                | calls::enum_frame_intervals::NR
+                | calls::query_ctrl::NR
+                | calls::query_ext_ctrl::NR
+                | calls::query_menu::NR
+                | calls::req_bufs::NR
+                | calls::create_bufs::NR
+                | calls::subscribe_event::NR => Self::Unsupported,
+                _ => Self::InvalidArgument,
+            },
+            Errno::EACCES => match nr {
+                calls::s_ctrl::NR | calls::s_ext_ctrls::NR | calls::try_ext_ctrls::NR => {
+                    Self::ReadOnly
+                }
+                calls::g_ctrl::NR | calls::g_ext_ctrls::NR => Self::WriteOnly,
+                _ => Self::PermissionDenied,
+            },
+            Errno::EPERM => Self::PermissionDenied,
+            Errno::ERANGE => Self::OutOfRange,
+            Errno::ENOSPC | Errno::ENOMEM => Self::NoSpace,
+            Errno::EPIPE => Self::Drained,
+            Errno::EAGAIN => Self::WouldBlock,
+            Errno::EINTR => Self::Interrupted,
+            _ => Self::Other,
+        }
+    }
+
+    /// Categorize any I/O error
+    pub fn of(error: &io::Error) -> Self {
+        if let Some(error) = Error::from_ref(error) {
+            return error.kind();
+        }
+
+        if let Some(errno) = error.raw_os_error() {
+            return Self::new(0, Errno::from_i32(errno));
+        }
+
+        match error.kind() {
+            io::ErrorKind::ResourceBusy => Self::Busy,
+            io::ErrorKind::Unsupported => Self::Unsupported,
+            io::ErrorKind::InvalidInput => Self::InvalidArgument,
+            io::ErrorKind::WouldBlock => Self::WouldBlock,
+            io::ErrorKind::Interrupted => Self::Interrupted,
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Busy => "device is busy",
+            Self::Unsupported => "not supported",
+            Self::InvalidArgument => "invalid argument",
+            Self::ReadOnly => "control is read-only",
+            Self::WriteOnly => "control is write-only",
+            Self::OutOfRange => "value is out of range",
+            Self::NoSpace => "not enough bandwidth",
+            Self::Drained => "stream is drained",
+            Self::Disconnected => "device is disconnected",
+            Self::WouldBlock => "operation would block",
+            Self::Interrupted => "operation interrupted",
+            Self::Cancelled => "waiting cancelled",
+            Self::PermissionDenied => "permission denied",
+            Self::Other => "device error",
+        }
+        .fmt(f)
+    }
+}
+
+/// The object which device call is applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Subject {
+    /// Device itself
+    Device,
+    /// Control with identifier
+    Control(u32),
+    /// Format or parameters of buffers
+    Format(BufferType),
+    /// Queue of buffers
+    Queue(BufferType),
+    /// Buffer in queue
+    Buffer(BufferType, u32),
+}
+
+impl core::fmt::Display for Subject {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Device => "device".fmt(f),
+            Self::Control(id) => match CtrlId::try_from(*id) {
+                Ok(id) => write!(f, "control {id}"),
+                Err(id) => write!(f, "control {id:#010x}"),
+            },
+            Self::Format(type_) => write!(f, "format of {type_}"),
+            Self::Queue(type_) => write!(f, "queue of {type_}"),
+            Self::Buffer(type_, index) => write!(f, "buffer {index} of {type_}"),
+        }
+    }
+}
+
+/// Get subject of device call
+pub(crate) trait GetSubject {
+    /// The subject after call with request number
+    fn subject(&self, _nr: u8) -> Subject {
+        Subject::Device
+    }
+}
+
+macro_rules! subject_impls {
+    ($($type:ty),*) => {
+        $(impl GetSubject for $type {})*
+    };
+
+    ($($type:ty: $this:ident => $subject:expr),* $(,)?) => {
+        $(
+            impl GetSubject for $type {
+                fn subject(&self, _nr: u8) -> Subject {
+                    let $this = self;
+                    $subject
+                }
+            }
+        )*
+    };
+}
+
+subject_impls!(
+    (),
+    Audio,
+    AudioOut,
+    Capability,
+    DecoderCmd,
+    DvTimings,
+    DvTimingsCap,
+    Edid,
+    EncIdx,
+    EncoderCmd,
+    EnumDvTimings,
+    Event,
+    FrameBuffer,
+    FrmIvalEnum,
+    FrmSizeEnum,
+    Frequency,
+    FrequencyBand,
+    HwFreqSeek,
+    Input,
+    JpegCompression,
+    Modulator,
+    Output,
+    Priority,
+    SlicedVbiCap,
+    Standard,
+    StdId,
+    Tuner
+);
+
+subject_impls! {
+    Format: this => Subject::Format(this.type_),
+    FmtDesc: this => Subject::Format(this.type_),
+    StreamParm: this => Subject::Format(this.type_),
+    Crop: this => Subject::Format(this.type_),
+    CropCap: this => Subject::Format(this.type_),
+    Selection: this => Subject::Format(this.type_),
+    RequestBuffers: this => Subject::Queue(this.type_),
+    CreateBuffers: this => Subject::Queue(this.format.type_),
+    RemoveBuffers: this => Subject::Queue(this.type_),
+    Buffer: this => Subject::Buffer(this.type_, this.index),
+    ExportBuffer: this => Subject::Buffer(this.type_, this.index),
+    Control: this => Subject::Control(this.id),
+    QueryCtrl: this => Subject::Control(this.id),
+    QueryExtCtrl: this => Subject::Control(this.id),
+    QueryMenu: this => Subject::Control(this.id),
+    EventSubscription: this => if this.type_ == EventType::Ctrl as u32 {
+        Subject::Control(this.id)
+    } else {
+        Subject::Device
+    },
+}
+
+impl GetSubject for c_int {
+    fn subject(&self, nr: u8) -> Subject {
+        match nr {
+            calls::stream_on::NR | calls::stream_off::NR => BufferType::try_from(*self as u32)
+                .map(Subject::Queue)
+                .unwrap_or(Subject::Device),
+            _ => Subject::Device,
+        }
+    }
+}
+
+impl GetSubject for ExtControls {
+    fn subject(&self, _nr: u8) -> Subject {
+        // error index equals to count when failure cannot be attributed to single control
+        let index = if self.count == 1 { 0 } else { self.error_idx };
+        if self.controls.is_null() || index >= self.count {
+            return Subject::Device;
+        }
+        Subject::Control(unsafe { (*self.controls.add(index as _)).id })
+    }
+}
+
+/// Device error
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Device call is failed
+    Call {
+        /// Name of call
+        call: &'static str,
+        /// Object which call is applied to
+        subject: Subject,
+        /// Category of failure
+        kind: ErrorKind,
+        /// Error number
+        errno: Errno,
+    },
+    /// Waiting is cancelled
+    Cancelled,
+    /// Other I/O error
+    Io(io::Error),
+}
+
+impl Error {
+    pub(crate) fn call<T: GetSubject>(
+        call: &'static str,
+        nr: u8,
+        data: Option<&T>,
+        errno: Errno,
+    ) -> Self {
+        Self::Call {
+            call,
+            subject: data.map(|data| data.subject(nr)).unwrap_or(Subject::Device),
+            kind: ErrorKind::new(nr, errno),
+            errno,
+        }
+    }
+
+    /// Get error details from I/O error
+    pub fn from_ref(error: &io::Error) -> Option<&Self> {
+        error.get_ref()?.downcast_ref()
+    }
+
+    /// Category of failure
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Call { kind, .. } => *kind,
+            Self::Cancelled => ErrorKind::Cancelled,
+            Self::Io(error) => ErrorKind::of(error),
+        }
+    }
+
+    /// Name of failed call
+    pub fn call_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Call { call, .. } => Some(call),
+            Self::Cancelled | Self::Io(_) => None,
+        }
+    }
+
+    /// Object which failed call is applied to
+    pub fn subject(&self) -> Subject {
+        match self {
+            Self::Call { subject, .. } => *subject,
+            Self::Cancelled | Self::Io(_) => Subject::Device,
+        }
+    }
+
+    /// Error number
+    pub fn errno(&self) -> Option<Errno> {
+        match self {
+            Self::Call { errno, .. } => Some(*errno),
+            Self::Cancelled => None,
+            Self::Io(error) => error.raw_os_error().map(Errno::from_i32),
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Call {
+                call,
+                subject,
+                kind,
+                errno,
+            } => {
+                call.fmt(f)?;
+                if !matches!(subject, Subject::Device) {
+                    " (".fmt(f)?;
+                    subject.fmt(f)?;
+                    ')'.fmt(f)?;
+                }
+                ": ".fmt(f)?;
+                kind.fmt(f)?;
+                " (".fmt(f)?;
+                errno.fmt(f)?;
+                ')'.fmt(f)
+            }
+            Self::Cancelled => ErrorKind::Cancelled.fmt(f),
+            Self::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Call { .. } | Self::Cancelled => None,
+            Self::Io(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        match error.get_ref().map(|inner| inner.is::<Self>()) {
+            Some(true) => *error.into_inner().unwrap().downcast::<Self>().unwrap(),
+            _ => Self::Io(error),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Call {
+                subject: Subject::Device,
+                kind,
+                errno,
+                ..
+            } if kind == ErrorKind::new(0, errno) => {
+                // error number tells everything so keep it as is
+                io::Error::from_raw_os_error(errno as _)
+            }
+            Error::Call { errno, .. } => {
+                // keep the kind which corresponds to error number
+                let kind = io::Error::from_raw_os_error(errno as _).kind();
+                io::Error::new(kind, error)
+            }
+            Error::Cancelled => io::Error::other(error),
+            Error::Io(error) => error,
+        }
+    }
+}
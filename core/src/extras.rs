@@ -3,3 +3,6 @@ mod drm_fourcc_ops;
 
 #[cfg(feature = "dcv-color-primitives")]
 mod dcv_color_primitives_ops;
+
+#[cfg(feature = "image")]
+mod image_ops;
@@ -0,0 +1,30 @@
+use crate::{convert, Frame, Method, Out, OwnedFrame, Result};
+use image::{DynamicImage, RgbImage};
+
+impl<'r, Dir, Met: Method> Frame<'r, Dir, Met> {
+    /// Convert frame to image
+    pub fn to_image(&self) -> Result<DynamicImage> {
+        convert::to_image(self, self.format().pix_format())
+    }
+}
+
+impl<'r, Met: Method> Frame<'r, Out, Met> {
+    /// Fill frame from image
+    ///
+    /// Image is converted to frame format and used size of buffer is updated.
+    pub fn fill_from_image(&mut self, image: &RgbImage) -> Result<()> {
+        let format = *self.format().pix_format();
+        let capacity = self.capacity();
+        self.set_len(capacity);
+        convert::from_rgb_image(image, &mut *self, &format)?;
+        self.set_len(format.size_image() as _);
+        Ok(())
+    }
+}
+
+impl OwnedFrame {
+    /// Convert frame to image
+    pub fn to_image(&self) -> Result<DynamicImage> {
+        convert::to_image(self, self.format().pix_format())
+    }
+}
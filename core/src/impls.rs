@@ -2,12 +2,21 @@ mod buffer;
 mod capability;
 mod control;
 mod controls;
+mod discovery;
 mod events;
 mod format;
 mod formats;
+mod frame;
 mod frmivals;
 mod frmsizes;
+mod hotplug;
+mod layout;
+mod priority;
+mod reconnect;
+mod shared;
+mod stats;
 mod streamparm;
+mod sync;
 mod timestamp;
 mod types;
 
@@ -15,11 +24,19 @@ pub use buffer::*;
 pub use capability::*;
 pub use control::*;
 pub use controls::*;
+pub use discovery::*;
 pub use events::*;
 pub use format::*;
 pub use formats::*;
+pub use frame::*;
 pub use frmivals::*;
 pub use frmsizes::*;
+pub use hotplug::*;
+pub use layout::*;
+pub use reconnect::*;
+pub use shared::*;
+pub use stats::*;
 pub use streamparm::*;
+pub use sync::*;
 pub use timestamp::*;
 pub use types::*;
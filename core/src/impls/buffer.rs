@@ -1,19 +1,30 @@
+use super::{
+    shared::{BufferReturns, Lent, ReturnsSender},
+    stats::StreamStats,
+    timestamp::{monotonic_time, timestamp_instant, timestamp_system_time},
+};
 use crate::{
     calls,
     safe_ref::{Lock, Mut, Ref},
     types::*,
-    ContentType, Direction, DirectionImpl, In, Internal, IsTimestamp, MethodImpl, Out, Result,
+    utils, ContentType, Direction, DirectionImpl, In, Internal, IsTimestamp, MethodImpl, Out,
+    Result, TimestampKind,
 };
 use core::{
     marker::PhantomData,
     mem::{ManuallyDrop, MaybeUninit},
-    num::NonZeroUsize,
+    ops::Range,
+    time::Duration,
 };
 use getset::CopyGetters;
 use std::{
     collections::VecDeque,
     os::unix::io::RawFd,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Instant, SystemTime},
 };
 
 impl Internal<RequestBuffers> {
@@ -31,12 +42,68 @@ impl Internal<RequestBuffers> {
     }
 }
 
+impl Internal<CreateBuffers> {
+    /// Create additional buffers
+    pub fn create(fd: RawFd, memory: Memory, count: u32, format: &Format) -> Result<Self> {
+        let create_bufs = MaybeUninit::<CreateBuffers>::zeroed();
+
+        unsafe_call!({
+            let mut create_bufs = create_bufs.assume_init();
+            create_bufs.memory = memory as _;
+            create_bufs.count = count;
+            create_bufs.format = *format;
+            calls::create_bufs(fd, &mut create_bufs).map(|_| create_bufs.into())
+        })
+    }
+}
+
+impl Internal<RemoveBuffers> {
+    /// Remove buffers
+    pub fn remove(fd: RawFd, type_: BufferType, index: u32, count: u32) -> Result<()> {
+        let remove_bufs = MaybeUninit::<RemoveBuffers>::zeroed();
+
+        unsafe_call!({
+            let mut remove_bufs = remove_bufs.assume_init();
+            remove_bufs.type_ = type_;
+            remove_bufs.index = index;
+            remove_bufs.count = count;
+            calls::remove_bufs(fd, &mut remove_bufs).map(|_| ())
+        })
+    }
+}
+
 impl Buffer {
     /// Get timestamp
+    ///
+    /// Returns raw value, see [Buffer::timestamp_kind] to determine its clock.
     pub fn timestamp<T: IsTimestamp>(&self) -> T {
         T::from_time_val(self.timestamp)
     }
 
+    /// Get kind of timestamp
+    pub fn timestamp_kind(&self) -> TimestampKind {
+        self.flags.into()
+    }
+
+    /// Get monotonic timestamp as instant
+    ///
+    /// Returns [None] when timestamp is not monotonic.
+    pub fn instant(&self) -> Option<Instant> {
+        timestamp_instant(self.timestamp, self.flags)
+    }
+
+    /// Get monotonic timestamp as system time
+    ///
+    /// Returns [None] when timestamp is not monotonic.
+    pub fn system_time(&self) -> Option<SystemTime> {
+        timestamp_system_time(self.timestamp, self.flags)
+    }
+
+    /// Set sequence number
+    pub fn set_sequence(&mut self, sequence: u32) {
+        self.sequence = sequence;
+    }
+
     /// Set timestamp
     pub fn set_timestamp<T: IsTimestamp>(&mut self, time: T) {
         self.timestamp = time.into_time_val();
@@ -67,6 +134,13 @@ impl Buffer {
         }
     }
 
+    /// Buffer field
+    ///
+    /// Returns raw value when field order is unknown.
+    pub fn field(&self) -> core::result::Result<Field, u32> {
+        Field::try_from(self.field)
+    }
+
     /// Is buffer locked by driver
     pub fn is_queued(&self) -> bool {
         self.flags.contains(BufferFlag::Queued)
@@ -97,9 +171,16 @@ impl core::fmt::Display for Buffer {
             ' '.fmt(f)?;
             self.flags.fmt(f)?;
         }
-        if self.field != Field::None {
-            ' '.fmt(f)?;
-            self.field.fmt(f)?;
+        match self.field() {
+            Ok(Field::None) => (),
+            Ok(field) => {
+                ' '.fmt(f)?;
+                field.fmt(f)?;
+            }
+            Err(field) => {
+                ' '.fmt(f)?;
+                field.fmt(f)?;
+            }
         }
         Ok(())
     }
@@ -133,6 +214,11 @@ impl Internal<Buffer> {
         unsafe_call!(calls::dq_buf(fd, self.as_mut()).map(|_| ()))
     }
 
+    /// Prepare buffer for queueing
+    pub fn prepare(&mut self, fd: RawFd) -> Result<()> {
+        unsafe_call!(calls::prepare_buf(fd, self.as_mut()).map(|_| ()))
+    }
+
     pub fn mark_dequeued(&mut self) {
         self.flags &= !BufferFlag::Queued;
     }
@@ -154,17 +240,8 @@ impl Method for Mmap {
 
 impl MethodImpl for Mmap {
     fn init(buffer: &Buffer, fd: RawFd) -> Result<*mut u8> {
-        use nix::sys::mman::{mmap, MapFlags, ProtFlags};
-
-        unsafe_call!(mmap(
-            None,
-            NonZeroUsize::new(buffer.length as _).unwrap(),
-            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
-            MapFlags::MAP_SHARED,
-            fd,
-            buffer.m.offset as _,
-        ))
-        .map(|pointer| pointer as _)
+        unsafe_call!(calls::mmap(fd, buffer.length as _, buffer.m.offset as _))
+            .map(|pointer| pointer as _)
     }
 
     fn done(buffer: &Buffer, pointer: *mut u8) {
@@ -203,12 +280,46 @@ impl MethodImpl for UserPtr {
     }
 }
 
-struct BufferState<Met: Method> {
+/// Memory of buffer
+///
+/// Stays valid while any buffer handle is alive even when queue is already deleted.
+pub(super) struct Mapping<Met: Method> {
     pointer: *mut u8,
     buffer: Internal<Buffer>,
     _phantom: PhantomData<Met>,
 }
 
+unsafe impl<Met: Method> Send for Mapping<Met> {}
+unsafe impl<Met: Method> Sync for Mapping<Met> {}
+
+impl<Met: Method> Mapping<Met> {
+    fn new(fd: RawFd, buffer: &Internal<Buffer>) -> Result<Self> {
+        let pointer = Met::init(buffer, fd)?;
+
+        Ok(Self {
+            pointer,
+            buffer: *buffer,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Pointer to buffer data
+    pub(super) fn pointer(&self) -> *mut u8 {
+        self.pointer
+    }
+}
+
+impl<Met: Method> Drop for Mapping<Met> {
+    fn drop(&mut self) {
+        Met::done(&self.buffer, self.pointer)
+    }
+}
+
+struct BufferState<Met: Method> {
+    mapping: Arc<Mapping<Met>>,
+    buffer: Internal<Buffer>,
+}
+
 unsafe impl<Met: Method> Send for BufferState<Met> {}
 
 impl<Met: Method> core::ops::Deref for BufferState<Met> {
@@ -227,20 +338,19 @@ impl<Met: Method> core::ops::DerefMut for BufferState<Met> {
 
 impl<Met: Method> BufferState<Met> {
     fn new(fd: RawFd, buffer: Internal<Buffer>) -> Result<Self> {
-        let pointer = Met::init(&buffer, fd)?;
+        let mapping = Arc::new(Mapping::new(fd, &buffer)?);
 
-        let data = Self {
-            pointer,
-            buffer,
-            _phantom: PhantomData,
-        };
+        Ok(Self { mapping, buffer })
+    }
 
-        Ok(data)
+    fn prepare(&mut self, fd: RawFd) -> Result<()> {
+        Met::update(&mut self.buffer, self.mapping.pointer());
+        self.buffer.prepare(fd)
     }
 
     fn enqueue(&mut self, fd: RawFd) -> Result<()> {
         // update buffer data
-        Met::update(&mut self.buffer, self.pointer);
+        Met::update(&mut self.buffer, self.mapping.pointer());
         // add buffer to queue
         self.buffer.queue(fd)
     }
@@ -254,20 +364,25 @@ impl<Met: Method> BufferState<Met> {
     }
 }
 
-impl<Met: Method> Drop for BufferState<Met> {
-    fn drop(&mut self) {
-        Met::done(&self.buffer, self.pointer)
-    }
-}
-
 #[derive(CopyGetters)]
 pub struct QueueData<Dir, Met: Method> {
     /// Requested buffers
-    buffers: Vec<Ref<Mut<BufferState<Met>>>>,
+    ///
+    /// Removed buffers is [None].
+    buffers: Vec<Option<Ref<Mut<BufferState<Met>>>>>,
 
     /// Dequeued buffers indexes
     dequeued: Mut<VecDeque<u32>>,
 
+    /// Buffers lent to other threads
+    pub(super) lent: Mut<VecDeque<Lent<Dir, Met>>>,
+
+    /// Channel to return lent buffers
+    pub(super) returns: (ReturnsSender, BufferReturns),
+
+    /// Frames statistics
+    stats: Mut<Option<StreamStats>>,
+
     /// Stream on flag
     on: AtomicBool,
 
@@ -281,12 +396,34 @@ pub struct QueueData<Dir, Met: Method> {
 impl<Dir, Met: Method> QueueData<Dir, Met> {
     /// Queue is empty
     pub fn is_empty(&self) -> bool {
-        self.buffers.is_empty()
+        self.len() == 0
     }
 
     /// Get actual number of buffers
     pub fn len(&self) -> usize {
-        self.buffers.len()
+        self.buffers.iter().flatten().count()
+    }
+
+    /// Get number of buffers lent to other threads
+    pub fn lent_len(&self) -> usize {
+        self.lent.lock().len()
+    }
+
+    /// Start collecting frames statistics
+    ///
+    /// Collected statistics will be reset.
+    pub fn enable_stats(&self, time_per_frame: Option<Duration>) {
+        *self.stats.lock() = Some(StreamStats::new(time_per_frame));
+    }
+
+    /// Stop collecting frames statistics
+    pub fn disable_stats(&self) {
+        *self.stats.lock() = None;
+    }
+
+    /// Get copy of collected frames statistics
+    pub fn stats(&self) -> Option<StreamStats> {
+        self.stats.lock().clone()
     }
 }
 
@@ -310,12 +447,15 @@ impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
             buffer.query(fd)?;
             let data = BufferState::new(fd, buffer)?;
 
-            buffers.push(Ref::new(Mut::new(data)));
+            buffers.push(Some(Ref::new(Mut::new(data))));
         }
 
         Ok(QueueData {
             buffers,
             dequeued: Mut::new(VecDeque::with_capacity(count as _)),
+            lent: Mut::new(VecDeque::with_capacity(count as _)),
+            returns: ReturnsSender::new()?,
+            stats: Mut::new(None),
             on: AtomicBool::new(false),
             buffer_type: buffer_type.into(),
             _phantom: PhantomData,
@@ -333,9 +473,111 @@ impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
         Ok(())
     }
 
+    /// Create additional buffers
+    ///
+    /// Buffers size is calculated according to format which may differ from current.
+    /// Returns indexes of created buffers.
+    pub fn create(&mut self, fd: RawFd, count: u32, format: &Format) -> Result<Range<u32>>
+    where
+        Dir: Direction,
+    {
+        if format.type_() != *self.buffer_type {
+            return Err(utils::invalid_input("Format does not match buffers type"));
+        }
+
+        let create_buffers = Internal::<CreateBuffers>::create(fd, Met::MEMORY, count, format)?;
+        let indexes = create_buffers.index..create_buffers.index + create_buffers.count;
+
+        for index in indexes.clone() {
+            let mut buffer = Internal::<Buffer>::new(*self.buffer_type, Met::MEMORY, index);
+            buffer.query(fd)?;
+            let data = BufferState::new(fd, buffer)?;
+
+            if self.buffers.len() <= index as usize {
+                self.buffers.resize_with(index as usize + 1, || None);
+            }
+            self.buffers[index as usize] = Some(Ref::new(Mut::new(data)));
+
+            if Dir::IN {
+                // capture buffers will be enqueued on getting next buffer
+                self.dequeued.lock().push_back(index);
+            }
+        }
+
+        Ok(indexes)
+    }
+
+    /// Remove buffers
+    ///
+    /// Buffers should not be queued or held by user.
+    /// Requires kernel support of `VIDIOC_REMOVE_BUFS`.
+    pub fn remove(&mut self, fd: RawFd, indexes: Range<u32>) -> Result<()> {
+        for (index, buffer_ref) in self.iter_buffers() {
+            if indexes.contains(&index) {
+                if Ref::strong_count(buffer_ref) > 1 {
+                    return Err(utils::resource_busy("Buffer is held"));
+                }
+                if buffer_ref.lock().is_queued() {
+                    return Err(utils::resource_busy("Buffer is queued"));
+                }
+            }
+        }
+
+        Internal::<RemoveBuffers>::remove(
+            fd,
+            *self.buffer_type,
+            indexes.start,
+            indexes.end.saturating_sub(indexes.start),
+        )?;
+
+        for index in indexes.clone() {
+            if let Some(buffer_ref) = self.buffers.get_mut(index as usize) {
+                *buffer_ref = None;
+            }
+        }
+        while let Some(None) = self.buffers.last() {
+            self.buffers.pop();
+        }
+        self.dequeued
+            .lock()
+            .retain(|index| !indexes.contains(index));
+
+        Ok(())
+    }
+
+    /// Prepare capture buffers which is waiting for enqueueing
+    ///
+    /// Prepared buffers is enqueued faster.
+    /// Returns number of prepared buffers.
+    pub fn prepare_buffers(&self, fd: RawFd) -> Result<usize>
+    where
+        Dir: Direction,
+    {
+        if !Dir::IN {
+            // output buffers should be prepared with actual data
+            return Ok(0);
+        }
+
+        let dequeued = self.dequeued.lock().iter().copied().collect::<Vec<_>>();
+        let mut count = 0;
+
+        for index in dequeued {
+            let buffer_ref = self.buffer(index)?;
+            if Ref::strong_count(buffer_ref) == 1 {
+                let mut buffer_data = buffer_ref.lock();
+                if !buffer_data.flags.contains(BufferFlag::Prepared) {
+                    buffer_data.prepare(fd)?;
+                    count += 1;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+
     /// Is queue started
     #[inline(always)]
-    fn is_on(&self) -> bool {
+    pub fn is_on(&self) -> bool {
         self.on.load(Ordering::SeqCst)
     }
 
@@ -365,26 +607,44 @@ impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
         Ok(())
     }
 
+    /// Iterate over existing buffers with indexes
+    fn iter_buffers(&self) -> impl Iterator<Item = (u32, &Ref<Mut<BufferState<Met>>>)> {
+        self.buffers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, buffer_ref)| Some((index as u32, buffer_ref.as_ref()?)))
+    }
+
+    /// Get existing buffer by index
+    fn buffer(&self, index: u32) -> Result<&Ref<Mut<BufferState<Met>>>> {
+        self.buffers
+            .get(index as usize)
+            .and_then(Option::as_ref)
+            .ok_or_else(|| utils::invalid_data("Unknown buffer index"))
+    }
+
     /// Dequeue all buffers
     fn dequeue_all(&self) {
-        for index in 0..self.buffers.len() {
-            let buffer_ref = &self.buffers[index];
+        for (index, buffer_ref) in self.iter_buffers() {
             if Ref::strong_count(buffer_ref) == 1 {
                 buffer_ref.lock().mark_dequeued();
-                self.dequeued.lock().push_back(index as _);
+                let mut dequeued = self.dequeued.lock();
+                // buffers may be already dequeued by stopping stream
+                if !dequeued.contains(&index) {
+                    dequeued.push_back(index);
+                }
             }
         }
     }
 
     /// Dequeue queued buffers
     fn dequeue_queued(&self) {
-        for index in 0..self.buffers.len() {
-            let buffer_ref = &self.buffers[index];
+        for (index, buffer_ref) in self.iter_buffers() {
             if Ref::strong_count(buffer_ref) == 1 {
                 let mut buffer_data = buffer_ref.lock();
                 if buffer_data.is_queued() {
                     buffer_data.mark_dequeued();
-                    self.dequeued.lock().push_back(index as _);
+                    self.dequeued.lock().push_back(index);
                 }
             }
         }
@@ -392,10 +652,14 @@ impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
 
     /// Dequeue single unused buffer
     fn dequeue_unused(&self) -> Option<BufferRef<Dir, Met>> {
-        for index in 0..self.buffers.len() {
-            let buffer_ref = &self.buffers[index];
+        for (index, buffer_ref) in self.iter_buffers() {
             if Ref::strong_count(buffer_ref) == 1 && !buffer_ref.lock().is_queued() {
-                self.dequeued.lock().push_back(index as _);
+                let mut dequeued = self.dequeued.lock();
+                // skip buffers which is waiting for enqueueing
+                if dequeued.contains(&index) {
+                    continue;
+                }
+                dequeued.push_back(index);
                 return Some(BufferRef::new(buffer_ref));
             }
         }
@@ -403,14 +667,38 @@ impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
     }
 
     /// Enqueue ready dequeued buffers
-    fn enqueue_ready(&self, fd: RawFd) -> Result<()> {
+    fn enqueue_ready(&self, fd: RawFd) -> Result<()>
+    where
+        Dir: Direction,
+    {
+        if Dir::IN {
+            // capture buffers may be released in any order
+            // (i.e. by different threads)
+            let mut position = 0;
+            while let Some(index) = {
+                let dequeued = self.dequeued.lock();
+                dequeued.get(position).copied()
+            } {
+                let buffer_ref = self.buffer(index)?;
+                if Ref::strong_count(buffer_ref) == 1 {
+                    let mut buffer_data = buffer_ref.lock();
+                    buffer_data.enqueue(fd)?;
+                    self.dequeued.lock().remove(position);
+                } else {
+                    position += 1;
+                }
+            }
+
+            return Ok(());
+        }
+
         // we need enqueue only first N buffers which is ready
         // (already processed by user)
         while let Some(first) = {
             let dequeued = self.dequeued.lock();
             dequeued.front().copied()
         } {
-            let buffer_ref = &self.buffers[first as usize];
+            let buffer_ref = self.buffer(first)?;
             if Ref::strong_count(buffer_ref) == 1 {
                 let mut buffer_data = buffer_ref.lock();
                 buffer_data.enqueue(fd)?;
@@ -428,57 +716,113 @@ impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
     fn dequeue(&self, fd: RawFd) -> Result<BufferRef<Dir, Met>> {
         let mut buffer = Internal::<Buffer>::new(*self.buffer_type, Met::MEMORY, 0);
         buffer.dequeue(fd)?;
-        let index = buffer.index as usize;
-        let buffer_ref = &self.buffers[index];
+        let index = buffer.index;
+        if let Some(stats) = &mut *self.stats.lock() {
+            stats.update(&buffer, monotonic_time());
+        }
+        let buffer_ref = self.buffer(index)?;
         if Ref::strong_count(buffer_ref) == 1 {
             buffer_ref.lock().reuse(buffer);
-            self.dequeued.lock().push_back(index as _);
+            self.dequeued.lock().push_back(index);
             Ok(BufferRef::new(buffer_ref))
         } else {
             unreachable!();
         }
     }
 
+    /// Start stream
+    ///
+    /// Does nothing when stream already started.
+    pub fn start(&self, fd: RawFd) -> Result<()>
+    where
+        Dir: Direction,
+    {
+        if self.is_on() {
+            return Ok(());
+        }
+        if Dir::IN {
+            // capture buffers should be queued before start
+            self.dequeue_all();
+            self.enqueue_ready(fd)?;
+        }
+        self.on(fd)
+    }
+
+    /// Stop stream
+    ///
+    /// Does nothing when stream already stopped.
+    pub fn stop(&self, fd: RawFd) -> Result<()> {
+        if !self.is_on() {
+            return Ok(());
+        }
+        self.off(fd)
+    }
+
+    /// Prepare queue to get next buffer
+    ///
+    /// Starts stream when needed. Returns buffer when it available without waiting.
+    pub fn prepare(&self, fd: RawFd) -> Result<Option<BufferRef<Dir, Met>>>
+    where
+        Dir: Direction,
+    {
+        Dir::prepare(self, fd)
+    }
+
+    /// Enqueue buffers which is released by user
+    ///
+    /// Output stream will be started when it is not started yet.
+    pub fn flush(&self, fd: RawFd) -> Result<()>
+    where
+        Dir: Direction,
+    {
+        self.enqueue_ready(fd)?;
+        if !Dir::IN && !self.is_on() {
+            self.start(fd)?;
+        }
+        Ok(())
+    }
+
     /// Get next buffer to read or write
     pub fn next(&self, fd: RawFd) -> Result<BufferRef<Dir, Met>>
     where
         Dir: Direction,
     {
-        Dir::next(self, fd)
+        if let Some(buffer) = self.prepare(fd)? {
+            Ok(buffer)
+        } else {
+            self.dequeue(fd)
+        }
     }
 }
 
 impl DirectionImpl for In {
-    fn next<Met: Method>(
+    fn prepare<Met: Method>(
         queue: &Internal<QueueData<Self, Met>>,
         fd: RawFd,
-    ) -> Result<BufferRef<Self, Met>> {
+    ) -> Result<Option<BufferRef<Self, Met>>> {
         if queue.is_on() {
             queue.enqueue_ready(fd)?;
         } else {
-            queue.dequeue_all();
-            queue.enqueue_ready(fd)?;
-            queue.on(fd)?;
+            queue.start(fd)?;
         }
-        queue.dequeue(fd)
+        Ok(None)
     }
 }
 
 impl DirectionImpl for Out {
-    fn next<Met: Method>(
+    fn prepare<Met: Method>(
         queue: &Internal<QueueData<Self, Met>>,
         fd: RawFd,
-    ) -> Result<BufferRef<Self, Met>> {
+    ) -> Result<Option<BufferRef<Self, Met>>> {
         queue.enqueue_ready(fd)?;
-        if queue.is_on() {
-            queue.dequeue(fd)
-        } else {
-            if let Some(buffer) = queue.dequeue_unused() {
-                return Ok(buffer);
-            }
-            queue.on(fd)?;
-            queue.dequeue(fd)
+        // created buffers may be available while streaming
+        if let Some(buffer) = queue.dequeue_unused() {
+            return Ok(Some(buffer));
         }
+        if !queue.is_on() {
+            queue.start(fd)?;
+        }
+        Ok(None)
     }
 }
 
@@ -511,6 +855,17 @@ impl<Dir, Met: Method> BufferRef<Dir, Met> {
             _phantom: PhantomData,
         })
     }
+
+    /// Get buffer memory with actual buffer metadata
+    pub(super) fn share(&self) -> (Arc<Mapping<Met>>, Internal<Buffer>) {
+        let data = self.data.lock();
+        (data.mapping.clone(), data.buffer)
+    }
+
+    /// Update buffer metadata
+    pub(super) fn restore(&self, buffer: Internal<Buffer>) {
+        self.data.lock().reuse(buffer)
+    }
 }
 
 impl<Dir, Met: Method> core::fmt::Display for BufferRef<Dir, Met> {
@@ -540,13 +895,13 @@ impl<'r, Met: Method> core::ops::DerefMut for BufferData<'r, Out, Met> {
 
 impl<'r, Dir, Met: Method> AsRef<[u8]> for BufferData<'r, Dir, Met> {
     fn as_ref(&self) -> &[u8] {
-        unsafe { core::slice::from_raw_parts(self.data.pointer, self.len()) }
+        unsafe { core::slice::from_raw_parts(self.data.mapping.pointer(), self.len()) }
     }
 }
 
 impl<'r, Met: Method> AsMut<[u8]> for BufferData<'r, Out, Met> {
     fn as_mut(&mut self) -> &mut [u8] {
-        unsafe { core::slice::from_raw_parts_mut(self.data.pointer, self.len()) }
+        unsafe { core::slice::from_raw_parts_mut(self.data.mapping.pointer(), self.len()) }
     }
 }
 
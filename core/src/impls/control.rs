@@ -42,8 +42,15 @@ trivial_impls! {
 }
 
 impl QueryCtrl {
+    /// Control type
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<CtrlType, u32> {
+        CtrlType::try_from(self.type_)
+    }
+
     pub fn is_menu(&self) -> bool {
-        self.type_.is_menu()
+        matches!(self.type_(), Ok(type_) if type_.is_menu())
     }
 
     pub fn has_payload(&self) -> bool {
@@ -58,7 +65,10 @@ impl core::fmt::Display for QueryCtrl {
             Err(id) => id.fmt(f),
         }?;
         f.write_str(": ")?;
-        self.type_().fmt(f)?;
+        match self.type_() {
+            Ok(type_) => type_.fmt(f),
+            Err(type_) => type_.fmt(f),
+        }?;
         f.write_str(" '")?;
         self.name().fmt(f)?;
         f.write_str("' ")?;
@@ -86,28 +96,28 @@ impl From<QueryCtrl> for QueryExtCtrl {
             type_: ctrl.type_,
             name: ctrl.name,
             min: ctrl.min as _,
-            max: if matches!(ctrl.type_, CtrlType::BitMask) {
+            max: if matches!(ctrl.type_(), Ok(CtrlType::BitMask)) {
                 ctrl.max as u32 as _
             } else {
                 ctrl.max as _
             },
-            default: if matches!(ctrl.type_, CtrlType::BitMask) {
+            default: if matches!(ctrl.type_(), Ok(CtrlType::BitMask)) {
                 ctrl.default as u32 as _
             } else {
                 ctrl.default as _
             },
             step: ctrl.step as _,
             flags: ctrl.flags
-                | if matches!(ctrl.type_, CtrlType::String) {
+                | if matches!(ctrl.type_(), Ok(CtrlType::String)) {
                     CtrlFlag::HasPayload
                 } else {
                     CtrlFlag::none()
                 },
             elems: 1,
             nr_of_dims: 0,
-            elem_size: match ctrl.type_ {
-                CtrlType::Integer64 => core::mem::size_of::<i64>() as _,
-                CtrlType::String => (ctrl.max + 1) as _,
+            elem_size: match ctrl.type_() {
+                Ok(CtrlType::Integer64) => core::mem::size_of::<i64>() as _,
+                Ok(CtrlType::String) => (ctrl.max + 1) as _,
                 _ => core::mem::size_of::<i32>() as _,
             },
             dims: [0; CTRL_MAX_DIMS],
@@ -180,8 +190,15 @@ trivial_impls! {
 }
 
 impl QueryExtCtrl {
+    /// Control type
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<CtrlType, u32> {
+        CtrlType::try_from(self.type_)
+    }
+
     pub fn is_menu(&self) -> bool {
-        self.type_.is_menu()
+        matches!(self.type_(), Ok(type_) if type_.is_menu())
     }
 
     pub fn has_payload(&self) -> bool {
@@ -206,7 +223,10 @@ impl core::fmt::Display for QueryExtCtrl {
             Err(id) => id.fmt(f),
         }?;
         f.write_str(": ")?;
-        self.type_().fmt(f)?;
+        match self.type_() {
+            Ok(type_) => type_.fmt(f),
+            Err(type_) => type_.fmt(f),
+        }?;
         f.write_str(" '")?;
         self.name().fmt(f)?;
         f.write_str("' ")?;
@@ -1,18 +1,63 @@
 use crate::{calls, types::*, Internal, Result};
-use core::mem::{ManuallyDrop, MaybeUninit};
+use core::mem::MaybeUninit;
 use std::os::unix::io::RawFd;
 
+/// Owned storage of control payload
+///
+/// Aligned to store compound control types.
+type Payload = Option<Box<[u64]>>;
+
+fn new_payload(ctrl: &QueryExtCtrl) -> Payload {
+    ctrl.has_payload()
+        .then(|| vec![0; (ctrl.size() as usize).div_ceil(8)].into_boxed_slice())
+}
+
+fn get_ext_ctrls(fd: RawFd, datas: &mut [Internal<ExtControl>]) -> Result<()> {
+    let ctrls = MaybeUninit::<ExtControls>::zeroed();
+
+    unsafe_call!({
+        let mut ctrls = ctrls.assume_init();
+
+        ctrls.count = datas.len() as _;
+        ctrls.controls = datas.as_mut_ptr() as _;
+
+        calls::g_ext_ctrls(fd, &mut ctrls).map(|_| ())
+    })
+}
+
+fn set_ext_ctrls(fd: RawFd, datas: &[Internal<ExtControl>]) -> Result<()> {
+    let ctrls = MaybeUninit::<ExtControls>::zeroed();
+
+    unsafe_call!({
+        let mut ctrls = ctrls.assume_init();
+
+        ctrls.count = datas.len() as _;
+        ctrls.controls = datas.as_ptr() as _;
+
+        calls::s_ext_ctrls(fd, &mut ctrls).map(|_| ())
+    })
+}
+
 /// Control value
 pub struct Value<C: AsRef<QueryExtCtrl>> {
     ctrl: C,
     data: Internal<ExtControl>,
+    payload: Payload,
 }
 
+// payload is owned by value
+unsafe impl<C: AsRef<QueryExtCtrl> + Send> Send for Value<C> {}
+
 impl<C: AsRef<QueryExtCtrl>> From<C> for Value<C> {
     fn from(ctrl: C) -> Self {
-        let data = Internal::<ExtControl>::new(ctrl.as_ref());
+        let mut payload = new_payload(ctrl.as_ref());
+        let data = Internal::<ExtControl>::new(ctrl.as_ref(), &mut payload);
 
-        Self { ctrl, data }
+        Self {
+            ctrl,
+            data,
+            payload,
+        }
     }
 }
 
@@ -37,95 +82,127 @@ impl<C: AsRef<QueryExtCtrl>> Value<C> {
     }
 }
 
-impl<C: AsRef<QueryExtCtrl>> Drop for Value<C> {
-    fn drop(&mut self) {
-        if self.ctrl.as_ref().has_payload() {
-            self.data.del();
-        }
-    }
-}
-
 impl AsRef<QueryExtCtrl> for QueryExtCtrl {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
+impl<C: AsRef<QueryExtCtrl>> CopyValue for Value<C> {
+    fn copy_data(&self) -> ValueData {
+        ValueData::new(
+            core::slice::from_ref(&self.data),
+            core::slice::from_ref(&self.payload),
+        )
+    }
+
+    fn update_data(&mut self, data: &ValueData) {
+        data.update(
+            core::slice::from_mut(&mut self.data),
+            core::slice::from_mut(&mut self.payload),
+        );
+    }
+}
+
 impl<C: AsRef<QueryExtCtrl>> GetValue for Value<C> {
     /// Get value from device
     fn get(&mut self, fd: RawFd) -> Result<()> {
-        let ctrls = MaybeUninit::<ExtControls>::zeroed();
-
-        unsafe_call!({
-            let mut ctrls = ctrls.assume_init();
-
-            ctrls.count = 1;
-            ctrls.controls = self.data.as_mut() as *mut _;
-
-            calls::g_ext_ctrls(fd, &mut ctrls as *mut _)
-        })?;
-
-        Ok(())
+        get_ext_ctrls(fd, core::slice::from_mut(&mut self.data))
     }
 }
 
 impl<C: AsRef<QueryExtCtrl>> SetValue for Value<C> {
     /// Set value to device
     fn set(&self, fd: RawFd) -> Result<()> {
-        let req = MaybeUninit::<ExtControls>::zeroed();
-
-        unsafe_call!({
-            let mut req = req.assume_init();
-
-            req.count = 1;
-            req.controls = self.data.as_ref() as *const _ as *mut _;
-
-            calls::s_ext_ctrls(fd, &mut req as *mut _)
-        })?;
-
-        Ok(())
+        set_ext_ctrls(fd, core::slice::from_ref(&self.data))
     }
 }
 
 impl Internal<ExtControl> {
-    pub fn new(ctrl: &QueryExtCtrl) -> Self {
+    pub fn new(ctrl: &QueryExtCtrl, payload: &mut Payload) -> Self {
         let data = MaybeUninit::<ExtControl>::zeroed();
-        let size = ctrl.size();
-
-        let ptr = if ctrl.has_payload() {
-            let mut data = Vec::<u8>::with_capacity(size as _);
-            let ptr = data.as_mut_ptr();
-            let _ = ManuallyDrop::new(data);
-            ptr
-        } else {
-            core::ptr::null_mut()
-        };
 
         let data = unsafe {
             let mut data = data.assume_init();
 
-            data.union_.ptr = ptr as _;
+            if let Some(payload) = payload {
+                data.union_.ptr = payload.as_mut_ptr() as _;
+            }
 
             data.id = ctrl.id;
-            data.size = size;
+            data.size = ctrl.size();
 
             data
         };
 
         Self(data)
     }
+}
 
-    pub fn del(&mut self) {
-        unsafe {
-            let _ = Vec::from_raw_parts(self.union_.ptr as *mut u8, 0, self.size as _);
-            //self.union_.ptr = core::ptr::null_mut();
+/// Owned copy of control values
+///
+/// Can be sent to other thread to get or set values there.
+pub struct ValueData {
+    datas: Vec<Internal<ExtControl>>,
+    payloads: Vec<Payload>,
+}
+
+// payloads is owned by data
+unsafe impl Send for ValueData {}
+
+impl ValueData {
+    fn new(datas: &[Internal<ExtControl>], payloads: &[Payload]) -> Self {
+        let mut payloads = payloads.to_vec();
+
+        let datas = datas
+            .iter()
+            .zip(&mut payloads)
+            .map(|(data, payload)| {
+                let mut data = *data;
+                if let Some(payload) = payload {
+                    data.union_.ptr = payload.as_mut_ptr() as _;
+                }
+                data
+            })
+            .collect();
+
+        Self { datas, payloads }
+    }
+
+    fn update(&self, datas: &mut [Internal<ExtControl>], payloads: &mut [Payload]) {
+        for (((data, payload), new_data), new_payload) in datas
+            .iter_mut()
+            .zip(payloads)
+            .zip(&self.datas)
+            .zip(&self.payloads)
+        {
+            if let (Some(payload), Some(new_payload)) = (payload, new_payload) {
+                payload.copy_from_slice(new_payload);
+            } else {
+                data.union_ = new_data.union_;
+            }
         }
     }
+
+    /// Identifiers of controls
+    pub fn ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.datas.iter().map(|data| data.id)
+    }
+
+    /// Get values from device
+    pub fn get(&mut self, fd: RawFd) -> Result<()> {
+        get_ext_ctrls(fd, &mut self.datas)
+    }
+
+    /// Set values to device
+    pub fn set(&self, fd: RawFd) -> Result<()> {
+        set_ext_ctrls(fd, &self.datas)
+    }
 }
 
 impl RefValue<ExtControl> for str {
     fn try_ref<'a>(data: &'a ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a Self> {
-        if matches!(ctrl.type_, CtrlType::String) && ctrl.has_payload() {
+        if matches!(ctrl.type_(), Ok(CtrlType::String)) && ctrl.has_payload() {
             unsafe { core::ffi::CStr::from_ptr(data.union_.string as _) }
                 .to_str()
                 .ok()
@@ -187,19 +264,27 @@ pub trait MutValue<T> {
     fn try_mut<'a>(data: &'a mut T, ctrl: &QueryExtCtrl) -> Option<&'a mut Self>;
 }
 
-pub trait GetValue {
+pub trait CopyValue {
+    /// Make owned copy of values
+    fn copy_data(&self) -> ValueData;
+
+    /// Update values from copy
+    fn update_data(&mut self, data: &ValueData);
+}
+
+pub trait GetValue: CopyValue {
     /// Get value from device
     fn get(&mut self, fd: RawFd) -> Result<()>;
 }
 
-pub trait SetValue {
+pub trait SetValue: CopyValue {
     /// Set value to device
     fn set(&self, fd: RawFd) -> Result<()>;
 }
 
 impl<T: IsPlainCtrlData> RefValue<ExtControl> for T {
     fn try_ref<'a>(data: &'a ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a Self> {
-        if T::TYPES.contains(&ctrl.type_) {
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_)) {
             if ctrl.has_payload() {
                 if core::mem::size_of::<T>() as u32 <= data.size {
                     return Some(unsafe { &*(data.union_.ptr as *const _) });
@@ -214,7 +299,7 @@ impl<T: IsPlainCtrlData> RefValue<ExtControl> for T {
 
 impl<T: IsPlainCtrlData> MutValue<ExtControl> for T {
     fn try_mut<'a>(data: &'a mut ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a mut Self> {
-        if T::TYPES.contains(&ctrl.type_) {
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_)) {
             if ctrl.has_payload() {
                 if core::mem::size_of::<T>() as u32 <= data.size {
                     return Some(unsafe { &mut *(data.union_.ptr as *mut _) });
@@ -229,7 +314,7 @@ impl<T: IsPlainCtrlData> MutValue<ExtControl> for T {
 
 impl<const N: usize, T: IsPlainCtrlData> RefValue<ExtControl> for [T; N] {
     fn try_ref<'a>(data: &'a ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -242,7 +327,7 @@ impl<const N: usize, T: IsPlainCtrlData> RefValue<ExtControl> for [T; N] {
 
 impl<const N: usize, T: IsPlainCtrlData> MutValue<ExtControl> for [T; N] {
     fn try_mut<'a>(data: &'a mut ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a mut Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -255,7 +340,7 @@ impl<const N: usize, T: IsPlainCtrlData> MutValue<ExtControl> for [T; N] {
 
 impl<const N: usize, const M: usize, T: IsPlainCtrlData> RefValue<ExtControl> for [[T; N]; M] {
     fn try_ref<'a>(data: &'a ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -268,7 +353,7 @@ impl<const N: usize, const M: usize, T: IsPlainCtrlData> RefValue<ExtControl> fo
 
 impl<const N: usize, const M: usize, T: IsPlainCtrlData> MutValue<ExtControl> for [[T; N]; M] {
     fn try_mut<'a>(data: &'a mut ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a mut Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -283,7 +368,7 @@ impl<const N: usize, const M: usize, const L: usize, T: IsPlainCtrlData> RefValu
     for [[[T; N]; M]; L]
 {
     fn try_ref<'a>(data: &'a ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -298,7 +383,7 @@ impl<const N: usize, const M: usize, const L: usize, T: IsPlainCtrlData> MutValu
     for [[[T; N]; M]; L]
 {
     fn try_mut<'a>(data: &'a mut ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a mut Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -313,7 +398,7 @@ impl<const N: usize, const M: usize, const L: usize, const O: usize, T: IsPlainC
     RefValue<ExtControl> for [[[[T; N]; M]; L]; O]
 {
     fn try_ref<'a>(data: &'a ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -328,7 +413,7 @@ impl<const N: usize, const M: usize, const L: usize, const O: usize, T: IsPlainC
     MutValue<ExtControl> for [[[[T; N]; M]; L]; O]
 {
     fn try_mut<'a>(data: &'a mut ExtControl, ctrl: &QueryExtCtrl) -> Option<&'a mut Self> {
-        if T::TYPES.contains(&ctrl.type_)
+        if matches!(ctrl.type_(), Ok(type_) if T::TYPES.contains(&type_))
             && ctrl.has_payload()
             && core::mem::size_of::<Self>() as u32 <= data.size
         {
@@ -343,30 +428,29 @@ impl<const N: usize, const M: usize, const L: usize, const O: usize, T: IsPlainC
 pub struct Values<C: AsRef<QueryExtCtrl>> {
     ctrls: Vec<C>,
     datas: Vec<Internal<ExtControl>>,
+    payloads: Vec<Payload>,
 }
 
+// payloads is owned by values
+unsafe impl<C: AsRef<QueryExtCtrl> + Send> Send for Values<C> {}
+
 impl<C: AsRef<QueryExtCtrl>> FromIterator<C> for Values<C> {
     fn from_iter<T: IntoIterator<Item = C>>(iter: T) -> Self {
         let mut ctrls = Vec::new();
         let mut datas = Vec::new();
+        let mut payloads = Vec::new();
 
         for ctrl in iter {
-            datas.push(Internal::<ExtControl>::new(ctrl.as_ref()));
+            let mut payload = new_payload(ctrl.as_ref());
+            datas.push(Internal::<ExtControl>::new(ctrl.as_ref(), &mut payload));
+            payloads.push(payload);
             ctrls.push(ctrl);
         }
 
-        Self { ctrls, datas }
-    }
-}
-
-impl<C: AsRef<QueryExtCtrl>> Drop for Values<C> {
-    fn drop(&mut self) {
-        for index in 0..self.len() {
-            let ctrl = &self.ctrls[index];
-            let data = &mut self.datas[index];
-            if ctrl.as_ref().has_payload() {
-                Internal::from(data).del();
-            }
+        Self {
+            ctrls,
+            datas,
+            payloads,
         }
     }
 }
@@ -406,34 +490,26 @@ impl<C: AsRef<QueryExtCtrl>> Values<C> {
     }
 }
 
+impl<C: AsRef<QueryExtCtrl>> CopyValue for Values<C> {
+    fn copy_data(&self) -> ValueData {
+        ValueData::new(&self.datas, &self.payloads)
+    }
+
+    fn update_data(&mut self, data: &ValueData) {
+        data.update(&mut self.datas, &mut self.payloads);
+    }
+}
+
 impl<C: AsRef<QueryExtCtrl>> GetValue for Values<C> {
     /// Get values from device
     fn get(&mut self, fd: RawFd) -> Result<()> {
-        let ctrls = MaybeUninit::<ExtControls>::zeroed();
-
-        unsafe_call!({
-            let mut ctrls = ctrls.assume_init();
-
-            ctrls.count = self.ctrls.len() as _;
-            ctrls.controls = self.datas.as_mut_ptr() as _;
-
-            calls::g_ext_ctrls(fd, &mut ctrls).map(|_| ())
-        })
+        get_ext_ctrls(fd, &mut self.datas)
     }
 }
 
 impl<C: AsRef<QueryExtCtrl>> SetValue for Values<C> {
     /// Set values to device
     fn set(&self, fd: RawFd) -> Result<()> {
-        let ctrls = MaybeUninit::<ExtControls>::zeroed();
-
-        unsafe_call!({
-            let mut ctrls = ctrls.assume_init();
-
-            ctrls.count = self.ctrls.len() as _;
-            ctrls.controls = self.datas.as_ptr() as _;
-
-            calls::s_ext_ctrls(fd, &mut ctrls).map(|_| ())
-        })
+        set_ext_ctrls(fd, &self.datas)
     }
 }
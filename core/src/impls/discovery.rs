@@ -0,0 +1,220 @@
+use crate::types::*;
+use std::path::{Path, PathBuf};
+
+/// USB device identification
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UsbInfo {
+    pub(crate) vendor_id: u16,
+    pub(crate) product_id: u16,
+    pub(crate) serial: Option<String>,
+    pub(crate) manufacturer: Option<String>,
+    pub(crate) product: Option<String>,
+}
+
+impl UsbInfo {
+    /// Vendor identifier
+    pub fn vendor_id(&self) -> u16 {
+        self.vendor_id
+    }
+
+    /// Product identifier
+    pub fn product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    /// Serial number
+    pub fn serial(&self) -> Option<&str> {
+        self.serial.as_deref()
+    }
+
+    /// Manufacturer name
+    pub fn manufacturer(&self) -> Option<&str> {
+        self.manufacturer.as_deref()
+    }
+
+    /// Product name
+    pub fn product(&self) -> Option<&str> {
+        self.product.as_deref()
+    }
+}
+
+impl core::fmt::Display for UsbInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vendor_id, self.product_id)?;
+        if let Some(manufacturer) = &self.manufacturer {
+            " ".fmt(f)?;
+            manufacturer.fmt(f)?;
+        }
+        if let Some(product) = &self.product {
+            " ".fmt(f)?;
+            product.fmt(f)?;
+        }
+        if let Some(serial) = &self.serial {
+            " #".fmt(f)?;
+            serial.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Video device node information
+///
+/// Collected from `/sys/class/video4linux` and `/dev/v4l` links.
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    pub(crate) path: PathBuf,
+    pub(crate) sys_path: PathBuf,
+    pub(crate) parent: PathBuf,
+    pub(crate) name: String,
+    pub(crate) index: u32,
+    pub(crate) driver: Option<String>,
+    pub(crate) usb: Option<UsbInfo>,
+    pub(crate) by_id: Vec<PathBuf>,
+    pub(crate) by_path: Vec<PathBuf>,
+    pub(crate) capability: Option<Capability>,
+}
+
+impl DeviceInfo {
+    /// Path of device node
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of device in sysfs
+    pub fn sys_path(&self) -> &Path {
+        &self.sys_path
+    }
+
+    /// Path of physical device in sysfs
+    pub fn parent(&self) -> &Path {
+        &self.parent
+    }
+
+    /// Device name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Index of node within physical device
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Driver name
+    pub fn driver(&self) -> Option<&str> {
+        self.capability
+            .as_ref()
+            .map(Capability::driver)
+            .or(self.driver.as_deref())
+    }
+
+    /// Bus name
+    ///
+    /// Available only when capabilities is known.
+    pub fn bus_info(&self) -> Option<&str> {
+        self.capability.as_ref().map(Capability::bus)
+    }
+
+    /// USB device identification
+    pub fn usb(&self) -> Option<&UsbInfo> {
+        self.usb.as_ref()
+    }
+
+    /// Persistent links from `/dev/v4l/by-id`
+    pub fn by_id(&self) -> &[PathBuf] {
+        &self.by_id
+    }
+
+    /// Persistent links from `/dev/v4l/by-path`
+    pub fn by_path(&self) -> &[PathBuf] {
+        &self.by_path
+    }
+
+    /// Device capabilities
+    ///
+    /// Not available when device cannot be opened.
+    pub fn capability(&self) -> Option<&Capability> {
+        self.capability.as_ref()
+    }
+
+    /// Check that buffer type is supported by device node
+    pub fn supports(&self, type_: BufferType) -> bool {
+        self.capability
+            .as_ref()
+            .map(|capability| type_.is_supported(capability.device_capabilities()))
+            .unwrap_or_default()
+    }
+}
+
+impl core::fmt::Display for DeviceInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.path.display().fmt(f)?;
+        ": '".fmt(f)?;
+        self.name.fmt(f)?;
+        "' #".fmt(f)?;
+        self.index.fmt(f)?;
+        if let Some(driver) = self.driver() {
+            ", driver: ".fmt(f)?;
+            driver.fmt(f)?;
+        }
+        if let Some(bus_info) = self.bus_info() {
+            ", bus: ".fmt(f)?;
+            bus_info.fmt(f)?;
+        }
+        if let Some(usb) = &self.usb {
+            ", usb: ".fmt(f)?;
+            usb.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Physical device with its video nodes
+#[derive(Debug, Clone)]
+pub struct PhysicalDevice {
+    pub(crate) sys_path: PathBuf,
+    pub(crate) nodes: Vec<DeviceInfo>,
+}
+
+impl PhysicalDevice {
+    /// Path of device in sysfs
+    pub fn sys_path(&self) -> &Path {
+        &self.sys_path
+    }
+
+    /// USB device identification
+    pub fn usb(&self) -> Option<&UsbInfo> {
+        self.nodes.first().and_then(DeviceInfo::usb)
+    }
+
+    /// Serial number of USB device
+    pub fn serial(&self) -> Option<&str> {
+        self.usb().and_then(UsbInfo::serial)
+    }
+
+    /// Video nodes of device ordered by index
+    pub fn nodes(&self) -> &[DeviceInfo] {
+        &self.nodes
+    }
+
+    /// Get first node which supports buffer type
+    pub fn node(&self, type_: BufferType) -> Option<&DeviceInfo> {
+        self.nodes.iter().find(|node| node.supports(type_))
+    }
+}
+
+impl core::fmt::Display for PhysicalDevice {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.sys_path.display().fmt(f)?;
+        if let Some(usb) = self.usb() {
+            " (".fmt(f)?;
+            usb.fmt(f)?;
+            ")".fmt(f)?;
+        }
+        for node in &self.nodes {
+            "\n  ".fmt(f)?;
+            node.fmt(f)?;
+        }
+        Ok(())
+    }
+}
@@ -25,9 +25,16 @@ event_data_impl! {
 }
 
 impl Event {
+    /// Event type
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<EventType, u32> {
+        EventType::try_from(self.type_)
+    }
+
     /// Try get reference to data of specific type
     pub fn data<T: IsEventData>(&self) -> Option<&T> {
-        if self.type_ == T::TYPE {
+        if self.type_ == T::TYPE as u32 {
             Some(unsafe { &*(&self.u as *const _ as *const T) })
         } else {
             None
@@ -40,7 +47,49 @@ impl Event {
     }
 }
 
+impl EventVsync {
+    /// Field of next frame
+    ///
+    /// Returns raw value when field order is unknown.
+    pub fn field(&self) -> core::result::Result<Field, u32> {
+        Field::try_from(self.field as u32)
+    }
+}
+
+impl EventFrameSync {
+    /// Create frame sync data
+    pub fn new(frame_sequence: u32) -> Self {
+        Self { frame_sequence }
+    }
+
+    /// Sequence number of frame being received
+    pub fn frame_sequence(&self) -> u32 {
+        self.frame_sequence
+    }
+}
+
+impl EventCtrl {
+    /// Type of changed control
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<CtrlType, u32> {
+        CtrlType::try_from(self.type_)
+    }
+}
+
 impl Internal<Event> {
+    /// Instantiate event with data
+    pub fn new<T: IsEventData>(id: u32, data: T) -> Self {
+        let event = MaybeUninit::<Event>::zeroed();
+        let mut event = unsafe { event.assume_init() };
+
+        event.type_ = T::TYPE as _;
+        event.id = id;
+        unsafe { core::ptr::write(&mut event.u as *mut _ as *mut T, data) };
+
+        event.into()
+    }
+
     /// Dequeue event
     pub fn dequeue(fd: RawFd) -> Result<Self> {
         let event = MaybeUninit::<Event>::uninit();
@@ -52,3 +101,29 @@ impl Internal<Event> {
         })
     }
 }
+
+impl EventSubscription {
+    /// Create subscription to events of specified type
+    ///
+    /// The `id` is a control identifier for [EventType::Ctrl] and zero otherwise.
+    pub fn new(type_: EventType, id: u32, flags: EventSubFlag) -> Self {
+        Self {
+            type_: type_ as _,
+            id,
+            flags: flags.bits(),
+            reserved: [0; 5],
+        }
+    }
+}
+
+impl Internal<&EventSubscription> {
+    /// Subscribe to events
+    pub fn subscribe(&self, fd: RawFd) -> Result<()> {
+        unsafe_call!(calls::subscribe_event(fd, *self.as_ref())).map(|_| ())
+    }
+
+    /// Unsubscribe from events
+    pub fn unsubscribe(&self, fd: RawFd) -> Result<()> {
+        unsafe_call!(calls::unsubscribe_event(fd, *self.as_ref())).map(|_| ())
+    }
+}
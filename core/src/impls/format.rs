@@ -57,6 +57,37 @@ impl Format {
             None
         }
     }
+
+    /// Get size of single frame in bytes
+    ///
+    /// Returns [None] for overlay formats and when size is unknown.
+    pub fn frame_size(&self) -> Option<usize> {
+        let size = if let Some(format) = self.try_ref::<PixFormat>() {
+            format.size_image as usize
+        } else if let Some(format) = self.try_ref::<PixFormatMplane>() {
+            format
+                .plane_fmt()
+                .iter()
+                .map(|plane| plane.size_image as usize)
+                .sum()
+        } else if let Some(format) = self.try_ref::<VbiFormat>() {
+            format.samples_per_line as usize * (format.count[0] + format.count[1]) as usize
+        } else if let Some(format) = self.try_ref::<SlicedVbiFormat>() {
+            format.io_size as usize
+        } else if let Some(format) = self.try_ref::<SdrFormat>() {
+            format.buffer_size as usize
+        } else if let Some(format) = self.try_ref::<MetaFormat>() {
+            format.buffer_size as usize
+        } else {
+            0
+        };
+
+        if size > 0 {
+            Some(size)
+        } else {
+            None
+        }
+    }
 }
 
 /// Format data types
@@ -98,6 +129,82 @@ format_impl! {
     MetaFormat: MetaCapture MetaOutput,
 }
 
+macro_rules! colorimetry_impl {
+    ($($type:ty,)*) => {
+        $(
+            impl $type {
+                /// Field order, from enum [Field]
+                ///
+                /// Returns raw value when field order is unknown.
+                pub fn field(&self) -> core::result::Result<Field, u32> {
+                    Field::try_from(self.field)
+                }
+
+                /// Set field order
+                pub fn set_field(&mut self, field: Field) -> &mut Self {
+                    self.field = field as _;
+                    self
+                }
+
+                /// Image colorspace, from enum [ColorSpace]
+                ///
+                /// Returns raw value when colorspace is unknown.
+                pub fn color_space(&self) -> core::result::Result<ColorSpace, u32> {
+                    ColorSpace::try_from(self.color_space)
+                }
+
+                /// Set image colorspace
+                pub fn set_color_space(&mut self, color_space: ColorSpace) -> &mut Self {
+                    self.color_space = color_space as _;
+                    self
+                }
+
+                /// Quantization range, from enum [Quantization]
+                ///
+                /// Returns raw value when quantization is unknown.
+                pub fn quantization(&self) -> core::result::Result<Quantization, u32> {
+                    try_from_u8(self.quantization as _)
+                }
+
+                /// Set quantization range
+                pub fn set_quantization(&mut self, quantization: Quantization) -> &mut Self {
+                    self.quantization = quantization as _;
+                    self
+                }
+
+                /// Transfer function, from enum [XferFunc]
+                ///
+                /// Returns raw value when transfer function is unknown.
+                pub fn xfer_func(&self) -> core::result::Result<XferFunc, u32> {
+                    try_from_u8(self.xfer_func as _)
+                }
+
+                /// Set transfer function
+                pub fn set_xfer_func(&mut self, xfer_func: XferFunc) -> &mut Self {
+                    self.xfer_func = xfer_func as _;
+                    self
+                }
+            }
+        )*
+    };
+}
+
+colorimetry_impl! {
+    PixFormat,
+    PixFormatMplane,
+}
+
+/// Format checked value or raw value when it is unknown
+fn fmt_checked<T: core::fmt::Display>(
+    value: core::result::Result<T, u32>,
+    f: &mut core::fmt::Formatter,
+) -> core::fmt::Result {
+    match value {
+        Ok(value) => value.fmt(f),
+        Err(value) => core::fmt::Display::fmt(&value, f),
+    }
+}
+
 impl core::fmt::Display for PixFormat {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         self.width.fmt(f)?;
@@ -106,18 +213,18 @@ impl core::fmt::Display for PixFormat {
         ' '.fmt(f)?;
         self.pixel_format.fmt(f)?;
         ' '.fmt(f)?;
-        self.field.fmt(f)?;
+        fmt_checked(self.field(), f)?;
         " #".fmt(f)?;
         self.bytes_per_line.fmt(f)?;
         '/'.fmt(f)?;
         self.size_image.fmt(f)?;
         ' '.fmt(f)?;
-        self.color_space.fmt(f)?;
+        fmt_checked(self.color_space(), f)?;
         ' '.fmt(f)?;
         // TODO: union
-        self.quantization.fmt(f)?;
+        fmt_checked(self.quantization(), f)?;
         ' '.fmt(f)?;
-        self.xfer_func.fmt(f)
+        fmt_checked(self.xfer_func(), f)
     }
 }
 
@@ -131,6 +238,32 @@ impl PixFormatMplane {
     pub fn plane_fmt_mut(&mut self) -> &mut [PlanePixFormat] {
         &mut self.plane_fmt[..self.num_planes as usize]
     }
+
+    /// Convert to single-planar format
+    ///
+    /// Returns [None] when format has more than one memory plane.
+    pub fn to_pix_format(&self) -> Option<PixFormat> {
+        if self.num_planes != 1 {
+            return None;
+        }
+
+        let plane_fmt = self.plane_fmt[0];
+
+        Some(PixFormat {
+            width: self.width,
+            height: self.height,
+            pixel_format: self.pixel_format,
+            field: self.field,
+            bytes_per_line: plane_fmt.bytes_per_line,
+            size_image: plane_fmt.size_image,
+            color_space: self.color_space,
+            priv_: PIX_FMT_PRIV_MAGIC,
+            flags: self.flags,
+            enc: self.enc as _,
+            quantization: self.quantization as _,
+            xfer_func: self.xfer_func as _,
+        })
+    }
 }
 
 impl core::fmt::Display for PixFormatMplane {
@@ -141,7 +274,7 @@ impl core::fmt::Display for PixFormatMplane {
         ' '.fmt(f)?;
         self.pixel_format().fmt(f)?;
         ' '.fmt(f)?;
-        self.field().fmt(f)?;
+        fmt_checked(self.field(), f)?;
         for plane in self.plane_fmt() {
             " #".fmt(f)?;
             plane.bytes_per_line().fmt(f)?;
@@ -149,12 +282,12 @@ impl core::fmt::Display for PixFormatMplane {
             plane.size_image().fmt(f)?;
         }
         ' '.fmt(f)?;
-        self.color_space().fmt(f)?;
+        fmt_checked(self.color_space(), f)?;
         ' '.fmt(f)?;
         // TODO: union
-        self.quantization().fmt(f)?;
+        fmt_checked(self.quantization(), f)?;
         ' '.fmt(f)?;
-        self.xfer_func().fmt(f)
+        fmt_checked(self.xfer_func(), f)
     }
 }
 
@@ -186,13 +319,32 @@ impl core::fmt::Display for Window {
 }
 
 impl core::fmt::Display for VbiFormat {
-    fn fmt(&self, _f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        FourCc::from(self.sample_format).fmt(f)?;
+        ' '.fmt(f)?;
+        self.samples_per_line.fmt(f)?;
+        '@'.fmt(f)?;
+        self.sampling_rate.fmt(f)?;
+        " +".fmt(f)?;
+        self.offset.fmt(f)?;
+        for field in 0..2 {
+            ' '.fmt(f)?;
+            self.start[field].fmt(f)?;
+            '+'.fmt(f)?;
+            self.count[field].fmt(f)?;
+        }
+        if !self.flags.is_none() {
+            ' '.fmt(f)?;
+            self.flags.fmt(f)?;
+        }
+        Ok(())
     }
 }
 
 impl core::fmt::Display for SlicedVbiFormat {
-    fn fmt(&self, _f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        SlicedVbiType::from(self.service_set as u32).fmt(f)?;
+        " #".fmt(f)?;
+        self.io_size.fmt(f)
     }
 }
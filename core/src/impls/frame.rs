@@ -0,0 +1,418 @@
+use super::timestamp::{timestamp_instant, timestamp_system_time};
+use crate::{
+    types::*, utils, BufferData, BufferRef, IsTimestamp, Method, Out, PlaneLayout, Result,
+    TimestampKind,
+};
+use getset::CopyGetters;
+use std::time::{Instant, SystemTime};
+
+/// Image format of frames
+///
+/// Single-planar pixel format with precalculated layout of color planes.
+/// Frame data is a single memory plane, so multi-planar formats which
+/// store color planes in separate memory planes (like [FourCc::Nv12m]) is not supported.
+/// Lines and pixels is accessed as raw bytes, so decoding of packed formats
+/// which store several color components per pixel (like [FourCc::Yuyv]) is not supported.
+#[derive(Clone, Copy)]
+pub struct FrameFormat {
+    format: PixFormat,
+    layout: Option<PlaneLayout>,
+}
+
+impl From<PixFormat> for FrameFormat {
+    fn from(format: PixFormat) -> Self {
+        Self {
+            layout: PlaneLayout::from_pix_format(&format),
+            format,
+        }
+    }
+}
+
+impl FrameFormat {
+    /// Create frame format from stream format
+    ///
+    /// Multi-planar formats are accepted only when image fits into single memory plane,
+    /// otherwise fails with [std::io::ErrorKind::InvalidInput].
+    pub fn new(format: &Format) -> Result<Self> {
+        if let Some(format) = format.try_ref::<PixFormat>() {
+            Ok((*format).into())
+        } else if let Some(format) = format.try_ref::<PixFormatMplane>() {
+            format
+                .to_pix_format()
+                .map(Self::from)
+                .ok_or_else(|| utils::invalid_input("Multiple memory planes is not supported"))
+        } else {
+            Err(utils::invalid_input("Not a video format"))
+        }
+    }
+
+    /// Pixel format
+    pub fn pix_format(&self) -> &PixFormat {
+        &self.format
+    }
+
+    /// Layout of color planes
+    ///
+    /// Returns [None] for compressed and unknown formats.
+    pub fn layout(&self) -> Option<&PlaneLayout> {
+        self.layout.as_ref()
+    }
+
+    /// Image width in pixels
+    pub fn width(&self) -> u32 {
+        self.format.width()
+    }
+
+    /// Image height in lines
+    pub fn height(&self) -> u32 {
+        self.format.height()
+    }
+
+    /// Four character code of pixel format
+    pub fn fourcc(&self) -> FourCc {
+        self.format.pixel_format()
+    }
+
+    /// Number of color planes
+    pub fn num_planes(&self) -> usize {
+        self.layout.map(|layout| layout.num_planes()).unwrap_or(0)
+    }
+
+    /// Get data of color plane
+    ///
+    /// The padding after last line of plane may be missing.
+    pub fn plane<'d>(&self, data: &'d [u8], plane: usize) -> Option<&'d [u8]> {
+        let layout = self.layout.as_ref()?;
+        if plane >= layout.num_planes() {
+            return None;
+        }
+        let info = layout.info();
+        let lines = info.plane_height(plane, layout.height()) as usize;
+        let bytes_per_line = layout.bytes_per_line(plane) as usize;
+        let offset = layout.plane_offset(plane) as usize;
+        let size = layout.plane_size(plane) as usize;
+        let min_size = if lines > 0 {
+            bytes_per_line * (lines - 1) + info.min_bytes_per_line(plane, layout.width()) as usize
+        } else {
+            0
+        };
+        if data.len() < offset + min_size {
+            return None;
+        }
+        Some(&data[offset..data.len().min(offset + size)])
+    }
+
+    /// Iterate over lines of color plane
+    ///
+    /// Each line is trimmed to the meaningful bytes without padding.
+    /// Lines is raw bytes of packed pixels without decoding into color components.
+    pub fn rows<'d>(&self, data: &'d [u8], plane: usize) -> Option<Rows<'d>> {
+        let data = self.plane(data, plane)?;
+        let layout = self.layout.as_ref()?;
+        let info = layout.info();
+        Some(Rows {
+            data,
+            stride: layout.bytes_per_line(plane) as _,
+            width: info.min_bytes_per_line(plane, layout.width()) as _,
+            lines: info.plane_height(plane, layout.height()) as _,
+        })
+    }
+
+    /// Get bytes of pixel in first color plane
+    ///
+    /// Pixel is raw bytes without decoding into color components.
+    /// For formats with horizontal subsampling (like [FourCc::Yuyv]) it is a part
+    /// of macropixel which color components are shared with neighbour pixel.
+    /// Returns [None] for formats which pixels is not byte-aligned
+    /// or when coordinates is out of image.
+    pub fn pixel<'d>(&self, data: &'d [u8], x: u32, y: u32) -> Option<&'d [u8]> {
+        let layout = self.layout.as_ref()?;
+        let info = layout.info();
+        if info.block_bytes() > 0 || info.bpp(0) % 8 != 0 {
+            return None;
+        }
+        if x >= layout.width() || y >= layout.height() {
+            return None;
+        }
+        let size = info.bpp(0) as usize / 8;
+        let offset = y as usize * layout.bytes_per_line(0) as usize + x as usize * size;
+        self.plane(data, 0)?.get(offset..offset + size)
+    }
+}
+
+impl core::fmt::Display for FrameFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.format.fmt(f)
+    }
+}
+
+/// Iterator over lines of color plane
+///
+/// Each item is raw bytes of line, see [FrameFormat::rows].
+#[derive(Clone)]
+pub struct Rows<'d> {
+    data: &'d [u8],
+    stride: usize,
+    width: usize,
+    lines: usize,
+}
+
+impl<'d> Iterator for Rows<'d> {
+    type Item = &'d [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.lines == 0 {
+            return None;
+        }
+        let row = &self.data[..self.width];
+        self.lines -= 1;
+        if self.lines > 0 {
+            self.data = &self.data[self.stride..];
+        }
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.lines, Some(self.lines))
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
+impl core::iter::FusedIterator for Rows<'_> {}
+
+/// Buffer reference with image format
+pub struct FrameRef<Dir, Met: Method> {
+    buffer: BufferRef<Dir, Met>,
+    format: FrameFormat,
+}
+
+impl<Dir, Met: Method> FrameRef<Dir, Met> {
+    /// Create frame using buffer and image format
+    pub fn new(buffer: BufferRef<Dir, Met>, format: FrameFormat) -> Self {
+        Self { buffer, format }
+    }
+
+    /// Image format of frame
+    pub fn format(&self) -> &FrameFormat {
+        &self.format
+    }
+
+    /// Underlying buffer
+    pub fn buffer(&self) -> &BufferRef<Dir, Met> {
+        &self.buffer
+    }
+
+    /// Convert into underlying buffer
+    pub fn into_buffer(self) -> BufferRef<Dir, Met> {
+        self.buffer
+    }
+
+    /// Get access to frame data
+    pub fn lock(&self) -> Frame<'_, Dir, Met> {
+        Frame {
+            data: self.buffer.lock(),
+            format: &self.format,
+        }
+    }
+
+    /// Try get access to frame data
+    pub fn try_lock(&self) -> Option<Frame<'_, Dir, Met>> {
+        Some(Frame {
+            data: self.buffer.try_lock()?,
+            format: &self.format,
+        })
+    }
+}
+
+impl<Dir, Met: Method> core::fmt::Display for FrameRef<Dir, Met> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.buffer.fmt(f)
+    }
+}
+
+/// Buffer data with image format
+///
+/// Buffer metadata like sequence, timestamp, field and flags
+/// available through dereferencing to [Buffer].
+pub struct Frame<'r, Dir, Met: Method> {
+    data: BufferData<'r, Dir, Met>,
+    format: &'r FrameFormat,
+}
+
+impl<'r, Dir, Met: Method> core::ops::Deref for Frame<'r, Dir, Met> {
+    type Target = BufferData<'r, Dir, Met>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<'r, Met: Method> core::ops::DerefMut for Frame<'r, Out, Met> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<'r, Dir, Met: Method> AsRef<[u8]> for Frame<'r, Dir, Met> {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+impl<'r, Met: Method> AsMut<[u8]> for Frame<'r, Out, Met> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.data.as_mut()
+    }
+}
+
+impl<'r, Dir, Met: Method> Frame<'r, Dir, Met> {
+    /// Image format of frame
+    pub fn format(&self) -> &FrameFormat {
+        self.format
+    }
+
+    /// Get data of color plane
+    pub fn plane(&self, plane: usize) -> Option<&[u8]> {
+        self.format.plane(self.data.as_ref(), plane)
+    }
+
+    /// Iterate over lines of color plane
+    pub fn rows(&self, plane: usize) -> Option<Rows<'_>> {
+        self.format.rows(self.data.as_ref(), plane)
+    }
+
+    /// Get bytes of pixel in first color plane
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        self.format.pixel(self.data.as_ref(), x, y)
+    }
+
+    /// Copy frame data and metadata
+    pub fn to_owned(&self) -> OwnedFrame {
+        OwnedFrame {
+            data: self.data.as_ref().to_vec(),
+            format: *self.format,
+            sequence: self.sequence(),
+            timestamp: self.timestamp(),
+            field: self.data.field,
+            flags: self.flags(),
+        }
+    }
+}
+
+impl<'r, Dir, Met: Method> core::fmt::Display for Frame<'r, Dir, Met> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.data.fmt(f)
+    }
+}
+
+/// Frame data copied from buffer
+#[derive(Clone, CopyGetters)]
+pub struct OwnedFrame {
+    data: Vec<u8>,
+
+    format: FrameFormat,
+
+    /// Frame sequence number
+    #[getset(get_copy = "pub")]
+    sequence: u32,
+
+    timestamp: TimeVal,
+
+    field: u32,
+
+    /// Buffer flags
+    #[getset(get_copy = "pub")]
+    flags: BufferFlag,
+}
+
+impl AsRef<[u8]> for OwnedFrame {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AsMut<[u8]> for OwnedFrame {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl OwnedFrame {
+    /// Create frame from data and image format
+    pub fn new(data: Vec<u8>, format: FrameFormat) -> Self {
+        Self {
+            data,
+            format,
+            sequence: 0,
+            timestamp: TimeVal::new(0, 0),
+            field: Field::None as _,
+            flags: BufferFlag::none(),
+        }
+    }
+
+    /// Image format of frame
+    pub fn format(&self) -> &FrameFormat {
+        &self.format
+    }
+
+    /// Frame data
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Convert into frame data
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Frame timestamp
+    pub fn timestamp<T: IsTimestamp>(&self) -> T {
+        T::from_time_val(self.timestamp)
+    }
+
+    /// Kind of frame timestamp
+    pub fn timestamp_kind(&self) -> TimestampKind {
+        self.flags.into()
+    }
+
+    /// Monotonic frame timestamp as instant
+    pub fn instant(&self) -> Option<Instant> {
+        timestamp_instant(self.timestamp, self.flags)
+    }
+
+    /// Monotonic frame timestamp as system time
+    pub fn system_time(&self) -> Option<SystemTime> {
+        timestamp_system_time(self.timestamp, self.flags)
+    }
+
+    /// Field order of frame
+    pub fn field(&self) -> core::result::Result<Field, u32> {
+        Field::try_from(self.field)
+    }
+
+    /// Get data of color plane
+    pub fn plane(&self, plane: usize) -> Option<&[u8]> {
+        self.format.plane(&self.data, plane)
+    }
+
+    /// Iterate over lines of color plane
+    pub fn rows(&self, plane: usize) -> Option<Rows<'_>> {
+        self.format.rows(&self.data, plane)
+    }
+
+    /// Get bytes of pixel in first color plane
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        self.format.pixel(&self.data, x, y)
+    }
+}
+
+impl core::fmt::Display for OwnedFrame {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.format.fmt(f)?;
+        " #".fmt(f)?;
+        self.sequence.fmt(f)?;
+        ' '.fmt(f)?;
+        self.data.len().fmt(f)
+    }
+}
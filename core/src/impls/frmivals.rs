@@ -3,9 +3,16 @@ use core::mem::MaybeUninit;
 use std::os::unix::io::RawFd;
 
 impl FrmIvalEnum {
+    /// Frame interval type
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<FrmIvalType, u32> {
+        FrmIvalType::try_from(self.type_)
+    }
+
     /// Get reference to value
     pub fn try_ref<T: IsFrmIvalData>(&self) -> Option<&T> {
-        if T::TYPES.contains(&self.type_()) {
+        if matches!(self.type_(), Ok(type_) if T::TYPES.contains(&type_)) {
             Some(unsafe { &*(&self.union_ as *const _ as *const T) })
         } else {
             None
@@ -61,14 +68,17 @@ macro_rules! frmsize_impl {
                 ' '.fmt(f)?;
                 self.pixel_format.fmt(f)?;
                 ' '.fmt(f)?;
-                self.type_.fmt(f)?;
-                ' '.fmt(f)?;
-                match self.type_ {
+                match self.type_() {
                     $(
-                        $(FrmIvalType::$buf_type)|* => self.try_ref::<$type>()
-                            .ok_or_else(Default::default)?.fmt(f),
+                        Ok(type_) if <$type as IsFrmIvalData>::TYPES.contains(&type_) => {
+                            type_.fmt(f)?;
+                            ' '.fmt(f)?;
+                            self.try_ref::<$type>()
+                                .ok_or_else(Default::default)?.fmt(f)
+                        }
                     )*
-                    _ => Ok(()),
+                    Ok(type_) => type_.fmt(f),
+                    Err(type_) => type_.fmt(f),
                 }
             }
         }
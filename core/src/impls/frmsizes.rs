@@ -3,9 +3,16 @@ use core::mem::MaybeUninit;
 use std::os::unix::io::RawFd;
 
 impl FrmSizeEnum {
+    /// Frame size type
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<FrmSizeType, u32> {
+        FrmSizeType::try_from(self.type_)
+    }
+
     /// Get reference to size description
     pub fn try_ref<T: IsFrmSizeData>(&self) -> Option<&T> {
-        if T::TYPES.contains(&self.type_()) {
+        if matches!(self.type_(), Ok(type_) if T::TYPES.contains(&type_)) {
             Some(unsafe { &*(&self.union_ as *const _ as *const T) })
         } else {
             None
@@ -34,12 +41,12 @@ impl<'i> Iterator for FrmSizeIter<'i> {
             return None;
         }
 
-        match self.sizes.type_ {
-            FrmSizeType::Discrete => {
+        match self.sizes.type_() {
+            Ok(FrmSizeType::Discrete) => {
                 self.index = u32::MAX;
                 Some(*self.sizes.try_ref::<FrmSizeDiscrete>().unwrap())
             }
-            FrmSizeType::Stepwise => {
+            Ok(FrmSizeType::Stepwise) => {
                 let size = self
                     .sizes
                     .try_ref::<FrmSizeStepwise>()
@@ -54,7 +61,8 @@ impl<'i> Iterator for FrmSizeIter<'i> {
 
                 size
             }
-            FrmSizeType::Continuous => None, // FIXME:
+            Ok(FrmSizeType::Continuous) => None, // FIXME:
+            Err(_) => None,
         }
     }
 }
@@ -115,14 +123,17 @@ macro_rules! frmsize_impl {
                 ' '.fmt(f)?;
                 self.pixel_format.fmt(f)?;
                 ' '.fmt(f)?;
-                self.type_.fmt(f)?;
-                ' '.fmt(f)?;
-                match self.type_ {
+                match self.type_() {
                     $(
-                        $(FrmSizeType::$buf_type)|* => self.try_ref::<$type>()
-                            .ok_or_else(Default::default)?.fmt(f),
+                        Ok(type_) if <$type as IsFrmSizeData>::TYPES.contains(&type_) => {
+                            type_.fmt(f)?;
+                            ' '.fmt(f)?;
+                            self.try_ref::<$type>()
+                                .ok_or_else(Default::default)?.fmt(f)
+                        }
                     )*
-                    _ => Ok(()),
+                    Ok(type_) => type_.fmt(f),
+                    Err(type_) => type_.fmt(f),
                 }
             }
         }
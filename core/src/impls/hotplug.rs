@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+
+/// Device node change
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceEvent {
+    /// Device node was created
+    Added(PathBuf),
+    /// Device node was removed
+    Removed(PathBuf),
+}
+
+impl DeviceEvent {
+    /// Path of device node
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Added(path) | Self::Removed(path) => path,
+        }
+    }
+
+    /// Check that device was added
+    pub fn is_added(&self) -> bool {
+        matches!(self, Self::Added(_))
+    }
+
+    /// Check that device was removed
+    pub fn is_removed(&self) -> bool {
+        matches!(self, Self::Removed(_))
+    }
+}
+
+impl core::fmt::Display for DeviceEvent {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Added(_) => "added ",
+            Self::Removed(_) => "removed ",
+        }
+        .fmt(f)?;
+        self.path().display().fmt(f)
+    }
+}
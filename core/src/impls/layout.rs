@@ -0,0 +1,214 @@
+use crate::types::*;
+use getset::CopyGetters;
+
+/// Memory layout of image planes
+///
+/// Reproduces the way the kernel calculates `bytesperline` and `sizeimage`
+/// for uncompressed pixel formats.
+#[derive(Debug, Clone, Copy, CopyGetters)]
+pub struct PlaneLayout {
+    /// Pixel format info
+    #[getset(get_copy = "pub")]
+    info: FormatInfo,
+
+    /// Image width in pixels
+    #[getset(get_copy = "pub")]
+    width: u32,
+
+    /// Image height in lines
+    #[getset(get_copy = "pub")]
+    height: u32,
+
+    bytes_per_line: [u32; 4],
+    plane_size: [u32; 4],
+    plane_offset: [u32; 4],
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn lcm(a: u32, b: u32) -> u32 {
+    a / gcd(a, b) * b
+}
+
+impl PlaneLayout {
+    /// Calculate layout of image planes
+    ///
+    /// The `align` is a required alignment of line in bytes for the first
+    /// plane. Lines of chroma planes are scaled down from the first plane
+    /// the same way as the width, as V4L2 requires for planar formats.
+    ///
+    /// Returns [None] for compressed and unknown formats.
+    pub fn compute(fourcc: FourCc, width: u32, height: u32, align: u32) -> Option<Self> {
+        let info = fourcc.info()?;
+
+        if info.is_compressed() {
+            return None;
+        }
+
+        let planes = info.comp_planes() as usize;
+        let bpp0 = info.bpp(0) as u32;
+        let hsub = info.hsub() as u32;
+
+        let mut align = align.max(1);
+        let mut bytes_per_line = info.min_bytes_per_line(0, width);
+
+        for plane in 1..planes {
+            // chroma line is bytes_per_line * bpp / (bpp0 * hsub)
+            let bpp = info.bpp(plane) as u32;
+            let div = bpp0 * hsub;
+            align = lcm(align, div / gcd(div, bpp));
+            bytes_per_line =
+                bytes_per_line.max((info.min_bytes_per_line(plane, width) * div).div_ceil(bpp));
+        }
+
+        Some(Self::new(
+            info,
+            width,
+            height,
+            bytes_per_line.next_multiple_of(align),
+        ))
+    }
+
+    /// Calculate layout of image planes using format reported by driver
+    ///
+    /// The `bytes_per_line` of format is used for the first plane when it
+    /// is large enough to fit the line.
+    ///
+    /// Returns [None] for compressed and unknown formats.
+    pub fn from_pix_format(pixfmt: &PixFormat) -> Option<Self> {
+        let this = Self::compute(pixfmt.pixel_format(), pixfmt.width(), pixfmt.height(), 1)?;
+
+        Some(if pixfmt.bytes_per_line() > this.bytes_per_line[0] {
+            Self::new(this.info, this.width, this.height, pixfmt.bytes_per_line())
+        } else {
+            this
+        })
+    }
+
+    fn new(info: FormatInfo, width: u32, height: u32, bytes_per_line: u32) -> Self {
+        let planes = info.comp_planes() as usize;
+        let bpp0 = info.bpp(0) as u32;
+        let hsub = info.hsub() as u32;
+
+        let mut this = Self {
+            info,
+            width,
+            height,
+            bytes_per_line: [0; 4],
+            plane_size: [0; 4],
+            plane_offset: [0; 4],
+        };
+
+        let mut offset = 0;
+
+        for plane in 0..planes {
+            this.bytes_per_line[plane] = if plane > 0 {
+                bytes_per_line * info.bpp(plane) as u32 / (bpp0 * hsub)
+            } else {
+                bytes_per_line
+            };
+            this.plane_size[plane] = this.bytes_per_line[plane] * info.plane_height(plane, height);
+            if info.mem_planes() > 1 {
+                offset = 0;
+            }
+            this.plane_offset[plane] = offset;
+            offset += this.plane_size[plane];
+        }
+
+        this
+    }
+
+    /// Number of color planes
+    pub fn num_planes(&self) -> usize {
+        self.info.comp_planes() as _
+    }
+
+    /// Number of memory planes
+    pub fn num_mem_planes(&self) -> usize {
+        self.info.mem_planes() as _
+    }
+
+    /// Distance in bytes between lines of color plane
+    pub fn bytes_per_line(&self, plane: usize) -> u32 {
+        self.bytes_per_line.get(plane).copied().unwrap_or_default()
+    }
+
+    /// Size of color plane in bytes
+    pub fn plane_size(&self, plane: usize) -> u32 {
+        self.plane_size.get(plane).copied().unwrap_or_default()
+    }
+
+    /// Offset of color plane in memory plane which it belongs to
+    pub fn plane_offset(&self, plane: usize) -> u32 {
+        self.plane_offset.get(plane).copied().unwrap_or_default()
+    }
+
+    /// Index of memory plane which color plane belongs to
+    pub fn mem_plane(&self, plane: usize) -> usize {
+        if self.num_mem_planes() > 1 {
+            plane
+        } else {
+            0
+        }
+    }
+
+    /// Size of memory plane in bytes
+    pub fn mem_plane_size(&self, mem_plane: usize) -> u32 {
+        (0..self.num_planes())
+            .filter(|plane| self.mem_plane(*plane) == mem_plane)
+            .map(|plane| self.plane_size[plane])
+            .sum()
+    }
+
+    /// Total size of image in bytes
+    pub fn size_image(&self) -> u32 {
+        self.plane_size.iter().sum()
+    }
+
+    /// Fill single-planar format using layout
+    pub fn fill_pix_format(&self, pixfmt: &mut PixFormat) {
+        pixfmt.set_pixel_format(self.info.fourcc());
+        pixfmt.set_width(self.width);
+        pixfmt.set_height(self.height);
+        pixfmt.set_bytes_per_line(self.bytes_per_line[0]);
+        pixfmt.set_size_image(self.size_image());
+    }
+
+    /// Fill multi-planar format using layout
+    pub fn fill_pix_format_mplane(&self, pixfmt: &mut PixFormatMplane) {
+        pixfmt.set_pixel_format(self.info.fourcc());
+        pixfmt.set_width(self.width);
+        pixfmt.set_height(self.height);
+        pixfmt.num_planes = self.info.mem_planes();
+        for mem_plane in 0..self.num_mem_planes() {
+            let plane_fmt = &mut pixfmt.plane_fmt_mut()[mem_plane];
+            // first color plane in memory plane defines line length
+            plane_fmt.set_bytes_per_line(self.bytes_per_line[mem_plane]);
+            plane_fmt.set_size_image(self.mem_plane_size(mem_plane));
+        }
+    }
+}
+
+impl core::fmt::Display for PlaneLayout {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.width.fmt(f)?;
+        'x'.fmt(f)?;
+        self.height.fmt(f)?;
+        ' '.fmt(f)?;
+        self.info.fourcc().fmt(f)?;
+        for plane in 0..self.num_planes() {
+            " #".fmt(f)?;
+            self.bytes_per_line[plane].fmt(f)?;
+            '/'.fmt(f)?;
+            self.plane_size[plane].fmt(f)?;
+            '@'.fmt(f)?;
+            self.plane_offset[plane].fmt(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{calls, types::*, utils, Internal, Result};
+use std::os::unix::io::RawFd;
+
+impl Internal<Priority> {
+    /// Query highest priority of device handles
+    pub fn query(fd: RawFd) -> Result<Self> {
+        let mut prio = 0u32;
+
+        unsafe_call!(calls::g_priority(
+            fd,
+            &mut prio as *mut u32 as *mut Priority
+        ))?;
+
+        Priority::try_from(prio)
+            .map(Internal::from)
+            .map_err(|_| utils::invalid_data("Unknown priority"))
+    }
+
+    /// Set priority of device handle
+    pub fn set(&self, fd: RawFd) -> Result<()> {
+        unsafe_call!(calls::s_priority(fd, self.as_ref())).map(|_| ())
+    }
+}
@@ -0,0 +1,144 @@
+use crate::DeviceInfo;
+use core::time::Duration;
+use std::path::PathBuf;
+
+/// Persistent identity of device node
+///
+/// Used to find device node again after reconnection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceIdentity {
+    /// USB device with serial number
+    Usb {
+        /// Vendor identifier
+        vendor_id: u16,
+        /// Product identifier
+        product_id: u16,
+        /// Serial number
+        serial: String,
+        /// Index of node within device
+        index: u32,
+    },
+    /// Device on bus location
+    Bus {
+        /// Bus name
+        bus_info: String,
+        /// Index of node within device
+        index: u32,
+    },
+    /// Device node path
+    Path(PathBuf),
+}
+
+impl DeviceIdentity {
+    /// Create identity of device node
+    ///
+    /// USB serial number is preferred because it does not depend on port.
+    pub fn new(info: &DeviceInfo) -> Self {
+        if let Some((usb, serial)) = info
+            .usb()
+            .and_then(|usb| usb.serial().map(|serial| (usb, serial)))
+        {
+            Self::Usb {
+                vendor_id: usb.vendor_id(),
+                product_id: usb.product_id(),
+                serial: serial.into(),
+                index: info.index(),
+            }
+        } else if let Some(bus_info) = info.bus_info() {
+            Self::Bus {
+                bus_info: bus_info.into(),
+                index: info.index(),
+            }
+        } else {
+            Self::Path(info.path().into())
+        }
+    }
+
+    /// Check that device node has this identity
+    pub fn matches(&self, info: &DeviceInfo) -> bool {
+        match self {
+            Self::Usb {
+                vendor_id,
+                product_id,
+                serial,
+                index,
+            } => {
+                *index == info.index()
+                    && info.usb().is_some_and(|usb| {
+                        usb.vendor_id() == *vendor_id
+                            && usb.product_id() == *product_id
+                            && usb.serial() == Some(serial)
+                    })
+            }
+            Self::Bus { bus_info, index } => {
+                *index == info.index() && info.bus_info() == Some(bus_info)
+            }
+            Self::Path(path) => info.path() == path,
+        }
+    }
+}
+
+impl core::fmt::Display for DeviceIdentity {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Usb {
+                vendor_id,
+                product_id,
+                serial,
+                index,
+            } => write!(
+                f,
+                "usb {vendor_id:04x}:{product_id:04x} #{serial} node {index}"
+            ),
+            Self::Bus { bus_info, index } => write!(f, "bus {bus_info} node {index}"),
+            Self::Path(path) => path.display().fmt(f),
+        }
+    }
+}
+
+/// Device connection change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// Device was disconnected
+    Disconnected,
+    /// Device does not provide frames in time
+    Stalled,
+    /// Device was reopened and configured again
+    Reconnected(PathBuf),
+}
+
+impl core::fmt::Display for ConnectionEvent {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Disconnected => "disconnected".fmt(f),
+            Self::Stalled => "stalled".fmt(f),
+            Self::Reconnected(path) => {
+                "reconnected ".fmt(f)?;
+                path.display().fmt(f)
+            }
+        }
+    }
+}
+
+/// Reconnection behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Time without frames after which stream is considered stalled
+    pub stall_timeout: Duration,
+    /// Interval between attempts to find device
+    pub retry_interval: Duration,
+    /// Maximum time to wait for device reappearing
+    ///
+    /// Waiting is not limited when [None].
+    pub max_wait: Option<Duration>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            stall_timeout: Duration::from_secs(2),
+            retry_interval: Duration::from_millis(500),
+            max_wait: None,
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use super::buffer::Mapping;
+use crate::{
+    private::{event_fd, wait_readable},
+    types::*,
+    utils, BufferRef, Error, Internal, Method, Out, QueueData, Result,
+};
+use core::{
+    marker::PhantomData,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, Read, Write},
+    os::unix::io::{AsRawFd, RawFd},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Behavior of stream when all buffers is held by other threads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HoldPolicy {
+    /// Wait until some buffer will be released
+    #[default]
+    Block,
+    /// Take back the oldest held buffer which is not accessed at the moment
+    ///
+    /// The data of taken buffer becomes unavailable for holder.
+    DropOldest,
+    /// Fail with [io::ErrorKind::ResourceBusy]
+    Error,
+}
+
+/// Buffer metadata shared between stream and holder
+///
+/// Empty when buffer is taken back by stream.
+struct SharedState(Option<Internal<Buffer>>);
+
+unsafe impl Send for SharedState {}
+
+impl SharedState {
+    fn lock(this: &Mutex<Self>) -> MutexGuard<'_, Self> {
+        this.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Buffer released by other thread
+pub struct ReturnedBuffer(Internal<Buffer>);
+
+unsafe impl Send for ReturnedBuffer {}
+
+/// Pending released buffers and waiters
+struct ChannelState {
+    buffers: VecDeque<ReturnedBuffer>,
+    wakers: Vec<Waker>,
+    closed: bool,
+}
+
+/// Channel of buffers released by other threads
+///
+/// The lock is never held while waiting so abandoned waiting takes nothing.
+struct Channel {
+    state: Mutex<ChannelState>,
+    /// Readable while channel has buffers or closed
+    event: File,
+}
+
+impl Channel {
+    fn lock(&self) -> MutexGuard<'_, ChannelState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn notify(&self, mut state: MutexGuard<'_, ChannelState>) {
+        let _ = (&self.event).write_all(&1u64.to_ne_bytes());
+        let wakers = core::mem::take(&mut state.wakers);
+        drop(state);
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    fn send(&self, buffer: ReturnedBuffer) {
+        let mut state = self.lock();
+        // stream may be already closed
+        if !state.closed {
+            state.buffers.push_back(buffer);
+            self.notify(state);
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        state.buffers.clear();
+        self.notify(state);
+    }
+
+    fn pop(&self, state: &mut ChannelState) -> Result<Option<ReturnedBuffer>> {
+        let buffer = state.buffers.pop_front();
+        if buffer.is_none() && state.closed {
+            return Err(Error::new(io::ErrorKind::BrokenPipe, "Stream is closed"));
+        }
+        if state.buffers.is_empty() && !state.closed {
+            // reset event counter
+            let mut counter = [0u8; 8];
+            let _ = (&self.event).read(&mut counter);
+        }
+        Ok(buffer)
+    }
+
+    fn try_recv(&self) -> Result<Option<ReturnedBuffer>> {
+        self.pop(&mut self.lock())
+    }
+}
+
+/// Sending side of channel which closes it when stream is dropped
+pub(super) struct ReturnsSender(Arc<Channel>);
+
+impl Drop for ReturnsSender {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl ReturnsSender {
+    pub(super) fn new() -> Result<(Self, BufferReturns)> {
+        let channel = Arc::new(Channel {
+            state: Mutex::new(ChannelState {
+                buffers: VecDeque::new(),
+                wakers: Vec::new(),
+                closed: false,
+            }),
+            event: event_fd()?,
+        });
+        Ok((Self(channel.clone()), BufferReturns(channel)))
+    }
+}
+
+/// Receiver of buffers released by other threads
+///
+/// It can be used to wait for releasing buffers outside of stream.
+/// The descriptor becomes readable when some buffer is released.
+#[derive(Clone)]
+pub struct BufferReturns(Arc<Channel>);
+
+impl AsRawFd for BufferReturns {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.event.as_raw_fd()
+    }
+}
+
+impl BufferReturns {
+    /// Wait for next released buffer
+    pub fn recv(&self) -> Result<ReturnedBuffer> {
+        loop {
+            if let Some(buffer) = self.0.try_recv()? {
+                return Ok(buffer);
+            }
+            wait_readable(self.as_raw_fd(), None, None)?;
+        }
+    }
+
+    /// Poll for next released buffer
+    ///
+    /// Dropping of pending future takes no buffer.
+    pub fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<ReturnedBuffer>> {
+        let mut state = self.0.lock();
+        if let Some(buffer) = self.0.pop(&mut state).transpose() {
+            return Poll::Ready(buffer);
+        }
+        if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    /// Get next released buffer without waiting
+    pub fn try_recv(&self) -> Option<ReturnedBuffer> {
+        self.0.try_recv().ok().flatten()
+    }
+}
+
+/// Buffer lent to other thread
+pub(super) struct Lent<Dir, Met: Method> {
+    index: u32,
+    /// Keeps buffer out of queue until released
+    buffer: BufferRef<Dir, Met>,
+    state: Arc<Mutex<SharedState>>,
+}
+
+impl<Dir, Met: Method> Internal<QueueData<Dir, Met>> {
+    /// Lend buffer to other thread
+    ///
+    /// The buffer will be enqueued again after releasing.
+    pub fn lend(&self, buffer: BufferRef<Dir, Met>) -> SharedBuffer<Dir, Met> {
+        let (mapping, data) = buffer.share();
+        let state = Arc::new(Mutex::new(SharedState(Some(data))));
+
+        self.lent.lock().push_back(Lent {
+            index: data.index,
+            buffer,
+            state: state.clone(),
+        });
+
+        SharedBuffer {
+            mapping,
+            state,
+            returns: (self.returns.0).0.clone(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get receiver of released buffers
+    pub fn returns(&self) -> BufferReturns {
+        self.returns.1.clone()
+    }
+
+    /// Take back released buffer
+    pub fn restore(&self, returned: ReturnedBuffer) {
+        let entry = {
+            let mut lent = self.lent.lock();
+            lent.iter()
+                .position(|entry| entry.index == returned.0.index)
+                .and_then(|position| lent.remove(position))
+        };
+
+        if let Some(entry) = entry {
+            entry.buffer.restore(returned.0);
+        }
+    }
+
+    /// Take back all released buffers and apply hold policy
+    ///
+    /// Returns `false` when caller should wait for releasing some buffer.
+    pub fn reserve(&self, policy: HoldPolicy) -> Result<bool> {
+        while let Some(returned) = self.returns.1.try_recv() {
+            self.restore(returned);
+        }
+
+        if self.lent_len() < self.len() {
+            return Ok(true);
+        }
+
+        match policy {
+            HoldPolicy::Block => Ok(false),
+            HoldPolicy::DropOldest => Ok(self.revoke_oldest()),
+            HoldPolicy::Error => Err(utils::resource_busy("All buffers is held")),
+        }
+    }
+
+    /// Take back oldest lent buffer which is not accessed by holder
+    fn revoke_oldest(&self) -> bool {
+        let mut lent = self.lent.lock();
+
+        for position in 0..lent.len() {
+            let buffer = lent[position]
+                .state
+                .try_lock()
+                .ok()
+                .and_then(|mut state| state.0.take());
+
+            if let Some(buffer) = buffer {
+                if let Some(entry) = lent.remove(position) {
+                    entry.buffer.restore(buffer);
+                }
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Buffer which can be sent to other thread
+///
+/// The buffer is returned to stream when dropped.
+pub struct SharedBuffer<Dir, Met: Method> {
+    mapping: Arc<Mapping<Met>>,
+    state: Arc<Mutex<SharedState>>,
+    returns: Arc<Channel>,
+    _phantom: PhantomData<Dir>,
+}
+
+impl<Dir, Met: Method> Drop for SharedBuffer<Dir, Met> {
+    fn drop(&mut self) {
+        if let Some(buffer) = SharedState::lock(&self.state).0.take() {
+            self.returns.send(ReturnedBuffer(buffer));
+        }
+    }
+}
+
+impl<Dir, Met: Method> SharedBuffer<Dir, Met> {
+    /// Get access to buffer data
+    ///
+    /// Returns [None] when buffer was taken back by stream
+    /// according to [HoldPolicy::DropOldest].
+    pub fn lock(&self) -> Option<SharedData<'_, Dir, Met>> {
+        let state = SharedState::lock(&self.state);
+
+        if state.0.is_some() {
+            Some(SharedData {
+                state,
+                pointer: self.mapping.pointer(),
+                _phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Check that buffer was taken back by stream
+    pub fn is_dropped(&self) -> bool {
+        SharedState::lock(&self.state).0.is_none()
+    }
+}
+
+impl<Dir, Met: Method> core::fmt::Display for SharedBuffer<Dir, Met> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if let Some(buffer) = &SharedState::lock(&self.state).0 {
+            buffer.as_ref().fmt(f)
+        } else {
+            "dropped".fmt(f)
+        }
+    }
+}
+
+/// Data of buffer lent to other thread
+pub struct SharedData<'r, Dir, Met: Method> {
+    state: MutexGuard<'r, SharedState>,
+    pointer: *mut u8,
+    _phantom: PhantomData<(Dir, Met)>,
+}
+
+impl<'r, Dir, Met: Method> core::ops::Deref for SharedData<'r, Dir, Met> {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Self::Target {
+        // state cannot be emptied while locked
+        self.state.0.as_ref().unwrap()
+    }
+}
+
+impl<'r, Met: Method> core::ops::DerefMut for SharedData<'r, Out, Met> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.state.0.as_mut().unwrap()
+    }
+}
+
+impl<'r, Dir, Met: Method> AsRef<[u8]> for SharedData<'r, Dir, Met> {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.pointer, self.len()) }
+    }
+}
+
+impl<'r, Met: Method> AsMut<[u8]> for SharedData<'r, Out, Met> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.pointer, self.len()) }
+    }
+}
+
+impl<'r, Met: Method> SharedData<'r, Out, Met> {
+    /// Set new size of buffer
+    ///
+    /// New size should be less than or equal to capacity.
+    /// If new size greater than capacity it will be set to be equal to capacity.
+    pub fn set_len(&mut self, len: usize) {
+        self.bytes_used = self.length.min(len as _);
+    }
+}
+
+impl<'r, Dir, Met: Method> SharedData<'r, Dir, Met> {
+    /// Check no used bytes in buffer
+    pub fn is_empty(&self) -> bool {
+        self.bytes_used == 0
+    }
+
+    /// Get used data of buffer in bytes
+    pub fn len(&self) -> usize {
+        self.bytes_used as _
+    }
+
+    /// Get available buffer capacity in bytes
+    pub fn capacity(&self) -> usize {
+        self.length as _
+    }
+}
+
+impl<'r, Dir, Met: Method> core::fmt::Display for SharedData<'r, Dir, Met> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
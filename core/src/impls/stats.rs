@@ -0,0 +1,277 @@
+use crate::{types::*, IsTimestamp};
+use core::time::Duration;
+use std::collections::VecDeque;
+
+/// Number of samples to calculate rolling averages
+const AVERAGE_WINDOW: usize = 32;
+
+/// Width of histogram bins
+const HISTOGRAM_BIN: Duration = Duration::from_millis(1);
+
+/// Number of histogram bins
+const HISTOGRAM_BINS: usize = 64;
+
+/// Rolling average of durations
+#[derive(Debug, Clone)]
+pub struct RollingAverage {
+    samples: VecDeque<Duration>,
+    window: usize,
+    sum: Duration,
+}
+
+impl RollingAverage {
+    /// Create average over specified number of last samples
+    pub fn new(window: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(window),
+            window: window.max(1),
+            sum: Duration::ZERO,
+        }
+    }
+
+    /// Add sample
+    pub fn add(&mut self, sample: Duration) {
+        if self.samples.len() >= self.window {
+            if let Some(first) = self.samples.pop_front() {
+                self.sum -= first;
+            }
+        }
+        self.samples.push_back(sample);
+        self.sum += sample;
+    }
+
+    /// Get average value
+    ///
+    /// Returns [None] when no samples.
+    pub fn average(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.samples.len() as u32)
+        }
+    }
+
+    /// Get maximum value of samples
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.iter().max().copied()
+    }
+
+    /// Get number of samples
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Check that no samples
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+/// Histogram of durations with fixed bins
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bin_width: Duration,
+    bins: Vec<u64>,
+    overflow: u64,
+}
+
+impl Histogram {
+    /// Create histogram with specified bin width and number of bins
+    pub fn new(bin_width: Duration, bins: usize) -> Self {
+        Self {
+            bin_width,
+            bins: vec![0; bins],
+            overflow: 0,
+        }
+    }
+
+    /// Add sample
+    pub fn add(&mut self, sample: Duration) {
+        let index = sample.as_nanos() / self.bin_width.as_nanos().max(1);
+        if let Some(bin) = self.bins.get_mut(index as usize) {
+            *bin += 1;
+        } else {
+            self.overflow += 1;
+        }
+    }
+
+    /// Width of single bin
+    pub fn bin_width(&self) -> Duration {
+        self.bin_width
+    }
+
+    /// Counters of samples in bins
+    ///
+    /// Bin `N` counts samples from `N * bin_width` up to `(N + 1) * bin_width`.
+    pub fn bins(&self) -> &[u64] {
+        &self.bins
+    }
+
+    /// Number of samples which is out of bins
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    /// Total number of samples
+    pub fn count(&self) -> u64 {
+        self.bins.iter().sum::<u64>() + self.overflow
+    }
+}
+
+/// Stream frames statistics
+///
+/// Detects dropped frames by gaps in sequence numbers,
+/// measures frame interval jitter against nominal frame interval
+/// and latency between capture and dequeueing of frames.
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    time_per_frame: Option<Duration>,
+    last_sequence: Option<u32>,
+    last_timestamp: Option<Duration>,
+    frames: u64,
+    dropped: u64,
+    errors: u64,
+    interval: RollingAverage,
+    jitter: RollingAverage,
+    latency: RollingAverage,
+    jitter_histogram: Histogram,
+    latency_histogram: Histogram,
+}
+
+impl StreamStats {
+    /// Create statistics collector
+    ///
+    /// When nominal frame interval is unknown jitter is measured against average interval.
+    pub fn new(time_per_frame: Option<Duration>) -> Self {
+        Self {
+            time_per_frame,
+            last_sequence: None,
+            last_timestamp: None,
+            frames: 0,
+            dropped: 0,
+            errors: 0,
+            interval: RollingAverage::new(AVERAGE_WINDOW),
+            jitter: RollingAverage::new(AVERAGE_WINDOW),
+            latency: RollingAverage::new(AVERAGE_WINDOW),
+            jitter_histogram: Histogram::new(HISTOGRAM_BIN, HISTOGRAM_BINS),
+            latency_histogram: Histogram::new(HISTOGRAM_BIN, HISTOGRAM_BINS),
+        }
+    }
+
+    /// Update statistics using dequeued buffer
+    ///
+    /// The `now` is a `CLOCK_MONOTONIC` time of dequeueing.
+    pub fn update(&mut self, buffer: &Buffer, now: Duration) {
+        self.frames += 1;
+
+        if buffer.flags().contains(BufferFlag::Error) {
+            self.errors += 1;
+        }
+
+        let sequence = buffer.sequence();
+        let timestamp = Duration::from_time_val(buffer.timestamp);
+
+        // sequence restarts when stream restarted
+        let frames = match self.last_sequence {
+            Some(last) if sequence > last => sequence - last,
+            _ => 0,
+        };
+
+        if frames > 1 {
+            self.dropped += (frames - 1) as u64;
+        }
+
+        if let Some(last) = self.last_timestamp {
+            if frames > 0 && timestamp > last {
+                let interval = (timestamp - last) / frames;
+                self.interval.add(interval);
+
+                if let Some(expected) = self.time_per_frame.or_else(|| self.interval.average()) {
+                    let jitter = interval.abs_diff(expected);
+                    self.jitter.add(jitter);
+                    self.jitter_histogram.add(jitter);
+                }
+            }
+        }
+
+        if buffer.flags().timestamp() == Ok(Timestamp::Monotonic) && now >= timestamp {
+            let latency = now - timestamp;
+            self.latency.add(latency);
+            self.latency_histogram.add(latency);
+        }
+
+        self.last_sequence = Some(sequence);
+        self.last_timestamp = Some(timestamp);
+    }
+
+    /// Nominal frame interval
+    pub fn time_per_frame(&self) -> Option<Duration> {
+        self.time_per_frame
+    }
+
+    /// Number of received frames
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Number of dropped frames
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Number of frames marked with [BufferFlag::Error]
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Rolling average of frame interval
+    pub fn interval(&self) -> &RollingAverage {
+        &self.interval
+    }
+
+    /// Rolling average of frame interval jitter
+    pub fn jitter(&self) -> &RollingAverage {
+        &self.jitter
+    }
+
+    /// Rolling average of latency between capture and dequeueing
+    ///
+    /// Latency is measured only for monotonic timestamps.
+    pub fn latency(&self) -> &RollingAverage {
+        &self.latency
+    }
+
+    /// Histogram of frame interval jitter
+    pub fn jitter_histogram(&self) -> &Histogram {
+        &self.jitter_histogram
+    }
+
+    /// Histogram of latency between capture and dequeueing
+    pub fn latency_histogram(&self) -> &Histogram {
+        &self.latency_histogram
+    }
+}
+
+impl core::fmt::Display for StreamStats {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        "frames: ".fmt(f)?;
+        self.frames.fmt(f)?;
+        ", dropped: ".fmt(f)?;
+        self.dropped.fmt(f)?;
+        ", errors: ".fmt(f)?;
+        self.errors.fmt(f)?;
+        if let Some(interval) = self.interval.average() {
+            ", interval: ".fmt(f)?;
+            core::fmt::Debug::fmt(&interval, f)?;
+        }
+        if let Some(jitter) = self.jitter.average() {
+            ", jitter: ".fmt(f)?;
+            core::fmt::Debug::fmt(&jitter, f)?;
+        }
+        if let Some(latency) = self.latency.average() {
+            ", latency: ".fmt(f)?;
+            core::fmt::Debug::fmt(&latency, f)?;
+        }
+        Ok(())
+    }
+}
@@ -61,6 +61,17 @@ impl StreamParm {
             None
         }
     }
+
+    /// Get nominal frame interval
+    ///
+    /// Returns [None] when interval is not set.
+    pub fn time_per_frame(&self) -> Option<core::time::Duration> {
+        self.try_ref::<CaptureParm>()
+            .map(|parm| parm.time_per_frame)
+            .or_else(|| self.try_ref::<OutputParm>().map(|parm| parm.time_per_frame))
+            .and_then(|fract| fract.to_duration())
+            .filter(|duration| !duration.is_zero())
+    }
 }
 
 /// Stream parameter types
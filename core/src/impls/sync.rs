@@ -0,0 +1,68 @@
+use core::time::Duration;
+
+/// The way to pair frames of several streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Pair frames which monotonic timestamps differ not more than tolerance
+    Timestamp(Duration),
+    /// Pair frames by sequence numbers of hardware synchronized streams
+    ///
+    /// Sequence offsets of streams is determined by first [EventFrameSync](crate::types::EventFrameSync)
+    /// events so all devices should be triggered by common source after start.
+    FrameSync,
+}
+
+impl Default for SyncMode {
+    fn default() -> Self {
+        Self::Timestamp(Duration::from_millis(5))
+    }
+}
+
+/// Synchronized capture statistics
+#[derive(Debug, Clone, Default)]
+pub struct SyncStats {
+    pub(crate) groups: u64,
+    pub(crate) unmatched: Vec<u64>,
+    pub(crate) dropped: Vec<u64>,
+}
+
+impl SyncStats {
+    pub(crate) fn new(streams: usize) -> Self {
+        Self {
+            groups: 0,
+            unmatched: vec![0; streams],
+            dropped: vec![0; streams],
+        }
+    }
+
+    /// Number of aligned groups of frames
+    pub fn groups(&self) -> u64 {
+        self.groups
+    }
+
+    /// Number of frames of each stream which was skipped because no pair found
+    pub fn unmatched(&self) -> &[u64] {
+        &self.unmatched
+    }
+
+    /// Number of frames of each stream which was dropped by driver
+    pub fn dropped(&self) -> &[u64] {
+        &self.dropped
+    }
+}
+
+impl core::fmt::Display for SyncStats {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        "groups: ".fmt(f)?;
+        self.groups.fmt(f)?;
+        for (index, (unmatched, dropped)) in self.unmatched.iter().zip(&self.dropped).enumerate() {
+            ", #".fmt(f)?;
+            index.fmt(f)?;
+            " unmatched: ".fmt(f)?;
+            unmatched.fmt(f)?;
+            " dropped: ".fmt(f)?;
+            dropped.fmt(f)?;
+        }
+        Ok(())
+    }
+}
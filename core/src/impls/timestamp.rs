@@ -1,7 +1,128 @@
-use crate::types::{TimeSpec, TimeVal};
+use crate::types::{BufferFlag, TimeSpec, TimeVal, Timestamp, TimestampSrc};
 use core::time::Duration;
 use nix::sys::time::TimeValLike;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
+
+/// Get current time of `CLOCK_MONOTONIC`
+///
+/// The same clock is used by kernel for monotonic buffer timestamps.
+pub fn monotonic_time() -> Duration {
+    use nix::time::{clock_gettime, ClockId};
+
+    clock_gettime(ClockId::CLOCK_MONOTONIC)
+        .map(Duration::from_time_spec)
+        .unwrap_or_default()
+}
+
+/// Kind of buffer timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampKind {
+    /// Clock of timestamp
+    pub clock: Timestamp,
+    /// Moment of frame which timestamp corresponds to
+    pub source: TimestampSrc,
+}
+
+impl From<BufferFlag> for TimestampKind {
+    fn from(flags: BufferFlag) -> Self {
+        Self {
+            clock: flags.timestamp().unwrap_or(Timestamp::Unknown),
+            source: flags.timestamp_src().unwrap_or(TimestampSrc::Eof),
+        }
+    }
+}
+
+impl TimestampKind {
+    /// Timestamp is taken from `CLOCK_MONOTONIC`
+    pub fn is_monotonic(&self) -> bool {
+        self.clock == Timestamp::Monotonic
+    }
+}
+
+impl core::fmt::Display for TimestampKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.clock.fmt(f)?;
+        ' '.fmt(f)?;
+        self.source.fmt(f)
+    }
+}
+
+/// Simultaneous readings of monotonic, instant and system clocks
+///
+/// Used to convert monotonic timestamps into other clock domains.
+/// Single readings may be reused to convert many timestamps consistently.
+#[derive(Debug, Clone, Copy)]
+pub struct ClockSync {
+    monotonic: Duration,
+    instant: Instant,
+    system: SystemTime,
+}
+
+impl ClockSync {
+    /// Read clocks
+    pub fn now() -> Self {
+        let instant = Instant::now();
+        // system clock is read between two monotonic readings to reduce skew
+        let before = monotonic_time();
+        let system = SystemTime::now();
+        let after = monotonic_time();
+
+        Self {
+            monotonic: before + (after - before) / 2,
+            instant,
+            system,
+        }
+    }
+
+    /// Convert monotonic time into instant
+    pub fn to_instant(&self, monotonic: Duration) -> Instant {
+        if monotonic <= self.monotonic {
+            self.instant - (self.monotonic - monotonic)
+        } else {
+            self.instant + (monotonic - self.monotonic)
+        }
+    }
+
+    /// Convert monotonic time into system time
+    pub fn to_system_time(&self, monotonic: Duration) -> SystemTime {
+        if monotonic <= self.monotonic {
+            self.system - (self.monotonic - monotonic)
+        } else {
+            self.system + (monotonic - self.monotonic)
+        }
+    }
+
+    /// Convert instant into monotonic time
+    pub fn to_monotonic(&self, instant: Instant) -> Duration {
+        if instant <= self.instant {
+            self.monotonic.saturating_sub(self.instant - instant)
+        } else {
+            self.monotonic + (instant - self.instant)
+        }
+    }
+}
+
+/// Convert monotonic timestamp into instant
+///
+/// Returns [None] when timestamp is not monotonic.
+pub(crate) fn timestamp_instant(time_val: TimeVal, flags: BufferFlag) -> Option<Instant> {
+    if TimestampKind::from(flags).is_monotonic() {
+        Some(ClockSync::now().to_instant(Duration::from_time_val(time_val)))
+    } else {
+        None
+    }
+}
+
+/// Convert monotonic timestamp into system time
+///
+/// Returns [None] when timestamp is not monotonic.
+pub(crate) fn timestamp_system_time(time_val: TimeVal, flags: BufferFlag) -> Option<SystemTime> {
+    if TimestampKind::from(flags).is_monotonic() {
+        Some(ClockSync::now().to_system_time(Duration::from_time_val(time_val)))
+    } else {
+        None
+    }
+}
 
 /// Something which can be used as timestamp
 pub trait IsTimestamp {
@@ -54,6 +175,9 @@ impl IsTimestamp for Duration {
     }
 }
 
+/// Raw conversion which treats timestamp as time since UNIX epoch
+///
+/// Buffer timestamps usually is monotonic so use [ClockSync] to get actual system time.
 impl IsTimestamp for SystemTime {
     fn from_time_val(time_val: TimeVal) -> Self {
         SystemTime::UNIX_EPOCH + Duration::from_time_val(time_val)
@@ -216,6 +216,20 @@ impl core::fmt::Display for Rect {
     }
 }
 
+impl Fract {
+    /// Convert fraction of seconds into duration
+    ///
+    /// Returns [None] when denominator is zero.
+    pub fn to_duration(&self) -> Option<core::time::Duration> {
+        if self.denominator == 0 {
+            return None;
+        }
+        Some(core::time::Duration::from_nanos(
+            self.numerator as u64 * 1_000_000_000 / self.denominator as u64,
+        ))
+    }
+}
+
 impl core::fmt::Display for Fract {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         self.numerator.fmt(f)?;
@@ -232,9 +246,27 @@ impl core::fmt::Display for Area {
     }
 }
 
+impl TimeCode {
+    /// Type of time code
+    ///
+    /// Returns raw value when type is unknown.
+    pub fn type_(&self) -> core::result::Result<TimeCodeType, u32> {
+        TimeCodeType::try_from(self.type_)
+    }
+
+    /// Set type of time code
+    pub fn set_type_(&mut self, type_: TimeCodeType) -> &mut Self {
+        self.type_ = type_ as _;
+        self
+    }
+}
+
 impl core::fmt::Display for TimeCode {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        self.type_.fmt(f)?;
+        match self.type_() {
+            Ok(type_) => type_.fmt(f),
+            Err(type_) => type_.fmt(f),
+        }?;
         if !self.flags.is_none() {
             ' '.fmt(f)?;
             self.flags.fmt(f)?;
@@ -8,14 +8,24 @@ compile_error!("This crate support Linux only");
 #[macro_use]
 mod macros;
 
-mod calls;
+pub mod calls;
 mod extras;
 mod impls;
 mod safe_ref;
+#[cfg(feature = "tracing")]
+mod trace;
 mod types;
 mod utils;
 
+pub mod convert;
+pub mod error;
+pub mod mjpeg;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod private;
+pub mod raw;
+#[cfg(feature = "record")]
+pub mod record;
 
 pub use impls::*;
 pub use types::*;
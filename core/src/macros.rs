@@ -154,3 +154,26 @@ macro_rules! unsafe_call {
         unsafe { $res }.map_err(crate::Error::from)
     };
 }
+
+macro_rules! checked_call {
+    ($name:ident, $nr:expr, $data:expr, $call:expr) => {
+        $call.map_err(|errno| {
+            crate::error::Error::call(stringify!($name), $nr, unsafe { $data.as_ref() }, errno)
+                .into()
+        })
+    };
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! traced_call {
+    ($name:ident, $data:expr, $call:expr) => {
+        crate::trace::traced(stringify!($name), $data as *const _, || $call)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! traced_call {
+    ($name:ident, $data:expr, $call:expr) => {
+        $call
+    };
+}
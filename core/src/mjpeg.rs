@@ -0,0 +1,358 @@
+//! Motion JPEG frames
+//!
+//! Many UVC cameras omit Huffman tables (DHT) in frames, because the MJPEG
+//! format implies the standard tables. This module validates such frames,
+//! splices the standard tables in when missing and optionally decodes it.
+
+#[cfg(feature = "mjpeg")]
+use crate::types::*;
+use crate::{utils, Result};
+use getset::CopyGetters;
+use std::borrow::Cow;
+
+const SOI: u8 = 0xd8;
+const EOI: u8 = 0xd9;
+const SOS: u8 = 0xda;
+const DQT: u8 = 0xdb;
+const DHT: u8 = 0xc4;
+const TEM: u8 = 0x01;
+const RST0: u8 = 0xd0;
+const RST7: u8 = 0xd7;
+
+/// Standard Huffman tables (ITU-T T.81 Annex K.3)
+const HUFFMAN_TABLES: [(u8, [u8; 16], &[u8]); 4] = [
+    (
+        0x00,
+        [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0],
+        &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    ),
+    (
+        0x01,
+        [0, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0],
+        &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    ),
+    (
+        0x10,
+        [0, 2, 1, 3, 3, 2, 4, 3, 5, 5, 4, 4, 0, 0, 1, 0x7d],
+        &[
+            0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, 0x21, 0x31, 0x41, 0x06, 0x13, 0x51,
+            0x61, 0x07, 0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xa1, 0x08, 0x23, 0x42, 0xb1, 0xc1,
+            0x15, 0x52, 0xd1, 0xf0, 0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0a, 0x16, 0x17, 0x18,
+            0x19, 0x1a, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39,
+            0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x53, 0x54, 0x55, 0x56, 0x57,
+            0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x73, 0x74, 0x75,
+            0x76, 0x77, 0x78, 0x79, 0x7a, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x92,
+            0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+            0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3,
+            0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8,
+            0xd9, 0xda, 0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf1, 0xf2,
+            0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa,
+        ],
+    ),
+    (
+        0x11,
+        [0, 2, 1, 2, 4, 4, 3, 4, 7, 5, 4, 4, 0, 1, 2, 0x77],
+        &[
+            0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, 0x31, 0x06, 0x12, 0x41, 0x51, 0x07,
+            0x61, 0x71, 0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, 0xa1, 0xb1, 0xc1, 0x09,
+            0x23, 0x33, 0x52, 0xf0, 0x15, 0x62, 0x72, 0xd1, 0x0a, 0x16, 0x24, 0x34, 0xe1, 0x25,
+            0xf1, 0x17, 0x18, 0x19, 0x1a, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x35, 0x36, 0x37, 0x38,
+            0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x53, 0x54, 0x55, 0x56,
+            0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x73, 0x74,
+            0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89,
+            0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5,
+            0xa6, 0xa7, 0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba,
+            0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6,
+            0xd7, 0xd8, 0xd9, 0xda, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf2,
+            0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa,
+        ],
+    ),
+];
+
+/// Standard luminance quantization table (ITU-T T.81 Annex K.1) in natural order
+const LUMA_QUANT: [u8; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+/// Natural order indexes of zig-zag ordered coefficients
+const ZIGZAG: [u8; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+/// Build DHT segment with standard Huffman tables
+fn dht_segment() -> Vec<u8> {
+    let length = 2 + HUFFMAN_TABLES
+        .iter()
+        .map(|(_, _, values)| 17 + values.len())
+        .sum::<usize>();
+
+    let mut segment = Vec::with_capacity(2 + length);
+    segment.extend_from_slice(&[0xff, DHT]);
+    segment.extend_from_slice(&(length as u16).to_be_bytes());
+    for (class_id, bits, values) in &HUFFMAN_TABLES {
+        segment.push(*class_id);
+        segment.extend_from_slice(bits);
+        segment.extend_from_slice(values);
+    }
+    segment
+}
+
+/// Estimate IJG quality factor using luminance quantization table
+fn estimate_quality(table: impl Iterator<Item = u16>) -> u8 {
+    let (sum, std_sum) = table
+        .zip(ZIGZAG)
+        .fold((0u32, 0u32), |(sum, std_sum), (value, index)| {
+            (
+                sum + value as u32,
+                std_sum + LUMA_QUANT[index as usize] as u32,
+            )
+        });
+
+    if std_sum == 0 {
+        return 0;
+    }
+
+    let scale = (sum * 100 + std_sum / 2) / std_sum;
+    let quality = if scale <= 100 {
+        (200 - scale) / 2
+    } else {
+        5000 / scale
+    };
+
+    quality.clamp(1, 100) as _
+}
+
+/// MJPEG frame information
+#[derive(Debug, Clone, Copy, CopyGetters)]
+pub struct FrameInfo {
+    /// Frame width in pixels
+    #[getset(get_copy = "pub")]
+    width: u16,
+
+    /// Frame height in lines
+    #[getset(get_copy = "pub")]
+    height: u16,
+
+    /// Number of color components
+    #[getset(get_copy = "pub")]
+    components: u8,
+
+    /// Estimated quality factor (1..=100)
+    ///
+    /// Estimated from luminance quantization table like IJG libjpeg does.
+    #[getset(get_copy = "pub")]
+    quality: Option<u8>,
+
+    /// Frame has Huffman tables
+    #[getset(get_copy = "pub")]
+    has_dht: bool,
+
+    /// Size of frame in bytes including EOI marker
+    ///
+    /// UVC drivers may report bigger size of data due to padding.
+    #[getset(get_copy = "pub")]
+    size: usize,
+
+    /// Offset of SOS marker
+    sos_offset: usize,
+}
+
+impl FrameInfo {
+    /// Parse frame headers
+    ///
+    /// Checks SOI and EOI markers so truncated frames (usually marked
+    /// by [BufferFlag::Error](crate::BufferFlag::Error)) are rejected.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < 4 || data[0] != 0xff || data[1] != SOI {
+            return Err(utils::invalid_data("Missing SOI marker"));
+        }
+
+        // skip padding after EOI
+        let end = data.len() - data.iter().rev().take_while(|byte| **byte == 0).count();
+        if end < 4 || data[end - 2] != 0xff || data[end - 1] != EOI {
+            return Err(utils::invalid_data("Missing EOI marker"));
+        }
+
+        let mut info = Self {
+            width: 0,
+            height: 0,
+            components: 0,
+            quality: None,
+            has_dht: false,
+            size: end,
+            sos_offset: 0,
+        };
+
+        let mut offset = 2;
+        loop {
+            if offset + 4 > end || data[offset] != 0xff {
+                return Err(utils::invalid_data("Invalid JPEG segment"));
+            }
+
+            let marker = data[offset + 1];
+            if marker == 0xff {
+                // fill byte
+                offset += 1;
+                continue;
+            }
+            if marker == TEM || (RST0..=RST7).contains(&marker) {
+                offset += 2;
+                continue;
+            }
+
+            let length = u16::from_be_bytes([data[offset + 2], data[offset + 3]]) as usize;
+            if length < 2 || offset + 2 + length > end {
+                return Err(utils::invalid_data("Truncated JPEG segment"));
+            }
+            let segment = &data[offset + 4..offset + 2 + length];
+
+            match marker {
+                SOS => {
+                    info.sos_offset = offset;
+                    break;
+                }
+                DHT => info.has_dht = true,
+                DQT => info.parse_dqt(segment)?,
+                // SOF0..SOF15 except DHT, JPG and DAC
+                0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                    if segment.len() < 6 {
+                        return Err(utils::invalid_data("Truncated SOF segment"));
+                    }
+                    info.height = u16::from_be_bytes([segment[1], segment[2]]);
+                    info.width = u16::from_be_bytes([segment[3], segment[4]]);
+                    info.components = segment[5];
+                }
+                EOI => return Err(utils::invalid_data("Missing SOS marker")),
+                _ => (),
+            }
+
+            offset += 2 + length;
+        }
+
+        if info.components == 0 {
+            return Err(utils::invalid_data("Missing SOF marker"));
+        }
+
+        Ok(info)
+    }
+
+    fn parse_dqt(&mut self, mut segment: &[u8]) -> Result<()> {
+        while let Some((&pq_tq, rest)) = segment.split_first() {
+            let precision = (pq_tq >> 4) as usize + 1;
+            let len = 64 * precision;
+            if rest.len() < len {
+                return Err(utils::invalid_data("Truncated DQT segment"));
+            }
+            let table = &rest[..len];
+
+            if pq_tq & 0xf == 0 {
+                self.quality = Some(if precision > 1 {
+                    estimate_quality(
+                        table
+                            .chunks_exact(2)
+                            .map(|value| u16::from_be_bytes([value[0], value[1]])),
+                    )
+                } else {
+                    estimate_quality(table.iter().map(|value| *value as u16))
+                });
+            }
+
+            segment = &rest[len..];
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for FrameInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.width.fmt(f)?;
+        'x'.fmt(f)?;
+        self.height.fmt(f)?;
+        " #".fmt(f)?;
+        self.components.fmt(f)?;
+        if let Some(quality) = self.quality {
+            " Q".fmt(f)?;
+            quality.fmt(f)?;
+        }
+        if !self.has_dht {
+            " no-DHT".fmt(f)?;
+        }
+        ' '.fmt(f)?;
+        self.size.fmt(f)
+    }
+}
+
+/// Get complete JPEG image from MJPEG frame
+///
+/// Inserts standard Huffman tables when frame has no it and drops padding
+/// after EOI marker. Frame data returned as is when nothing to fix.
+pub fn with_dht(data: &[u8]) -> Result<Cow<'_, [u8]>> {
+    let info = FrameInfo::parse(data)?;
+    let data = &data[..info.size];
+
+    Ok(if info.has_dht {
+        Cow::Borrowed(data)
+    } else {
+        let dht = dht_segment();
+        let mut image = Vec::with_capacity(data.len() + dht.len());
+        image.extend_from_slice(&data[..info.sos_offset]);
+        image.extend_from_slice(&dht);
+        image.extend_from_slice(&data[info.sos_offset..]);
+        Cow::Owned(image)
+    })
+}
+
+/// Decode MJPEG frame
+///
+/// The output pixel format may be one of [FourCc::Rgb24], [FourCc::Yuv420]
+/// or [FourCc::Yuyv]. The Y'CbCr outputs are in full range BT.601 encoding
+/// like JPEG itself, which is reflected by returned format.
+#[cfg(feature = "mjpeg")]
+pub fn decode(data: &[u8], fourcc: FourCc, output: &mut Vec<u8>) -> Result<PixFormat> {
+    use crate::PlaneLayout;
+
+    if !matches!(fourcc, FourCc::Rgb24 | FourCc::Yuv420 | FourCc::Yuyv) {
+        return Err(utils::invalid_input("Unsupported output pixel format"));
+    }
+
+    let image = with_dht(data)?;
+    let mut decoder = jpeg_decoder::Decoder::new(image.as_ref());
+    let pixels = decoder
+        .decode()
+        .map_err(|_| utils::invalid_data("Unable to decode JPEG"))?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| utils::invalid_data("Unable to decode JPEG"))?;
+
+    let src_fourcc = match info.pixel_format {
+        jpeg_decoder::PixelFormat::RGB24 => FourCc::Rgb24,
+        jpeg_decoder::PixelFormat::L8 => FourCc::Grey,
+        _ => return Err(utils::invalid_data("Unsupported JPEG color format")),
+    };
+
+    let pix_format = |fourcc| {
+        let mut pixfmt = PixFormat::from(fourcc);
+        PlaneLayout::compute(fourcc, info.width as _, info.height as _, 1)
+            .ok_or_else(|| utils::invalid_data("Unsupported image size"))?
+            .fill_pix_format(&mut pixfmt);
+        pixfmt.set_color_space(ColorSpace::Jpeg);
+        pixfmt.set_quantization(Quantization::FullRange);
+        pixfmt.set_ycbcr_enc(YcbcrEncoding::E601);
+        Ok::<_, crate::Error>(pixfmt)
+    };
+
+    let dst_fmt = pix_format(fourcc)?;
+
+    if src_fourcc == fourcc {
+        *output = pixels;
+    } else {
+        output.resize(dst_fmt.size_image() as _, 0);
+        crate::convert::convert(&pixels, &pix_format(src_fourcc)?, &mut *output, &dst_fmt)?;
+    }
+
+    Ok(dst_fmt)
+}
@@ -0,0 +1,1467 @@
+//! Mock devices for testing without hardware
+//!
+//! The [MockDevice] handles calls of opened device file in process instead of
+//! kernel. It simulates capabilities, formats with frame sizes and intervals,
+//! controls with menus, events, priority and buffer queues with test pattern
+//! frames.
+//!
+//! The device file is an unix socket which becomes readable when captured
+//! frames is available, so streams can wait buffers as usual. Events cannot be
+//! awaited because socket never signals priority data, dequeue it without
+//! waiting instead.
+
+use crate::{
+    calls::{self, Backend, Request},
+    monotonic_time,
+    types::*,
+    IsTimestamp, PlaneLayout, Result,
+};
+use core::num::NonZeroUsize;
+use nix::{
+    errno::Errno,
+    libc::{c_int, c_void},
+};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Read, Write},
+    os::unix::{
+        fs::FileExt,
+        io::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+        net::UnixStream,
+    },
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Maximum number of buffers
+const MAX_BUFFERS: usize = 32;
+
+/// Maximum number of pending events
+const MAX_EVENTS: usize = 64;
+
+/// Format of mock device
+#[derive(Debug, Clone)]
+pub struct MockFormat {
+    pixel_format: FourCc,
+    description: String,
+    sizes: Vec<(u32, u32)>,
+    intervals: Vec<Fract>,
+}
+
+impl MockFormat {
+    /// Create format without frame sizes and intervals
+    pub fn new(pixel_format: FourCc, description: impl Into<String>) -> Self {
+        Self {
+            pixel_format,
+            description: description.into(),
+            sizes: Vec::new(),
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Add discrete frame size
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.sizes.push((width, height));
+        self
+    }
+
+    /// Add discrete frame interval
+    ///
+    /// Intervals is the same for all frame sizes.
+    pub fn interval(mut self, numerator: u32, denominator: u32) -> Self {
+        self.intervals.push(Fract {
+            numerator,
+            denominator,
+        });
+        self
+    }
+
+    /// Pixel format
+    pub fn pixel_format(&self) -> FourCc {
+        self.pixel_format
+    }
+
+    fn nearest_size(&self, width: u32, height: u32) -> Option<(u32, u32)> {
+        self.sizes.iter().copied().min_by_key(|(w, h)| {
+            (*w as i64 - width as i64).abs() + (*h as i64 - height as i64).abs()
+        })
+    }
+
+    fn nearest_interval(&self, interval: Fract) -> Option<Fract> {
+        let duration = interval.to_duration()?;
+        self.intervals
+            .iter()
+            .copied()
+            .min_by_key(|other| other.to_duration().unwrap_or_default().abs_diff(duration))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum MockMenuItem {
+    Name(String),
+    Value(i64),
+}
+
+/// Control of mock device
+#[derive(Debug, Clone)]
+pub struct MockControl {
+    id: u32,
+    name: String,
+    type_: CtrlType,
+    min: i64,
+    max: i64,
+    step: u64,
+    default: i64,
+    flags: CtrlFlag,
+    items: Vec<MockMenuItem>,
+}
+
+impl MockControl {
+    fn new(id: impl Into<u32>, name: impl Into<String>, type_: CtrlType) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            type_,
+            min: 0,
+            max: 0,
+            step: 1,
+            default: 0,
+            flags: CtrlFlag::none(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Create integer control
+    pub fn integer(
+        id: impl Into<u32>,
+        name: impl Into<String>,
+        min: i32,
+        max: i32,
+        step: u32,
+        default: i32,
+    ) -> Self {
+        Self {
+            min: min as _,
+            max: max as _,
+            step: step as _,
+            default: default as _,
+            ..Self::new(id, name, CtrlType::Integer)
+        }
+    }
+
+    /// Create 64-bit integer control
+    pub fn integer64(
+        id: impl Into<u32>,
+        name: impl Into<String>,
+        min: i64,
+        max: i64,
+        step: u64,
+        default: i64,
+    ) -> Self {
+        Self {
+            min,
+            max,
+            step,
+            default,
+            ..Self::new(id, name, CtrlType::Integer64)
+        }
+    }
+
+    /// Create boolean control
+    pub fn boolean(id: impl Into<u32>, name: impl Into<String>, default: bool) -> Self {
+        Self {
+            max: 1,
+            default: default as _,
+            ..Self::new(id, name, CtrlType::Boolean)
+        }
+    }
+
+    /// Create menu control with named items
+    pub fn menu(
+        id: impl Into<u32>,
+        name: impl Into<String>,
+        items: impl IntoIterator<Item = impl Into<String>>,
+        default: u32,
+    ) -> Self {
+        let items: Vec<_> = items
+            .into_iter()
+            .map(|name| MockMenuItem::Name(name.into()))
+            .collect();
+        Self {
+            max: items.len().saturating_sub(1) as _,
+            default: default as _,
+            items,
+            ..Self::new(id, name, CtrlType::Menu)
+        }
+    }
+
+    /// Create menu control with integer items
+    pub fn integer_menu(
+        id: impl Into<u32>,
+        name: impl Into<String>,
+        items: impl IntoIterator<Item = i64>,
+        default: u32,
+    ) -> Self {
+        let items: Vec<_> = items.into_iter().map(MockMenuItem::Value).collect();
+        Self {
+            max: items.len().saturating_sub(1) as _,
+            default: default as _,
+            items,
+            ..Self::new(id, name, CtrlType::IntegerMenu)
+        }
+    }
+
+    /// Set control flags
+    pub fn flags(mut self, flags: CtrlFlag) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Control identifier
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn elem_size(&self) -> u32 {
+        if matches!(self.type_, CtrlType::Integer64) {
+            8
+        } else {
+            4
+        }
+    }
+
+    fn validate(&self, value: i64) -> nix::Result<i64> {
+        match self.type_ {
+            CtrlType::Menu | CtrlType::IntegerMenu => {
+                if value < self.min || value > self.max {
+                    Err(Errno::EINVAL)
+                } else {
+                    Ok(value)
+                }
+            }
+            _ => {
+                let value = value.clamp(self.min, self.max);
+                let step = self.step.max(1) as i64;
+                Ok(self.min + (value - self.min + step / 2) / step * step)
+            }
+        }
+        .map(|value| value.min(self.max))
+    }
+}
+
+/// Configuration of mock device
+#[derive(Debug, Clone)]
+pub struct MockConfig {
+    /// Driver name
+    pub driver: String,
+    /// Card name
+    pub card: String,
+    /// Bus name
+    pub bus_info: String,
+    /// Buffer type of device
+    ///
+    /// Only [BufferType::VideoCapture] and [BufferType::VideoOutput] is supported.
+    pub type_: BufferType,
+    /// Supported formats
+    pub formats: Vec<MockFormat>,
+    /// Available controls
+    pub controls: Vec<MockControl>,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            driver: "mock".into(),
+            card: "Mock Camera".into(),
+            bus_info: "platform:mock".into(),
+            type_: BufferType::VideoCapture,
+            formats: vec![
+                MockFormat::new(FourCc::Yuyv, "YUYV 4:2:2")
+                    .size(640, 480)
+                    .size(1280, 720)
+                    .interval(1, 30)
+                    .interval(1, 15),
+                MockFormat::new(FourCc::Rgb24, "24-bit RGB 8-8-8")
+                    .size(640, 480)
+                    .size(320, 240)
+                    .interval(1, 30),
+            ],
+            controls: vec![
+                MockControl::integer(CtrlId::Brightness, "Brightness", 0, 255, 1, 128),
+                MockControl::integer(CtrlId::Contrast, "Contrast", 0, 255, 1, 128),
+                MockControl::boolean(CtrlId::AutoWhiteBalance, "White Balance, Automatic", true),
+                MockControl::menu(
+                    CtrlId::PowerLineFrequency,
+                    "Power Line Frequency",
+                    ["Disabled", "50 Hz", "60 Hz", "Auto"],
+                    1,
+                ),
+            ],
+        }
+    }
+}
+
+impl MockConfig {
+    /// Default configuration of output device
+    pub fn output() -> Self {
+        Self {
+            card: "Mock Output".into(),
+            type_: BufferType::VideoOutput,
+            ..Default::default()
+        }
+    }
+}
+
+/// In-process device for testing
+///
+/// Calls of files opened by [MockDevice::open] is handled by mock device
+/// until it dropped.
+pub struct MockDevice {
+    backend: Arc<MockBackend>,
+    device: UnixStream,
+}
+
+impl MockDevice {
+    /// Create capture device with default configuration
+    pub fn new() -> Result<Self> {
+        Self::with_config(MockConfig::default())
+    }
+
+    /// Create device with configuration
+    pub fn with_config(config: MockConfig) -> Result<Self> {
+        if !matches!(
+            config.type_,
+            BufferType::VideoCapture | BufferType::VideoOutput
+        ) {
+            return Err(crate::utils::invalid_input("Unsupported buffer type"));
+        }
+        if config.formats.iter().all(|format| format.sizes.is_empty()) {
+            return Err(crate::utils::invalid_input("No formats with frame sizes"));
+        }
+
+        let (device, peer) = UnixStream::pair()?;
+        device.set_nonblocking(true)?;
+        peer.set_nonblocking(true)?;
+
+        let state = MockState::new(config, device.try_clone()?, peer);
+        let backend = Arc::new(MockBackend {
+            state: Mutex::new(state),
+        });
+
+        calls::register_backend(device.as_raw_fd(), backend.clone())?;
+
+        Ok(Self { backend, device })
+    }
+
+    /// Open device file
+    ///
+    /// The file is in non-blocking mode.
+    pub fn open(&self) -> Result<File> {
+        let device = self.device.try_clone()?;
+        Ok(File::from(OwnedFd::from(device)))
+    }
+
+    /// Get current control value
+    pub fn control(&self, id: impl Into<u32>) -> Option<i64> {
+        let state = self.backend.state();
+        let index = state.control_index(id.into())?;
+        Some(state.values[index])
+    }
+
+    /// Change control value by device side
+    ///
+    /// Subscribers get control event.
+    pub fn set_control(&self, id: impl Into<u32>, value: i64) -> Result<()> {
+        let mut state = self.backend.state();
+        let index = state
+            .control_index(id.into())
+            .ok_or(Errno::EINVAL)
+            .and_then(|index| {
+                state.config.controls[index]
+                    .validate(value)
+                    .map(|value| (index, value))
+            });
+        let (index, value) = index?;
+        state.set_value(index, value, true);
+        Ok(())
+    }
+
+    /// Signal source change
+    ///
+    /// Subscribers get source change event.
+    pub fn source_change(&self) {
+        let mut state = self.backend.state();
+        if state.is_subscribed(EventType::SourceChange, 0) {
+            let mut u = EventUnion { data: [0; 64] };
+            u.src_change.changes = EventSrcChangeFlag::Resolution;
+            state.push_event(EventType::SourceChange, 0, u);
+        }
+    }
+
+    /// Number of frames which is captured or output
+    pub fn frames(&self) -> u64 {
+        self.backend.state().frames
+    }
+
+    /// Data of last output frame
+    pub fn last_output(&self) -> Option<Vec<u8>> {
+        self.backend.state().last_output.clone()
+    }
+}
+
+impl Drop for MockDevice {
+    fn drop(&mut self) {
+        let _ = calls::unregister_backend(self.device.as_raw_fd());
+    }
+}
+
+/// Generate test pattern frame
+///
+/// Vertical color bars is shifted by frame sequence.
+/// Unsupported formats is filled by shifted gradient.
+pub fn test_pattern(format: &PixFormat, sequence: u32) -> Vec<u8> {
+    const BARS: [[u8; 3]; 8] = [
+        [255, 255, 255],
+        [255, 255, 0],
+        [0, 255, 255],
+        [0, 255, 0],
+        [255, 0, 255],
+        [255, 0, 0],
+        [0, 0, 255],
+        [0, 0, 0],
+    ];
+
+    let width = format.width as usize;
+    let height = format.height as usize;
+    let line = format.bytes_per_line as usize;
+    let mut data = vec![0u8; format.size_image as usize];
+
+    let color = |x: usize| BARS[(x + sequence as usize) % width * BARS.len() / width];
+    let luma = |[r, g, b]: [u8; 3]| {
+        (((66 * r as i32 + 129 * g as i32 + 25 * b as i32 + 128) >> 8) + 16) as u8
+    };
+    let chroma = |[r, g, b]: [u8; 3]| {
+        (
+            (((-38 * r as i32 - 74 * g as i32 + 112 * b as i32 + 128) >> 8) + 128) as u8,
+            (((112 * r as i32 - 94 * g as i32 - 18 * b as i32 + 128) >> 8) + 128) as u8,
+        )
+    };
+
+    let rows = data.chunks_mut(line.max(1)).take(height);
+
+    match format.pixel_format {
+        FourCc::Rgb24 | FourCc::Bgr24 if line >= width * 3 => {
+            let bgr = format.pixel_format == FourCc::Bgr24;
+            for row in rows {
+                for (x, pixel) in row.chunks_exact_mut(3).take(width).enumerate() {
+                    let mut rgb = color(x);
+                    if bgr {
+                        rgb.reverse();
+                    }
+                    pixel.copy_from_slice(&rgb);
+                }
+            }
+        }
+        FourCc::Grey if line >= width => {
+            for row in rows {
+                for (x, pixel) in row.iter_mut().take(width).enumerate() {
+                    *pixel = luma(color(x));
+                }
+            }
+        }
+        FourCc::Yuyv if line >= width * 2 => {
+            for row in rows {
+                for (x, pixels) in row.chunks_exact_mut(4).take(width / 2).enumerate() {
+                    let (c0, c1) = (color(x * 2), color(x * 2 + 1));
+                    let (u0, v0) = chroma(c0);
+                    let (u1, v1) = chroma(c1);
+                    pixels.copy_from_slice(&[
+                        luma(c0),
+                        ((u0 as u16 + u1 as u16) / 2) as u8,
+                        luma(c1),
+                        ((v0 as u16 + v1 as u16) / 2) as u8,
+                    ]);
+                }
+            }
+        }
+        _ => {
+            for (index, byte) in data.iter_mut().enumerate() {
+                *byte = (index as u32).wrapping_add(sequence) as u8;
+            }
+        }
+    }
+
+    data
+}
+
+struct MockBackend {
+    state: Mutex<MockState>,
+}
+
+impl MockBackend {
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl Backend for MockBackend {
+    unsafe fn ioctl(&self, _fd: RawFd, request: Request, data: *mut c_void) -> nix::Result<c_int> {
+        if calls::request_magic(request) != calls::MAGIC {
+            return Err(Errno::ENOTTY);
+        }
+
+        let mut state = self.state();
+
+        macro_rules! data {
+            () => {
+                &mut *(data as *mut _)
+            };
+        }
+
+        match calls::request_nr(request) {
+            calls::query_cap::NR => state.query_cap(data!()),
+            calls::enum_fmt::NR => state.enum_fmt(data!()),
+            calls::g_fmt::NR => state.g_fmt(data!()),
+            calls::s_fmt::NR => state.s_fmt(data!()),
+            calls::try_fmt::NR => state.try_fmt(data!()),
+            calls::enum_frame_sizes::NR => state.enum_frame_sizes(data!()),
+            calls::enum_frame_intervals::NR => state.enum_frame_intervals(data!()),
+            calls::g_parm::NR => state.g_parm(data!()),
+            calls::s_parm::NR => state.s_parm(data!()),
+            calls::query_ext_ctrl::NR => state.query_ext_ctrl(data!()),
+            calls::query_ctrl::NR => state.query_ctrl(data!()),
+            calls::query_menu::NR => state.query_menu(data!()),
+            calls::g_ctrl::NR => state.g_ctrl(data!()),
+            calls::s_ctrl::NR => state.s_ctrl(data!()),
+            calls::g_ext_ctrls::NR => state.ext_ctrls(data!(), CtrlsOp::Get),
+            calls::s_ext_ctrls::NR => state.ext_ctrls(data!(), CtrlsOp::Set),
+            calls::try_ext_ctrls::NR => state.ext_ctrls(data!(), CtrlsOp::Try),
+            calls::req_bufs::NR => state.req_bufs(data!()),
+            calls::create_bufs::NR => state.create_bufs(data!()),
+            calls::remove_bufs::NR => state.remove_bufs(data!()),
+            calls::query_buf::NR => state.query_buf(data!()),
+            calls::prepare_buf::NR => state.prepare_buf(data!()),
+            calls::q_buf::NR => state.q_buf(data!()),
+            calls::dq_buf::NR => state.dq_buf(data!()),
+            calls::stream_on::NR => state.stream_on(*(data as *const c_int)),
+            calls::stream_off::NR => state.stream_off(*(data as *const c_int)),
+            calls::subscribe_event::NR => state.subscribe_event(&*(data as *const _)),
+            calls::unsubscribe_event::NR => state.unsubscribe_event(&*(data as *const _)),
+            calls::dq_event::NR => state.dq_event(data!()),
+            calls::g_priority::NR => state.g_priority(data!()),
+            calls::s_priority::NR => state.s_priority(*(data as *const u32)),
+            _ => Err(Errno::ENOTTY),
+        }
+        .map(|_| 0)
+    }
+
+    fn mmap(&self, _fd: RawFd, length: usize, offset: i64) -> nix::Result<*mut c_void> {
+        use nix::sys::mman::{mmap, MapFlags, ProtFlags};
+
+        let state = self.state();
+        let memfd = state.memfd.as_ref().ok_or(Errno::EINVAL)?;
+
+        if offset < 0 || offset as u64 + length as u64 > state.memfd_len {
+            return Err(Errno::EINVAL);
+        }
+
+        unsafe {
+            mmap(
+                None,
+                NonZeroUsize::new(length).ok_or(Errno::EINVAL)?,
+                ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+                MapFlags::MAP_SHARED,
+                memfd.as_raw_fd(),
+                offset,
+            )
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum CtrlsOp {
+    Get,
+    Set,
+    Try,
+}
+
+#[derive(Default)]
+struct MockBuffer {
+    length: u32,
+    offset: u32,
+    userptr: usize,
+    bytes_used: u32,
+    sequence: u32,
+    timestamp: core::time::Duration,
+    queued: bool,
+    done: bool,
+    prepared: bool,
+}
+
+struct MockState {
+    config: MockConfig,
+    format: Format,
+    interval: Fract,
+    values: Vec<i64>,
+    memory: Option<Memory>,
+    buffers: Vec<Option<MockBuffer>>,
+    memfd: Option<File>,
+    memfd_len: u64,
+    pending: VecDeque<u32>,
+    done: VecDeque<u32>,
+    streaming: bool,
+    sequence: u32,
+    subscriptions: Vec<(u32, u32, EventSubFlag)>,
+    priority: Priority,
+    events: VecDeque<Event>,
+    event_sequence: u32,
+    frames: u64,
+    last_output: Option<Vec<u8>>,
+    device: UnixStream,
+    peer: UnixStream,
+}
+
+impl MockState {
+    fn new(config: MockConfig, device: UnixStream, peer: UnixStream) -> Self {
+        let mut format = Format::from(config.type_);
+        let default = config
+            .formats
+            .iter()
+            .find(|format| !format.sizes.is_empty())
+            .unwrap();
+        let (width, height) = default.sizes[0];
+        *format.try_mut().unwrap() = pix_format(default.pixel_format, width, height);
+        let interval = default.intervals.first().copied().unwrap_or_default();
+        let values = config
+            .controls
+            .iter()
+            .map(|control| control.default)
+            .collect();
+
+        Self {
+            config,
+            format,
+            interval,
+            values,
+            memory: None,
+            buffers: Vec::new(),
+            memfd: None,
+            memfd_len: 0,
+            pending: VecDeque::new(),
+            done: VecDeque::new(),
+            streaming: false,
+            sequence: 0,
+            subscriptions: Vec::new(),
+            priority: Priority::default(),
+            events: VecDeque::new(),
+            event_sequence: 0,
+            frames: 0,
+            last_output: None,
+            device,
+            peer,
+        }
+    }
+
+    fn g_priority(&self, priority: &mut u32) -> nix::Result<()> {
+        *priority = *self.priority;
+        Ok(())
+    }
+
+    fn s_priority(&mut self, priority: u32) -> nix::Result<()> {
+        // opened files refers to the same file, so them shares priority
+        self.priority = match Priority::try_from(priority) {
+            Ok(Priority::Unset) | Err(_) => return Err(Errno::EINVAL),
+            Ok(priority) => priority,
+        };
+        Ok(())
+    }
+
+    fn is_capture(&self) -> bool {
+        self.config.type_.is_capture()
+    }
+
+    fn check_type(&self, type_: BufferType) -> nix::Result<()> {
+        if type_ == self.config.type_ {
+            Ok(())
+        } else {
+            Err(Errno::EINVAL)
+        }
+    }
+
+    fn pix(&self) -> &PixFormat {
+        self.format.try_ref().unwrap()
+    }
+
+    fn find_format(&self, pixel_format: FourCc) -> nix::Result<&MockFormat> {
+        self.config
+            .formats
+            .iter()
+            .find(|format| format.pixel_format == pixel_format)
+            .ok_or(Errno::EINVAL)
+    }
+
+    fn current_format(&self) -> &MockFormat {
+        self.find_format(self.pix().pixel_format).unwrap()
+    }
+
+    fn query_cap(&self, cap: &mut Capability) -> nix::Result<()> {
+        let device_capabilities = if self.is_capture() {
+            CapabilityFlag::VideoCapture
+        } else {
+            CapabilityFlag::VideoOutput
+        } | CapabilityFlag::Streaming
+            | CapabilityFlag::ExtPixFormat;
+
+        *cap = unsafe { core::mem::zeroed() };
+        copy_str(&mut cap.driver, &self.config.driver);
+        copy_str(&mut cap.card, &self.config.card);
+        copy_str(&mut cap.bus_info, &self.config.bus_info);
+        cap.device_capabilities = device_capabilities;
+        cap.capabilities = device_capabilities | CapabilityFlag::DeviceCaps;
+        Ok(())
+    }
+
+    fn enum_fmt(&self, desc: &mut FmtDesc) -> nix::Result<()> {
+        self.check_type(desc.type_)?;
+        let format = self
+            .config
+            .formats
+            .get(desc.index as usize)
+            .ok_or(Errno::EINVAL)?;
+
+        let is_compressed = format
+            .pixel_format
+            .info()
+            .map(|info| info.is_compressed())
+            .unwrap_or(true);
+
+        desc.flags = if is_compressed {
+            FmtFlag::Compressed
+        } else {
+            FmtFlag::none()
+        };
+        copy_str(&mut desc.description, &format.description);
+        desc.pixel_format = format.pixel_format;
+        desc.mbus_code = 0;
+        Ok(())
+    }
+
+    fn g_fmt(&self, fmt: &mut Format) -> nix::Result<()> {
+        self.check_type(fmt.type_)?;
+        *fmt = self.format;
+        Ok(())
+    }
+
+    fn try_fmt(&self, fmt: &mut Format) -> nix::Result<()> {
+        self.check_type(fmt.type_)?;
+        let pix = fmt.try_mut::<PixFormat>().ok_or(Errno::EINVAL)?;
+        let format = self
+            .find_format(pix.pixel_format)
+            .ok()
+            .filter(|format| !format.sizes.is_empty())
+            .or_else(|| {
+                self.config
+                    .formats
+                    .iter()
+                    .find(|format| !format.sizes.is_empty())
+            })
+            .ok_or(Errno::EINVAL)?;
+        let (width, height) = format
+            .nearest_size(pix.width, pix.height)
+            .ok_or(Errno::EINVAL)?;
+        *pix = pix_format(format.pixel_format, width, height);
+        Ok(())
+    }
+
+    fn s_fmt(&mut self, fmt: &mut Format) -> nix::Result<()> {
+        self.try_fmt(fmt)?;
+        if self.buffers.iter().any(Option::is_some) {
+            return Err(Errno::EBUSY);
+        }
+        self.format = *fmt;
+        let format = self.current_format();
+        if !format
+            .intervals
+            .iter()
+            .any(|interval| same_fract(interval, &self.interval))
+        {
+            self.interval = format.intervals.first().copied().unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    fn enum_frame_sizes(&self, size: &mut FrmSizeEnum) -> nix::Result<()> {
+        let format = self.find_format(size.pixel_format)?;
+        let (width, height) = *format.sizes.get(size.index as usize).ok_or(Errno::EINVAL)?;
+        size.type_ = FrmSizeType::Discrete as _;
+        size.union_.discrete = Area { width, height };
+        Ok(())
+    }
+
+    fn enum_frame_intervals(&self, ival: &mut FrmIvalEnum) -> nix::Result<()> {
+        let format = self.find_format(ival.pixel_format)?;
+        if !format.sizes.contains(&(ival.width, ival.height)) {
+            return Err(Errno::EINVAL);
+        }
+        let interval = *format
+            .intervals
+            .get(ival.index as usize)
+            .ok_or(Errno::EINVAL)?;
+        ival.type_ = FrmIvalType::Discrete as _;
+        ival.union_.discrete = interval;
+        Ok(())
+    }
+
+    fn g_parm(&self, parm: &mut StreamParm) -> nix::Result<()> {
+        self.check_type(parm.type_)?;
+        if self.is_capture() {
+            parm.parm.capture = CaptureParm {
+                capability: IoCapabilityFlag::TemperFrame,
+                time_per_frame: self.interval,
+                ..Default::default()
+            };
+        } else {
+            parm.parm.output = OutputParm {
+                capability: IoCapabilityFlag::TemperFrame,
+                time_per_frame: self.interval,
+                ..Default::default()
+            };
+        }
+        Ok(())
+    }
+
+    fn s_parm(&mut self, parm: &mut StreamParm) -> nix::Result<()> {
+        self.check_type(parm.type_)?;
+        let interval = unsafe {
+            if self.is_capture() {
+                parm.parm.capture.time_per_frame
+            } else {
+                parm.parm.output.time_per_frame
+            }
+        };
+        if let Some(interval) = self.current_format().nearest_interval(interval) {
+            self.interval = interval;
+        }
+        self.g_parm(parm)
+    }
+
+    fn control_index(&self, id: u32) -> Option<usize> {
+        self.config
+            .controls
+            .iter()
+            .position(|control| control.id == id)
+    }
+
+    fn find_control(&self, id: u32) -> nix::Result<usize> {
+        let flags = CtrlEnumFlag::NextCtrl | CtrlEnumFlag::NextCompound;
+        let base = id & !flags.bits();
+
+        if id & CtrlEnumFlag::NextCtrl.bits() != 0 {
+            // compound controls is not simulated
+            self.config
+                .controls
+                .iter()
+                .enumerate()
+                .filter(|(_, control)| control.id > base)
+                .min_by_key(|(_, control)| control.id)
+                .map(|(index, _)| index)
+                .ok_or(Errno::EINVAL)
+        } else if id & flags.bits() != 0 {
+            Err(Errno::EINVAL)
+        } else {
+            self.control_index(id).ok_or(Errno::EINVAL)
+        }
+    }
+
+    fn query_ext_ctrl(&self, ctrl: &mut QueryExtCtrl) -> nix::Result<()> {
+        let control = &self.config.controls[self.find_control(ctrl.id)?];
+
+        *ctrl = unsafe { core::mem::zeroed() };
+        ctrl.id = control.id;
+        ctrl.type_ = control.type_ as _;
+        copy_str(&mut ctrl.name, &control.name);
+        ctrl.min = control.min;
+        ctrl.max = control.max;
+        ctrl.step = control.step;
+        ctrl.default = control.default;
+        ctrl.flags = control.flags;
+        ctrl.elem_size = control.elem_size();
+        ctrl.elems = 1;
+        Ok(())
+    }
+
+    fn query_ctrl(&self, ctrl: &mut QueryCtrl) -> nix::Result<()> {
+        let control = &self.config.controls[self.find_control(ctrl.id)?];
+
+        *ctrl = unsafe { core::mem::zeroed() };
+        ctrl.id = control.id;
+        ctrl.type_ = control.type_ as _;
+        copy_str(&mut ctrl.name, &control.name);
+        ctrl.min = control.min as _;
+        ctrl.max = control.max as _;
+        ctrl.step = control.step as _;
+        ctrl.default = control.default as _;
+        ctrl.flags = control.flags;
+        Ok(())
+    }
+
+    fn query_menu(&self, menu: &mut QueryMenu) -> nix::Result<()> {
+        let (id, index) = (menu.id, menu.index);
+        let control = &self.config.controls[self.control_index(id).ok_or(Errno::EINVAL)?];
+        let item = control.items.get(index as usize).ok_or(Errno::EINVAL)?;
+
+        let union_ = match item {
+            MockMenuItem::Name(name) => {
+                let mut union_ = QueryMenuUnion { name: [0; 32] };
+                copy_str(unsafe { &mut union_.name }, name);
+                union_
+            }
+            MockMenuItem::Value(value) => QueryMenuUnion { value: *value },
+        };
+
+        *menu = QueryMenu {
+            id,
+            index,
+            union_,
+            reserved: 0,
+        };
+        Ok(())
+    }
+
+    fn g_ctrl(&self, ctrl: &mut Control) -> nix::Result<()> {
+        let index = self.control_index(ctrl.id).ok_or(Errno::EINVAL)?;
+        ctrl.value = self.values[index] as _;
+        Ok(())
+    }
+
+    fn s_ctrl(&mut self, ctrl: &mut Control) -> nix::Result<()> {
+        let index = self.control_index(ctrl.id).ok_or(Errno::EINVAL)?;
+        let value = self.config.controls[index].validate(ctrl.value as _)?;
+        self.set_value(index, value, false);
+        ctrl.value = value as _;
+        Ok(())
+    }
+
+    fn ext_ctrls(&mut self, ctrls: &mut ExtControls, op: CtrlsOp) -> nix::Result<()> {
+        if ctrls.count == 0 {
+            return Ok(());
+        }
+
+        let datas = unsafe { core::slice::from_raw_parts_mut(ctrls.controls, ctrls.count as _) };
+
+        let mut changes = Vec::with_capacity(datas.len());
+
+        for (position, data) in datas.iter().enumerate() {
+            let result = self
+                .control_index(data.id)
+                .ok_or(Errno::EINVAL)
+                .and_then(|index| {
+                    let control = &self.config.controls[index];
+                    let value = if matches!(control.type_, CtrlType::Integer64) {
+                        unsafe { data.union_.value64 }
+                    } else {
+                        unsafe { data.union_.value as _ }
+                    };
+                    if matches!(op, CtrlsOp::Get) {
+                        Ok((index, self.values[index]))
+                    } else {
+                        control.validate(value).map(|value| (index, value))
+                    }
+                });
+            match result {
+                Ok(change) => changes.push(change),
+                Err(error) => {
+                    ctrls.error_idx = if matches!(op, CtrlsOp::Set) {
+                        ctrls.count
+                    } else {
+                        position as _
+                    };
+                    return Err(error);
+                }
+            }
+        }
+
+        for (data, (index, value)) in datas.iter_mut().zip(changes) {
+            if matches!(op, CtrlsOp::Set) {
+                self.set_value(index, value, false);
+            }
+            if matches!(self.config.controls[index].type_, CtrlType::Integer64) {
+                data.union_.value64 = value;
+            } else {
+                data.union_.value = value as _;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_value(&mut self, index: usize, value: i64, external: bool) {
+        if self.values[index] == value {
+            return;
+        }
+        self.values[index] = value;
+
+        let id = self.config.controls[index].id;
+        let feedback = self.subscriptions.iter().any(|(type_, other, flags)| {
+            *type_ == EventType::Ctrl as u32
+                && *other == id
+                && (external || flags.contains(EventSubFlag::AllowFeedback))
+        });
+        if feedback {
+            self.push_ctrl_event(index, EventCtrlChangeFlag::Value);
+        }
+    }
+
+    fn push_ctrl_event(&mut self, index: usize, changes: EventCtrlChangeFlag) {
+        let control = &self.config.controls[index];
+        let mut u = EventUnion { data: [0; 64] };
+        u.ctrl = EventCtrl {
+            changes,
+            type_: control.type_ as _,
+            union_: if matches!(control.type_, CtrlType::Integer64) {
+                EventCtrlUnion {
+                    value64: self.values[index],
+                }
+            } else {
+                EventCtrlUnion {
+                    value: self.values[index] as _,
+                }
+            },
+            flags: control.flags,
+            minimum: control.min as _,
+            maximum: control.max as _,
+            step: control.step as _,
+            default_value: control.default as _,
+        };
+        self.push_event(EventType::Ctrl, control.id, u);
+    }
+
+    fn is_subscribed(&self, type_: EventType, id: u32) -> bool {
+        self.subscriptions
+            .iter()
+            .any(|(other_type, other_id, _)| *other_type == type_ as u32 && *other_id == id)
+    }
+
+    fn push_event(&mut self, type_: EventType, id: u32, u: EventUnion) {
+        let mut event: Event = unsafe { core::mem::zeroed() };
+        event.type_ = type_ as _;
+        event.id = id;
+        event.u = u;
+        event.sequence = self.event_sequence;
+        event.timestamp = monotonic_time().into_time_spec();
+        self.event_sequence = self.event_sequence.wrapping_add(1);
+
+        if self.events.len() >= MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    fn subscribe_event(&mut self, sub: &EventSubscription) -> nix::Result<()> {
+        let type_ = EventType::try_from(sub.type_).map_err(|_| Errno::EINVAL)?;
+        let flags = EventSubFlag::from(sub.flags);
+
+        match type_ {
+            EventType::Ctrl => {
+                let index = self.control_index(sub.id).ok_or(Errno::EINVAL)?;
+                if !self.is_subscribed(type_, sub.id) {
+                    self.subscriptions.push((sub.type_, sub.id, flags));
+                    if flags.contains(EventSubFlag::SendInitial) {
+                        self.push_ctrl_event(
+                            index,
+                            EventCtrlChangeFlag::Value | EventCtrlChangeFlag::Flags,
+                        );
+                    }
+                }
+            }
+            EventType::FrameSync | EventType::SourceChange | EventType::Eos => {
+                if !self.is_subscribed(type_, sub.id) {
+                    self.subscriptions.push((sub.type_, sub.id, flags));
+                }
+            }
+            _ => return Err(Errno::EINVAL),
+        }
+
+        Ok(())
+    }
+
+    fn unsubscribe_event(&mut self, sub: &EventSubscription) -> nix::Result<()> {
+        let all = sub.type_ == EventType::All as u32;
+        self.subscriptions
+            .retain(|(type_, id, _)| !(all || (*type_ == sub.type_ && *id == sub.id)));
+        self.events
+            .retain(|event| !(all || (event.type_ == sub.type_ && event.id == sub.id)));
+        Ok(())
+    }
+
+    fn dq_event(&mut self, event: &mut Event) -> nix::Result<()> {
+        *event = self.events.pop_front().ok_or(Errno::ENOENT)?;
+        event.pending = self.events.len() as _;
+        Ok(())
+    }
+
+    fn buffer_capabilities() -> BufferCapabilityFlag {
+        BufferCapabilityFlag::SupportsMmap | BufferCapabilityFlag::SupportsUserPtr
+    }
+
+    fn check_memory(&self, memory: Memory) -> nix::Result<()> {
+        if !matches!(memory, Memory::Mmap | Memory::UserPtr) {
+            return Err(Errno::EINVAL);
+        }
+        if self.memory.is_some_and(|other| other != memory) {
+            return Err(Errno::EBUSY);
+        }
+        Ok(())
+    }
+
+    fn free_buffers(&mut self) {
+        self.buffers.clear();
+        self.pending.clear();
+        self.done.clear();
+        self.memory = None;
+        self.memfd = None;
+        self.memfd_len = 0;
+        self.drain_ready();
+    }
+
+    fn alloc_buffers(&mut self, memory: Memory, count: usize, length: u32) -> nix::Result<u32> {
+        let index = self.buffers.len() as u32;
+        let count = count.min(MAX_BUFFERS - self.buffers.len());
+
+        if matches!(memory, Memory::Mmap) && count > 0 {
+            let stride = page_align(length as u64);
+            let memfd_len = self.memfd_len + stride * count as u64;
+            if memfd_len > u32::MAX as u64 {
+                return Err(Errno::ENOMEM);
+            }
+            let memfd = match self.memfd.take() {
+                Some(memfd) => memfd,
+                None => create_memfd()?,
+            };
+            memfd.set_len(memfd_len).map_err(|_| Errno::ENOMEM)?;
+            for position in 0..count {
+                self.buffers.push(Some(MockBuffer {
+                    length,
+                    offset: (self.memfd_len + stride * position as u64) as _,
+                    ..Default::default()
+                }));
+            }
+            self.memfd = Some(memfd);
+            self.memfd_len = memfd_len;
+        } else {
+            for _ in 0..count {
+                self.buffers.push(Some(MockBuffer {
+                    length,
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if count > 0 {
+            self.memory = Some(memory);
+        }
+
+        Ok(index)
+    }
+
+    fn req_bufs(&mut self, req: &mut RequestBuffers) -> nix::Result<()> {
+        self.check_type(req.type_)?;
+        if self.streaming {
+            return Err(Errno::EBUSY);
+        }
+        if !matches!(req.memory, Memory::Mmap | Memory::UserPtr) {
+            return Err(Errno::EINVAL);
+        }
+
+        req.capabilities = Self::buffer_capabilities();
+
+        self.free_buffers();
+
+        let length = self.pix().size_image;
+        self.alloc_buffers(req.memory, req.count as _, length)?;
+        req.count = self.buffers.len() as _;
+        Ok(())
+    }
+
+    fn create_bufs(&mut self, create: &mut CreateBuffers) -> nix::Result<()> {
+        let memory = Memory::try_from(create.memory).map_err(|_| Errno::EINVAL)?;
+        self.check_type(create.format.type_)?;
+        self.check_memory(memory)?;
+
+        create.capabilities = Self::buffer_capabilities();
+
+        let length = create
+            .format
+            .try_ref::<PixFormat>()
+            .map(|pix| pix.size_image)
+            .unwrap_or_default()
+            .max(self.pix().size_image);
+
+        create.index = self.alloc_buffers(memory, create.count as _, length)?;
+        create.count = self.buffers.len() as u32 - create.index;
+        Ok(())
+    }
+
+    fn remove_bufs(&mut self, remove: &mut RemoveBuffers) -> nix::Result<()> {
+        self.check_type(remove.type_)?;
+        let range = remove.index as usize..(remove.index + remove.count) as usize;
+        let buffers = self.buffers.get(range.clone()).ok_or(Errno::EINVAL)?;
+        if buffers.iter().any(Option::is_none) {
+            return Err(Errno::EINVAL);
+        }
+        if buffers.iter().flatten().any(|buffer| buffer.queued) {
+            return Err(Errno::EBUSY);
+        }
+        for buffer in &mut self.buffers[range] {
+            *buffer = None;
+        }
+        Ok(())
+    }
+
+    fn buffer_mut(&mut self, buffer: &Buffer) -> nix::Result<&mut MockBuffer> {
+        self.check_type(buffer.type_)?;
+        if self.memory != Some(buffer.memory) {
+            return Err(Errno::EINVAL);
+        }
+        self.buffers
+            .get_mut(buffer.index as usize)
+            .and_then(Option::as_mut)
+            .ok_or(Errno::EINVAL)
+    }
+
+    fn fill_buffer(&self, buffer: &mut Buffer) {
+        let state = self.buffers[buffer.index as usize].as_ref().unwrap();
+        let memory = self.memory.unwrap();
+
+        let mut flags = BufferFlag::from(Timestamp::Monotonic as u32);
+        if state.queued {
+            flags |= BufferFlag::Queued;
+        }
+        if state.done {
+            flags |= BufferFlag::Done;
+        }
+        if state.prepared {
+            flags |= BufferFlag::Prepared;
+        }
+
+        buffer.type_ = self.config.type_;
+        buffer.memory = memory;
+        buffer.bytes_used = state.bytes_used;
+        buffer.flags = flags;
+        buffer.field = Field::None as _;
+        buffer.timestamp = state.timestamp.into_time_val();
+        buffer.sequence = state.sequence;
+        buffer.length = state.length;
+        if matches!(memory, Memory::Mmap) {
+            buffer.m.offset = state.offset;
+        } else {
+            buffer.m.userptr = state.userptr as _;
+        }
+    }
+
+    fn query_buf(&mut self, buffer: &mut Buffer) -> nix::Result<()> {
+        self.buffer_mut(buffer)?;
+        self.fill_buffer(buffer);
+        Ok(())
+    }
+
+    fn prepare(&mut self, buffer: &Buffer) -> nix::Result<()> {
+        let is_capture = self.is_capture();
+        let state = self.buffer_mut(buffer)?;
+        if state.queued {
+            return Err(Errno::EINVAL);
+        }
+        if matches!(buffer.memory, Memory::UserPtr) {
+            let userptr = unsafe { buffer.m.userptr } as usize;
+            if userptr == 0 || buffer.length < state.length {
+                return Err(Errno::EINVAL);
+            }
+            state.userptr = userptr;
+        }
+        if !is_capture {
+            state.bytes_used = if buffer.bytes_used == 0 {
+                state.length
+            } else {
+                buffer.bytes_used.min(state.length)
+            };
+        }
+        state.prepared = true;
+        Ok(())
+    }
+
+    fn prepare_buf(&mut self, buffer: &mut Buffer) -> nix::Result<()> {
+        self.prepare(buffer)?;
+        self.fill_buffer(buffer);
+        Ok(())
+    }
+
+    fn q_buf(&mut self, buffer: &mut Buffer) -> nix::Result<()> {
+        self.prepare(buffer)?;
+        let state = self.buffer_mut(buffer)?;
+        state.queued = true;
+        state.done = false;
+        self.pending.push_back(buffer.index);
+        if self.streaming {
+            self.process();
+        }
+        self.fill_buffer(buffer);
+        Ok(())
+    }
+
+    fn dq_buf(&mut self, buffer: &mut Buffer) -> nix::Result<()> {
+        self.check_type(buffer.type_)?;
+        if self.memory != Some(buffer.memory) {
+            return Err(Errno::EINVAL);
+        }
+        let index = self.done.pop_front().ok_or(Errno::EAGAIN)?;
+        if self.is_capture() {
+            let _ = (&self.device).read(&mut [0u8]);
+        }
+
+        let state = self.buffers[index as usize].as_mut().unwrap();
+        state.queued = false;
+        state.done = false;
+        state.prepared = false;
+
+        buffer.index = index;
+        self.fill_buffer(buffer);
+        Ok(())
+    }
+
+    fn stream_on(&mut self, type_: c_int) -> nix::Result<()> {
+        if type_ != self.config.type_ as c_int {
+            return Err(Errno::EINVAL);
+        }
+        if self.buffers.iter().all(Option::is_none) {
+            return Err(Errno::EINVAL);
+        }
+        if !self.streaming {
+            self.streaming = true;
+            self.sequence = 0;
+            self.process();
+        }
+        Ok(())
+    }
+
+    fn stream_off(&mut self, type_: c_int) -> nix::Result<()> {
+        if type_ != self.config.type_ as c_int {
+            return Err(Errno::EINVAL);
+        }
+        self.streaming = false;
+        self.pending.clear();
+        self.done.clear();
+        for buffer in self.buffers.iter_mut().flatten() {
+            buffer.queued = false;
+            buffer.done = false;
+            buffer.prepared = false;
+        }
+        self.drain_ready();
+        Ok(())
+    }
+
+    fn drain_ready(&self) {
+        let mut data = [0u8; MAX_BUFFERS];
+        while matches!((&self.device).read(&mut data), Ok(len) if len > 0) {}
+    }
+
+    /// Complete queued buffers
+    fn process(&mut self) {
+        let pix = *self.pix();
+        let is_capture = self.is_capture();
+
+        while let Some(index) = self.pending.pop_front() {
+            let sequence = self.sequence;
+            self.sequence = self.sequence.wrapping_add(1);
+
+            let memfd = self.memfd.as_ref();
+            let state = self.buffers[index as usize].as_mut().unwrap();
+
+            if is_capture {
+                let mut data = test_pattern(&pix, sequence);
+                data.truncate(state.length as _);
+                write_buffer(memfd, state, &data);
+                state.bytes_used = data.len() as _;
+            } else {
+                self.last_output = Some(read_buffer(memfd, state));
+            }
+
+            state.sequence = sequence;
+            state.timestamp = monotonic_time();
+            state.done = true;
+            self.done.push_back(index);
+            self.frames += 1;
+
+            if is_capture {
+                let _ = (&self.peer).write(&[0]);
+            }
+
+            if self.is_subscribed(EventType::FrameSync, 0) {
+                let mut u = EventUnion { data: [0; 64] };
+                u.frame_sync.frame_sequence = sequence;
+                self.push_event(EventType::FrameSync, 0, u);
+            }
+        }
+    }
+}
+
+fn write_buffer(memfd: Option<&File>, buffer: &MockBuffer, data: &[u8]) {
+    if let Some(memfd) = memfd {
+        let _ = memfd.write_all_at(data, buffer.offset as _);
+    } else if buffer.userptr != 0 {
+        // user memory stays valid while buffer is queued
+        unsafe {
+            core::ptr::copy_nonoverlapping(data.as_ptr(), buffer.userptr as *mut u8, data.len())
+        };
+    }
+}
+
+fn read_buffer(memfd: Option<&File>, buffer: &MockBuffer) -> Vec<u8> {
+    let mut data = vec![0u8; buffer.bytes_used as _];
+    if let Some(memfd) = memfd {
+        let _ = memfd.read_exact_at(&mut data, buffer.offset as _);
+    } else if buffer.userptr != 0 {
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                buffer.userptr as *const u8,
+                data.as_mut_ptr(),
+                data.len(),
+            )
+        };
+    }
+    data
+}
+
+fn pix_format(pixel_format: FourCc, width: u32, height: u32) -> PixFormat {
+    let mut pix = PixFormat::from(pixel_format);
+    if let Some(layout) = PlaneLayout::compute(pixel_format, width, height, 1) {
+        layout.fill_pix_format(&mut pix);
+    } else {
+        // compressed frames is not larger than packed 4:2:2
+        pix.width = width;
+        pix.height = height;
+        pix.size_image = width * height * 2;
+    }
+    pix
+}
+
+fn same_fract(a: &Fract, b: &Fract) -> bool {
+    a.numerator as u64 * b.denominator as u64 == b.numerator as u64 * a.denominator as u64
+}
+
+fn copy_str(dst: &mut [u8], src: &str) {
+    let len = src.len().min(dst.len() - 1);
+    dst.fill(0);
+    dst[..len].copy_from_slice(&src.as_bytes()[..len]);
+}
+
+fn page_align(length: u64) -> u64 {
+    let page = unsafe { nix::libc::sysconf(nix::libc::_SC_PAGESIZE) }.max(4096) as u64;
+    length.div_ceil(page) * page
+}
+
+fn create_memfd() -> nix::Result<File> {
+    use nix::sys::memfd::{memfd_create, MemFdCreateFlag};
+
+    let fd = memfd_create(c"linux-video-mock", MemFdCreateFlag::MFD_CLOEXEC)?;
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
@@ -1,7 +1,15 @@
+mod discovery;
+mod framebuf;
 mod helpers;
 mod internals;
+mod matcher;
 mod traits;
+mod watcher;
 
+pub use discovery::*;
+pub use framebuf::*;
 pub use helpers::*;
 pub use internals::*;
+pub use matcher::*;
 pub use traits::*;
+pub use watcher::*;
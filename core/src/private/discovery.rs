@@ -0,0 +1,212 @@
+use crate::{
+    check_dev_name, open, Capability, DeviceIdentity, DeviceInfo, Internal, PhysicalDevice, Result,
+    UsbInfo,
+};
+use std::{
+    fs, io,
+    os::unix::io::AsRawFd,
+    path::{Path, PathBuf},
+};
+
+/// Discover video devices grouped by physical devices
+///
+/// Nodes which cannot be opened have no capabilities.
+pub fn discover_devices() -> Result<Vec<PhysicalDevice>> {
+    let mut infos = read_device_infos("/sys", "/dev")?;
+
+    for info in &mut infos {
+        info.capability = open(&info.path, true)
+            .and_then(|file| Internal::<Capability>::query(file.as_raw_fd()))
+            .map(Internal::into_inner)
+            .ok();
+    }
+
+    Ok(group_device_infos(infos))
+}
+
+/// Get identity of device node
+///
+/// Node path is used as identity when device is not found in sysfs.
+pub fn identify_device(path: impl AsRef<Path>) -> Result<DeviceIdentity> {
+    let path = fs::canonicalize(path)?;
+
+    Ok(discover_devices()?
+        .iter()
+        .flat_map(PhysicalDevice::nodes)
+        .find(|node| fs::canonicalize(node.path()).ok().as_ref() == Some(&path))
+        .map(DeviceIdentity::new)
+        .unwrap_or(DeviceIdentity::Path(path)))
+}
+
+/// Find device node by identity
+pub fn find_device(identity: &DeviceIdentity) -> Result<Option<PathBuf>> {
+    Ok(discover_devices()?
+        .iter()
+        .flat_map(PhysicalDevice::nodes)
+        .find(|node| identity.matches(node))
+        .map(|node| node.path().into()))
+}
+
+/// Read video nodes information from sysfs
+///
+/// Capabilities is not queried.
+pub fn read_device_infos(
+    sys_root: impl AsRef<Path>,
+    dev_root: impl AsRef<Path>,
+) -> Result<Vec<DeviceInfo>> {
+    let class_dir = sys_root.as_ref().join("class/video4linux");
+    let dev_root = dev_root.as_ref();
+
+    let by_id = read_links(dev_root.join("v4l/by-id"))?;
+    let by_path = read_links(dev_root.join("v4l/by-path"))?;
+
+    let mut infos = Vec::new();
+
+    let entries = match fs::read_dir(&class_dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(infos),
+        Err(error) => return Err(error),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        if check_dev_name(file_name).is_none() {
+            continue;
+        }
+
+        // node may be removed already
+        let Ok(sys_path) = fs::canonicalize(entry.path()) else {
+            continue;
+        };
+        let path = dev_root.join(file_name);
+
+        let name = read_attr(&sys_path, "name").unwrap_or_default();
+        let index = read_attr(&sys_path, "index")
+            .and_then(|index| index.parse().ok())
+            .unwrap_or_default();
+
+        let device = fs::canonicalize(sys_path.join("device")).ok();
+
+        let driver = device
+            .as_ref()
+            .and_then(|device| fs::read_link(device.join("driver")).ok())
+            .and_then(|driver| Some(driver.file_name()?.to_str()?.into()));
+
+        let usb_device = device.as_ref().and_then(|device| {
+            device
+                .ancestors()
+                .find(|path| path.join("idVendor").is_file())
+        });
+
+        let usb = usb_device.and_then(read_usb_info);
+
+        let parent = usb_device
+            .map(Path::to_path_buf)
+            .or(device)
+            .unwrap_or_else(|| sys_path.clone());
+
+        let real_path = fs::canonicalize(&path).ok();
+        let links = |links: &[(PathBuf, PathBuf)]| {
+            links
+                .iter()
+                .filter(|(_, target)| Some(target) == real_path.as_ref())
+                .map(|(link, _)| link.clone())
+                .collect()
+        };
+
+        infos.push(DeviceInfo {
+            by_id: links(&by_id),
+            by_path: links(&by_path),
+            path,
+            sys_path,
+            parent,
+            name,
+            index,
+            driver,
+            usb,
+            capability: None,
+        });
+    }
+
+    infos.sort_by_key(|info| node_key(&info.path));
+
+    Ok(infos)
+}
+
+/// Group video nodes by physical devices
+pub fn group_device_infos(infos: Vec<DeviceInfo>) -> Vec<PhysicalDevice> {
+    let mut devices: Vec<PhysicalDevice> = Vec::new();
+
+    for info in infos {
+        if let Some(device) = devices
+            .iter_mut()
+            .find(|device| device.sys_path == info.parent)
+        {
+            device.nodes.push(info);
+        } else {
+            devices.push(PhysicalDevice {
+                sys_path: info.parent.clone(),
+                nodes: vec![info],
+            });
+        }
+    }
+
+    for device in &mut devices {
+        device.nodes.sort_by_key(|node| node.index);
+    }
+
+    devices
+}
+
+fn read_attr(dir: &Path, name: &str) -> Option<String> {
+    fs::read_to_string(dir.join(name))
+        .ok()
+        .map(|value| value.trim_end().into())
+}
+
+fn read_usb_info(dir: &Path) -> Option<UsbInfo> {
+    let read_id = |name| u16::from_str_radix(&read_attr(dir, name)?, 16).ok();
+
+    Some(UsbInfo {
+        vendor_id: read_id("idVendor")?,
+        product_id: read_id("idProduct")?,
+        serial: read_attr(dir, "serial"),
+        manufacturer: read_attr(dir, "manufacturer"),
+        product: read_attr(dir, "product"),
+    })
+}
+
+fn read_links(dir: PathBuf) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut links = Vec::new();
+
+    for entry in entries {
+        let link = entry?.path();
+        if let Ok(target) = fs::canonicalize(&link) {
+            links.push((link, target));
+        }
+    }
+
+    links.sort();
+
+    Ok(links)
+}
+
+fn node_key(path: &Path) -> (String, u32) {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = name[prefix.len()..].parse().unwrap_or_default();
+    (prefix.into(), number)
+}
@@ -0,0 +1,94 @@
+use crate::{utils, Result};
+
+/// Single frame buffer for read/write I/O
+///
+/// Keeps frame boundaries when user reads or writes data by parts.
+pub struct FrameBuf {
+    data: Vec<u8>,
+    pos: usize,
+    len: usize,
+    complete: bool,
+}
+
+impl FrameBuf {
+    /// Create buffer for frames of specified size
+    pub fn new(size: usize) -> Self {
+        Self {
+            data: vec![0; size],
+            pos: 0,
+            len: 0,
+            complete: false,
+        }
+    }
+
+    /// Frame size in bytes
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Check that no pending data in buffer
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.len
+    }
+
+    /// Check that buffer contains entire frame
+    pub fn is_full(&self) -> bool {
+        self.complete || self.len >= self.data.len()
+    }
+
+    /// Discard pending data
+    pub fn clear(&mut self) {
+        self.pos = 0;
+        self.len = 0;
+        self.complete = false;
+    }
+
+    /// Read frame into buffer using reader
+    pub fn fill(&mut self, reader: impl FnOnce(&mut [u8]) -> Result<usize>) -> Result<()> {
+        self.clear();
+        self.len = reader(&mut self.data)?;
+        Ok(())
+    }
+
+    /// Copy pending data of frame into output
+    pub fn take(&mut self, out: &mut [u8]) -> usize {
+        let len = out.len().min(self.len - self.pos);
+        out[..len].copy_from_slice(&self.data[self.pos..self.pos + len]);
+        self.pos += len;
+        len
+    }
+
+    /// Append data to frame
+    pub fn put(&mut self, data: &[u8]) -> usize {
+        let len = data.len().min(self.data.len() - self.len);
+        self.data[self.len..self.len + len].copy_from_slice(&data[..len]);
+        self.len += len;
+        len
+    }
+
+    /// Replace pending data with entire frame
+    ///
+    /// Data which exceeds frame size is ignored.
+    pub fn set(&mut self, data: &[u8]) -> usize {
+        self.clear();
+        let len = self.put(data);
+        self.complete = len > 0;
+        len
+    }
+
+    /// Write entire frame using writer
+    ///
+    /// Buffer will be cleared when data is written.
+    /// Fails with [std::io::ErrorKind::InvalidInput] when frame is incomplete.
+    pub fn flush(&mut self, writer: impl FnOnce(&[u8]) -> Result<usize>) -> Result<()> {
+        if self.len == 0 {
+            return Ok(());
+        }
+        if !self.is_full() {
+            return Err(utils::invalid_input("Incomplete frame"));
+        }
+        writer(&self.data[..self.len])?;
+        self.clear();
+        Ok(())
+    }
+}
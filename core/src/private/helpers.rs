@@ -1,8 +1,13 @@
-use crate::{ContentType, Result};
+use crate::{CapabilityFlag, ContentType, Direction, Error, Format, Internal, Result};
 use std::{
     fs::{File, OpenOptions},
-    os::unix::fs::{FileTypeExt, OpenOptionsExt},
+    io,
+    os::unix::{
+        fs::{FileTypeExt, OpenOptionsExt},
+        io::{FromRawFd, RawFd},
+    },
     path::Path,
+    time::{Duration, Instant},
 };
 
 /// Open device by path or name
@@ -32,6 +37,32 @@ pub fn open(path: impl AsRef<Path>, nonblock: bool) -> Result<File> {
         .open(path)
 }
 
+/// Get data format for read/write I/O method
+///
+/// Also returns frame size in bytes.
+pub fn read_write_format<Dir: Direction>(fd: RawFd, type_: ContentType) -> Result<(Format, usize)> {
+    let caps = Internal::<crate::Capability>::query(fd)?;
+
+    if !caps
+        .device_capabilities()
+        .contains(CapabilityFlag::ReadWrite)
+    {
+        return Err(Error::new(
+            io::ErrorKind::Unsupported,
+            "Read/write I/O is not supported",
+        ));
+    }
+
+    let mut format = Format::from(Dir::buffer_type(type_));
+    Internal::from(&mut format).get(fd)?;
+
+    let size = format
+        .frame_size()
+        .ok_or_else(|| crate::utils::invalid_input("Unknown frame size"))?;
+
+    Ok((format, size))
+}
+
 /// Check video device name prefix
 pub fn check_dev_name(name: impl AsRef<str>) -> Option<ContentType> {
     let name = name.as_ref();
@@ -45,3 +76,96 @@ pub fn check_dev_name(name: impl AsRef<str>) -> Option<ContentType> {
         None
     }
 }
+
+/// Check that error means device disconnection
+pub fn is_disconnected(error: &Error) -> bool {
+    crate::error::ErrorKind::of(error) == crate::error::ErrorKind::Disconnected
+}
+
+/// Create event descriptor to interrupt waiting
+pub fn event_fd() -> Result<File> {
+    use nix::sys::eventfd::{eventfd, EfdFlags};
+
+    let fd = eventfd(0, EfdFlags::EFD_CLOEXEC | EfdFlags::EFD_NONBLOCK)?;
+
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+/// Wait until device is ready to read or write
+///
+/// Fails with [io::ErrorKind::TimedOut] when timeout is elapsed
+/// and with [ErrorKind::Cancelled](crate::error::ErrorKind::Cancelled) when event descriptor is signaled.
+pub fn wait_ready(
+    fd: RawFd,
+    output: bool,
+    event_fd: RawFd,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    use nix::poll::PollFlags;
+
+    let events = if output {
+        PollFlags::POLLOUT
+    } else {
+        PollFlags::POLLIN
+    };
+
+    wait_poll(fd, events, Some(event_fd), timeout)
+}
+
+/// Wait until device has pending events
+///
+/// Fails with [io::ErrorKind::TimedOut] when timeout is elapsed
+/// and with [ErrorKind::Cancelled](crate::error::ErrorKind::Cancelled) when event descriptor is signaled.
+pub fn wait_event(fd: RawFd, event_fd: Option<RawFd>, timeout: Option<Duration>) -> Result<()> {
+    wait_poll(fd, nix::poll::PollFlags::POLLPRI, event_fd, timeout)
+}
+
+/// Wait until descriptor is readable
+///
+/// Fails with [io::ErrorKind::TimedOut] when timeout is elapsed
+/// and with [ErrorKind::Cancelled](crate::error::ErrorKind::Cancelled) when event descriptor is signaled.
+pub fn wait_readable(fd: RawFd, event_fd: Option<RawFd>, timeout: Option<Duration>) -> Result<()> {
+    wait_poll(fd, nix::poll::PollFlags::POLLIN, event_fd, timeout)
+}
+
+fn wait_poll(
+    fd: RawFd,
+    events: nix::poll::PollFlags,
+    event_fd: Option<RawFd>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    use nix::{
+        errno::Errno,
+        poll::{poll, PollFd, PollFlags},
+    };
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let timeout = deadline
+            .map(|deadline| {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                // round up to avoid busy looping
+                remaining.as_nanos().div_ceil(1_000_000).min(i32::MAX as _) as _
+            })
+            .unwrap_or(-1);
+
+        let mut fds = [
+            PollFd::new(fd, events),
+            // negative descriptor is ignored by poll
+            PollFd::new(event_fd.unwrap_or(-1), PollFlags::POLLIN),
+        ];
+
+        match poll(&mut fds, timeout) {
+            Ok(0) => return Err(Error::new(io::ErrorKind::TimedOut, "Stream timed out")),
+            Ok(_) => {
+                if fds[1].revents().map(|e| !e.is_empty()).unwrap_or(false) {
+                    return Err(crate::error::Error::Cancelled.into());
+                }
+                return Ok(());
+            }
+            Err(Errno::EINTR) => continue,
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{types::*, SyncMode, SyncStats};
+use core::time::Duration;
+
+/// Frames pairing state of synchronized streams
+pub struct FrameMatcher {
+    mode: SyncMode,
+    offsets: Vec<Option<u32>>,
+    sequences: Vec<Option<u32>>,
+    stats: SyncStats,
+}
+
+impl FrameMatcher {
+    /// Create matcher for specified number of streams
+    pub fn new(mode: SyncMode, streams: usize) -> Self {
+        Self {
+            mode,
+            offsets: vec![None; streams],
+            sequences: vec![None; streams],
+            stats: SyncStats::new(streams),
+        }
+    }
+
+    /// Pairing mode
+    pub fn mode(&self) -> SyncMode {
+        self.mode
+    }
+
+    /// Collected statistics
+    pub fn stats(&self) -> &SyncStats {
+        &self.stats
+    }
+
+    /// Forget sequence numbers and offsets
+    ///
+    /// Should be called when streams is restarted.
+    pub fn reset(&mut self) {
+        self.offsets.iter_mut().for_each(|offset| *offset = None);
+        self.sequences
+            .iter_mut()
+            .for_each(|sequence| *sequence = None);
+    }
+
+    /// Check that stream needs frame sync event to determine its sequence offset
+    pub fn needs_event(&self, index: usize) -> bool {
+        self.mode == SyncMode::FrameSync && self.offsets[index].is_none()
+    }
+
+    /// Use frame sync event of stream
+    pub fn event(&mut self, index: usize, event: &Event) {
+        if let Some(data) = event.data::<EventFrameSync>() {
+            if self.offsets[index].is_none() {
+                self.offsets[index] = Some(data.frame_sequence());
+            }
+        }
+    }
+
+    /// Account frame received from stream
+    pub fn receive(&mut self, index: usize, buffer: &Buffer) {
+        let sequence = buffer.sequence();
+        if let Some(last) = self.sequences[index] {
+            // sequence numbers wraps around, backward steps is ignored
+            let step = sequence.wrapping_sub(last);
+            if step > 1 && step <= i32::MAX as u32 {
+                self.stats.dropped[index] += (step - 1) as u64;
+            }
+        }
+        self.sequences[index] = Some(sequence);
+    }
+
+    /// Check that frames of all streams is aligned
+    ///
+    /// Returns indexes of streams which frames should be skipped
+    /// or empty list when frames is aligned.
+    pub fn check(&mut self, frames: &[Buffer]) -> Vec<usize> {
+        let stale: Vec<usize> = match self.mode {
+            SyncMode::Timestamp(tolerance) => {
+                let times: Vec<Duration> = frames
+                    .iter()
+                    .map(|frame| frame.timestamp::<Duration>())
+                    .collect();
+                let latest = times.iter().max().copied().unwrap_or_default();
+                (0..frames.len())
+                    .filter(|&index| latest - times[index] > tolerance)
+                    .collect()
+            }
+            SyncMode::FrameSync => {
+                let keys: Vec<u32> = frames
+                    .iter()
+                    .zip(&self.offsets)
+                    .map(|(frame, offset)| frame.sequence().wrapping_sub(offset.unwrap_or(0)))
+                    .collect();
+                let latest = keys.iter().max().copied().unwrap_or_default();
+                (0..frames.len())
+                    .filter(|&index| keys[index] < latest)
+                    .collect()
+            }
+        };
+
+        if stale.is_empty() {
+            self.stats.groups += 1;
+        } else {
+            for &index in &stale {
+                self.stats.unmatched[index] += 1;
+            }
+        }
+
+        stale
+    }
+}
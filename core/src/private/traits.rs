@@ -6,11 +6,13 @@ use std::os::unix::io::RawFd;
 
 /// Direction implementation details
 pub trait DirectionImpl: Sized {
-    /// Get next frame buffer from queue
-    fn next<Met: Method>(
+    /// Prepare queue to get next frame buffer
+    ///
+    /// Returns buffer when it available without dequeueing.
+    fn prepare<Met: Method>(
         queue: &Internal<QueueData<Self, Met>>,
         fd: RawFd,
-    ) -> Result<BufferRef<Self, Met>>;
+    ) -> Result<Option<BufferRef<Self, Met>>>;
 }
 
 /// I/O method implementation details
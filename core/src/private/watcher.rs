@@ -0,0 +1,96 @@
+use crate::{check_dev_name, DeviceEvent, Result};
+use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    os::unix::{
+        fs::FileTypeExt,
+        io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, RawFd},
+    },
+    path::Path,
+};
+
+const DEV_DIR: &str = "/dev";
+
+/// Watcher of video device nodes
+///
+/// Watches `/dev` directory using inotify.
+/// Descriptor is non-blocking and readable when events is pending.
+pub struct DeviceWatcher {
+    file: File,
+    events: VecDeque<DeviceEvent>,
+}
+
+impl AsRawFd for DeviceWatcher {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+impl AsFd for DeviceWatcher {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
+
+impl DeviceWatcher {
+    /// Start watching
+    pub fn new() -> Result<Self> {
+        let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?;
+        let file = unsafe { File::from_raw_fd(inotify.as_raw_fd()) };
+
+        inotify.add_watch(
+            DEV_DIR,
+            AddWatchFlags::IN_CREATE
+                | AddWatchFlags::IN_DELETE
+                | AddWatchFlags::IN_MOVED_TO
+                | AddWatchFlags::IN_MOVED_FROM,
+        )?;
+
+        Ok(Self {
+            file,
+            events: VecDeque::new(),
+        })
+    }
+
+    /// Get next pending event
+    ///
+    /// Fails with [std::io::ErrorKind::WouldBlock] when no events is pending.
+    pub fn read(&mut self) -> Result<DeviceEvent> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+
+            let inotify = unsafe { Inotify::from_raw_fd(self.file.as_raw_fd()) };
+
+            for event in inotify.read_events()? {
+                let Some(name) = event.name else {
+                    continue;
+                };
+
+                if check_dev_name(name.to_string_lossy()).is_none() {
+                    continue;
+                }
+
+                let path = Path::new(DEV_DIR).join(name);
+
+                if event
+                    .mask
+                    .intersects(AddWatchFlags::IN_CREATE | AddWatchFlags::IN_MOVED_TO)
+                {
+                    // node may be removed already
+                    if path
+                        .symlink_metadata()
+                        .map(|meta| meta.file_type().is_char_device())
+                        .unwrap_or_default()
+                    {
+                        self.events.push_back(DeviceEvent::Added(path));
+                    }
+                } else {
+                    self.events.push_back(DeviceEvent::Removed(path));
+                }
+            }
+        }
+    }
+}
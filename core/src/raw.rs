@@ -0,0 +1,598 @@
+//! Raw sensor data processing
+//!
+//! Unpacking of Bayer formats to 16-bit samples and simple image signal
+//! processing (black level, white balance, demosaicing, color correction)
+//! for sensors which has no own ISP.
+
+use crate::{types::*, utils, PlaneLayout, Result};
+use getset::{CopyGetters, Setters};
+
+/// Color filter array pattern
+///
+/// Colors of top-left 2x2 block of sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum CfaPattern {
+    Rggb,
+    Bggr,
+    Grbg,
+    Gbrg,
+}
+
+impl CfaPattern {
+    /// Get pattern of Bayer pixel format
+    pub fn from_fourcc(fourcc: FourCc) -> Option<Self> {
+        let info = fourcc.info()?;
+        if !info.is_bayer() {
+            return None;
+        }
+        Some(match info.order() {
+            [Component::Red, ..] => Self::Rggb,
+            [Component::Blue, ..] => Self::Bggr,
+            [Component::Green, Component::Red, ..] => Self::Grbg,
+            [Component::Green, Component::Blue, ..] => Self::Gbrg,
+            _ => return None,
+        })
+    }
+
+    /// Get color channel (0 - red, 1 - green, 2 - blue) of pixel
+    pub fn channel(self, x: usize, y: usize) -> usize {
+        let block = match self {
+            Self::Rggb => [0, 1, 1, 2],
+            Self::Bggr => [2, 1, 1, 0],
+            Self::Grbg => [1, 0, 2, 1],
+            Self::Gbrg => [1, 2, 0, 1],
+        };
+        block[(y & 1) * 2 + (x & 1)]
+    }
+}
+
+impl core::fmt::Display for CfaPattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Rggb => "RGGB",
+            Self::Bggr => "BGGR",
+            Self::Grbg => "GRBG",
+            Self::Gbrg => "GBRG",
+        })
+    }
+}
+
+/// Demosaicing method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Demosaic {
+    /// Bilinear interpolation
+    Bilinear,
+    /// Gradient-corrected linear interpolation by Malvar, He and Cutler
+    #[default]
+    Malvar,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Packing {
+    /// 8-bit samples
+    U8,
+    /// 16-bit little-endian samples
+    U16,
+    /// MIPI CSI-2 packed 10-bit, 4 samples in 5 bytes
+    Mipi10,
+    /// MIPI CSI-2 packed 12-bit, 2 samples in 3 bytes
+    Mipi12,
+    /// MIPI CSI-2 packed 14-bit, 4 samples in 7 bytes
+    Mipi14,
+    /// 10-bit A-law compressed to 8-bit
+    Alaw8,
+    /// 10-bit DPCM compressed to 8-bit
+    Dpcm8,
+    /// Intel IPU3 packed 10-bit, 25 samples in 32 bytes
+    Ipu3,
+}
+
+impl Packing {
+    fn new(fourcc: FourCc) -> Option<Self> {
+        Some(match fourcc {
+            FourCc::Sbggr8 | FourCc::Sgbrg8 | FourCc::Sgrbg8 | FourCc::Srggb8 => Self::U8,
+            FourCc::Sbggr10
+            | FourCc::Sgbrg10
+            | FourCc::Sgrbg10
+            | FourCc::Srggb10
+            | FourCc::Sbggr12
+            | FourCc::Sgbrg12
+            | FourCc::Sgrbg12
+            | FourCc::Srggb12
+            | FourCc::Sbggr14
+            | FourCc::Sgbrg14
+            | FourCc::Sgrbg14
+            | FourCc::Srggb14
+            | FourCc::Sbggr16
+            | FourCc::Sgbrg16
+            | FourCc::Sgrbg16
+            | FourCc::Srggb16 => Self::U16,
+            FourCc::Sbggr10p | FourCc::Sgbrg10p | FourCc::Sgrbg10p | FourCc::Srggb10p => {
+                Self::Mipi10
+            }
+            FourCc::Sbggr12p | FourCc::Sgbrg12p | FourCc::Sgrbg12p | FourCc::Srggb12p => {
+                Self::Mipi12
+            }
+            FourCc::Sbggr14p | FourCc::Sgbrg14p | FourCc::Sgrbg14p | FourCc::Srggb14p => {
+                Self::Mipi14
+            }
+            FourCc::Sbggr10alaw8
+            | FourCc::Sgbrg10alaw8
+            | FourCc::Sgrbg10alaw8
+            | FourCc::Srggb10alaw8 => Self::Alaw8,
+            FourCc::Sbggr10dpcm8
+            | FourCc::Sgbrg10dpcm8
+            | FourCc::Sgrbg10dpcm8
+            | FourCc::Srggb10dpcm8 => Self::Dpcm8,
+            FourCc::Ipu3Sbggr10
+            | FourCc::Ipu3Sgbrg10
+            | FourCc::Ipu3Sgrbg10
+            | FourCc::Ipu3Srggb10 => Self::Ipu3,
+            _ => return None,
+        })
+    }
+
+    fn unpack(self, row: &[u8], out: &mut [u16]) {
+        match self {
+            Self::U8 => {
+                for (out, data) in out.iter_mut().zip(row) {
+                    *out = *data as _;
+                }
+            }
+            Self::U16 => {
+                for (out, data) in out.iter_mut().zip(row.chunks_exact(2)) {
+                    *out = u16::from_le_bytes([data[0], data[1]]);
+                }
+            }
+            Self::Mipi10 => {
+                for (out, data) in out.chunks_mut(4).zip(row.chunks(5)) {
+                    let low = data[data.len() - 1];
+                    for (i, out) in out.iter_mut().enumerate() {
+                        *out = ((data[i] as u16) << 2) | ((low >> (i * 2)) & 0x3) as u16;
+                    }
+                }
+            }
+            Self::Mipi12 => {
+                for (out, data) in out.chunks_mut(2).zip(row.chunks(3)) {
+                    let low = data[data.len() - 1];
+                    for (i, out) in out.iter_mut().enumerate() {
+                        *out = ((data[i] as u16) << 4) | ((low >> (i * 4)) & 0xf) as u16;
+                    }
+                }
+            }
+            Self::Mipi14 => {
+                for (out, data) in out.chunks_mut(4).zip(row.chunks(7)) {
+                    let low = data[4..]
+                        .iter()
+                        .rev()
+                        .fold(0u32, |low, byte| (low << 8) | *byte as u32);
+                    for (i, out) in out.iter_mut().enumerate() {
+                        *out = ((data[i] as u16) << 6) | ((low >> (i * 6)) & 0x3f) as u16;
+                    }
+                }
+            }
+            Self::Alaw8 => {
+                for (out, data) in out.iter_mut().zip(row) {
+                    *out = alaw_expand(*data);
+                }
+            }
+            Self::Dpcm8 => {
+                for x in 0..out.len().min(row.len()) {
+                    // the first two samples has no predictor
+                    let pred = if x < 2 { None } else { Some(out[x - 2]) };
+                    out[x] = dpcm_decode(row[x], pred);
+                }
+            }
+            Self::Ipu3 => {
+                for (out, data) in out.chunks_mut(25).zip(row.chunks(32)) {
+                    for (i, out) in out.iter_mut().enumerate() {
+                        let bit = i * 10;
+                        let byte = bit / 8;
+                        let word =
+                            data[byte] as u16 | (*data.get(byte + 1).unwrap_or(&0) as u16) << 8;
+                        *out = (word >> (bit % 8)) & 0x3ff;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Expand 8-bit A-law code to 10-bit sample
+///
+/// The code consists of 3-bit segment and 5-bit mantissa, the first two
+/// segments is linear and each next one doubles the step.
+fn alaw_expand(code: u8) -> u16 {
+    let segment = (code >> 5) as u16;
+    let mantissa = (code & 0x1f) as u16;
+
+    let value = if segment == 0 {
+        mantissa
+    } else {
+        // use middle of quantization step
+        ((32 + mantissa) << (segment - 1)) + ((1 << (segment - 1)) >> 1)
+    };
+
+    value.min(0x3ff)
+}
+
+/// Decode 8-bit DPCM code to 10-bit sample (SMIA 10-8-10 simple predictor)
+///
+/// | Code        | Mode  | Difference  |
+/// |-------------|-------|-------------|
+/// | `00s xxxxx` | DPCM1 | 0..=31      |
+/// | `010s xxxx` | DPCM2 | 32..=63     |
+/// | `011s xxxx` | DPCM3 | 64..=127    |
+/// | `1 xxxxxxx` | PCM   | 7 MSBs      |
+fn dpcm_decode(code: u8, pred: Option<u16>) -> u16 {
+    let pcm = || ((code as u16 & 0x7f) << 3) | 0x4;
+
+    let pred = match pred {
+        Some(pred) if code & 0x80 == 0 => pred as i32,
+        _ => return pcm(),
+    };
+
+    let (negative, diff) = if code & 0x40 == 0 {
+        (code & 0x20 != 0, (code & 0x1f) as i32)
+    } else if code & 0x20 == 0 {
+        (code & 0x10 != 0, 32 + ((code & 0xf) as i32) * 2 + 1)
+    } else {
+        (code & 0x10 != 0, 64 + ((code & 0xf) as i32) * 4 + 2)
+    };
+
+    (if negative { pred - diff } else { pred + diff }).clamp(0, 0x3ff) as _
+}
+
+/// Raw sensor image unpacked to 16-bit samples
+#[derive(Debug, Clone, CopyGetters)]
+pub struct RawImage {
+    /// Image width in pixels
+    #[getset(get_copy = "pub")]
+    width: u32,
+
+    /// Image height in lines
+    #[getset(get_copy = "pub")]
+    height: u32,
+
+    /// Number of significant bits of samples
+    #[getset(get_copy = "pub")]
+    depth: u8,
+
+    /// Color filter array pattern
+    #[getset(get_copy = "pub")]
+    pattern: CfaPattern,
+
+    samples: Vec<u16>,
+}
+
+impl RawImage {
+    /// Create from unpacked samples
+    pub fn new(
+        width: u32,
+        height: u32,
+        depth: u8,
+        pattern: CfaPattern,
+        samples: Vec<u16>,
+    ) -> Result<Self> {
+        if samples.len() != width as usize * height as usize {
+            return Err(utils::invalid_input(
+                "Number of samples mismatch image size",
+            ));
+        }
+        if depth == 0 || depth > 16 {
+            return Err(utils::invalid_input("Invalid depth of samples"));
+        }
+        Ok(Self {
+            width,
+            height,
+            depth,
+            pattern,
+            samples,
+        })
+    }
+
+    /// Unpack raw image data
+    ///
+    /// The `pixfmt` describes the layout of data, so strided buffers of
+    /// stream may be used directly.
+    pub fn unpack(data: impl AsRef<[u8]>, pixfmt: &PixFormat) -> Result<Self> {
+        let data = data.as_ref();
+        let fourcc = pixfmt.pixel_format();
+
+        let packing =
+            Packing::new(fourcc).ok_or_else(|| utils::invalid_input("Unsupported pixel format"))?;
+        let pattern = CfaPattern::from_fourcc(fourcc)
+            .ok_or_else(|| utils::invalid_input("Unsupported pixel format"))?;
+        let layout = PlaneLayout::from_pix_format(pixfmt)
+            .ok_or_else(|| utils::invalid_input("Unsupported pixel format"))?;
+
+        if data.len() < layout.size_image() as usize {
+            return Err(utils::invalid_input("Buffer too small"));
+        }
+
+        let width = layout.width() as usize;
+        let height = layout.height() as usize;
+        let bytes_per_line = layout.bytes_per_line(0) as usize;
+        let mut samples = vec![0; width * height];
+
+        for (line, out) in samples.chunks_exact_mut(width).enumerate() {
+            packing.unpack(&data[line * bytes_per_line..][..bytes_per_line], out);
+        }
+
+        Ok(Self {
+            width: width as _,
+            height: height as _,
+            depth: layout.info().depth(),
+            pattern,
+            samples,
+        })
+    }
+
+    /// Get samples
+    pub fn samples(&self) -> &[u16] {
+        &self.samples
+    }
+
+    /// Get sample at position
+    pub fn sample(&self, x: u32, y: u32) -> u16 {
+        self.samples[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Process raw image to 8-bit RGB
+    ///
+    /// Returns tightly packed [FourCc::Rgb24] data.
+    pub fn process(&self, processing: &Processing) -> Vec<u8> {
+        let width = self.width as usize;
+        let height = self.height as usize;
+
+        let black = processing.black_level as f32;
+        let white = processing
+            .white_level
+            .unwrap_or(((1u32 << self.depth) - 1) as u16) as f32;
+        let scale = 1.0 / (white - black).max(1.0);
+
+        // normalize samples and apply white balance gains
+        let plane: Vec<f32> = self
+            .samples
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                let gain = processing.gains[self.pattern.channel(i % width, i / width)];
+                (*sample as f32 - black).max(0.0) * scale * gain
+            })
+            .collect();
+
+        let demosaic = Demosaicer {
+            plane: &plane,
+            width,
+            height,
+            pattern: self.pattern,
+        };
+
+        let lut = processing.srgb.then(srgb_lut);
+        let ccm = processing.ccm;
+
+        let mut output = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let rgb = match processing.method {
+                    Demosaic::Bilinear => demosaic.bilinear(x, y),
+                    Demosaic::Malvar => demosaic.malvar(x, y),
+                };
+                for row in &ccm {
+                    let value =
+                        (row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]).clamp(0.0, 1.0);
+                    output.push(if let Some(lut) = &lut {
+                        lut[(value * (LUT_SIZE - 1) as f32).round() as usize]
+                    } else {
+                        (value * 255.0).round() as u8
+                    });
+                }
+            }
+        }
+
+        output
+    }
+}
+
+const LUT_SIZE: usize = 4096;
+
+/// Make lookup table of sRGB transfer function
+fn srgb_lut() -> Vec<u8> {
+    (0..LUT_SIZE)
+        .map(|i| {
+            let value = i as f32 / (LUT_SIZE - 1) as f32;
+            let value = if value <= 0.0031308 {
+                value * 12.92
+            } else {
+                1.055 * value.powf(1.0 / 2.4) - 0.055
+            };
+            (value * 255.0).round() as u8
+        })
+        .collect()
+}
+
+/// Raw image processing parameters
+#[derive(Debug, Clone, Copy, CopyGetters, Setters)]
+#[getset(get_copy = "pub", set = "pub")]
+pub struct Processing {
+    /// Black level in units of samples
+    black_level: u16,
+
+    /// White level in units of samples
+    ///
+    /// The maximum value for depth of samples is used by default.
+    white_level: Option<u16>,
+
+    /// White balance gains of red, green and blue channels
+    gains: [f32; 3],
+
+    /// Color correction matrix (row-major, applied to linear RGB)
+    ccm: [[f32; 3]; 3],
+
+    /// Apply sRGB transfer function
+    srgb: bool,
+
+    /// Demosaicing method
+    method: Demosaic,
+}
+
+impl Default for Processing {
+    fn default() -> Self {
+        Self {
+            black_level: 0,
+            white_level: None,
+            gains: [1.0; 3],
+            ccm: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            srgb: true,
+            method: Demosaic::default(),
+        }
+    }
+}
+
+struct Demosaicer<'a> {
+    plane: &'a [f32],
+    width: usize,
+    height: usize,
+    pattern: CfaPattern,
+}
+
+/// Mirror coordinate at edges keeping the phase of pattern
+fn mirror(i: isize, n: usize) -> usize {
+    let n = n as isize;
+    let i = if i < 0 {
+        -i
+    } else if i >= n {
+        2 * (n - 1) - i
+    } else {
+        i
+    };
+    i.clamp(0, n - 1) as _
+}
+
+/// Malvar-He-Cutler kernels as (dx, dy, weight * 16)
+type Kernel = &'static [(isize, isize, i8)];
+
+/// Green at red or blue
+const MHC_G: Kernel = &[
+    (0, -2, -2),
+    (0, -1, 4),
+    (-2, 0, -2),
+    (-1, 0, 4),
+    (0, 0, 8),
+    (1, 0, 4),
+    (2, 0, -2),
+    (0, 1, 4),
+    (0, 2, -2),
+];
+
+/// Red or blue at green which has the color in row
+const MHC_ROW: Kernel = &[
+    (0, -2, 1),
+    (-1, -1, -2),
+    (1, -1, -2),
+    (-2, 0, -2),
+    (-1, 0, 8),
+    (0, 0, 10),
+    (1, 0, 8),
+    (2, 0, -2),
+    (-1, 1, -2),
+    (1, 1, -2),
+    (0, 2, 1),
+];
+
+/// Red or blue at green which has the color in column
+const MHC_COL: Kernel = &[
+    (0, -2, -2),
+    (-1, -1, -2),
+    (1, -1, -2),
+    (0, -1, 8),
+    (-2, 0, 1),
+    (0, 0, 10),
+    (2, 0, 1),
+    (0, 1, 8),
+    (-1, 1, -2),
+    (1, 1, -2),
+    (0, 2, -2),
+];
+
+/// Red at blue or blue at red
+const MHC_DIAG: Kernel = &[
+    (0, -2, -3),
+    (-1, -1, 4),
+    (1, -1, 4),
+    (-2, 0, -3),
+    (0, 0, 12),
+    (2, 0, -3),
+    (-1, 1, 4),
+    (1, 1, 4),
+    (0, 2, -3),
+];
+
+impl Demosaicer<'_> {
+    fn get(&self, x: isize, y: isize) -> f32 {
+        self.plane[mirror(y, self.height) * self.width + mirror(x, self.width)]
+    }
+
+    fn channel(&self, x: isize, y: isize) -> usize {
+        self.pattern
+            .channel(mirror(x, self.width), mirror(y, self.height))
+    }
+
+    fn bilinear(&self, x: usize, y: usize) -> [f32; 3] {
+        let (x, y) = (x as isize, y as isize);
+        let mut sum = [0.0; 3];
+        let mut count = [0u8; 3];
+
+        let own = self.channel(x, y);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let channel = self.channel(x + dx, y + dy);
+                if channel != own {
+                    sum[channel] += self.get(x + dx, y + dy);
+                    count[channel] += 1;
+                }
+            }
+        }
+
+        core::array::from_fn(|channel| {
+            if channel == own {
+                self.get(x, y)
+            } else if count[channel] > 0 {
+                sum[channel] / count[channel] as f32
+            } else {
+                0.0
+            }
+        })
+    }
+
+    fn convolve(&self, x: isize, y: isize, kernel: Kernel) -> f32 {
+        kernel
+            .iter()
+            .map(|(dx, dy, weight)| self.get(x + dx, y + dy) * *weight as f32)
+            .sum::<f32>()
+            / 16.0
+    }
+
+    fn malvar(&self, x: usize, y: usize) -> [f32; 3] {
+        let (x, y) = (x as isize, y as isize);
+
+        let own = self.channel(x, y);
+        core::array::from_fn(|channel| {
+            if channel == own {
+                self.get(x, y)
+            } else if channel == 1 {
+                self.convolve(x, y, MHC_G)
+            } else if own != 1 {
+                self.convolve(x, y, MHC_DIAG)
+            } else if self.channel(x + 1, y) == channel {
+                self.convolve(x, y, MHC_ROW)
+            } else {
+                self.convolve(x, y, MHC_COL)
+            }
+            .max(0.0)
+        })
+    }
+}
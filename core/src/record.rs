@@ -0,0 +1,460 @@
+//! Recording and replaying of device calls
+//!
+//! The [Recorder] captures ioctl calls of opened device with its data and
+//! results into [Session], which can be saved to file. The [ReplayDevice]
+//! reproduces recorded session offline without device.
+//!
+//! Memory of buffers and payloads of compound controls is not recorded, so
+//! replayed frames is empty.
+
+use crate::{
+    calls::{self, Backend, Kernel, Request},
+    types::*,
+    Result,
+};
+use core::time::Duration;
+use nix::{
+    errno::Errno,
+    libc::{c_int, c_void},
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    os::unix::{
+        io::{AsRawFd, BorrowedFd, OwnedFd, RawFd},
+        net::UnixStream,
+    },
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// Recorded device call
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IoctlRecord {
+    pub(crate) request: u64,
+    pub(crate) result: i32,
+    pub(crate) duration: Duration,
+    pub(crate) input: Vec<u8>,
+    pub(crate) output: Vec<u8>,
+    pub(crate) extra_input: Vec<u8>,
+    pub(crate) extra_output: Vec<u8>,
+}
+
+impl IoctlRecord {
+    /// Request code
+    pub fn request(&self) -> u64 {
+        self.request
+    }
+
+    /// Request number
+    pub fn nr(&self) -> u8 {
+        calls::request_nr(self.request as _)
+    }
+
+    /// Result of call
+    ///
+    /// Returns error number when call is failed.
+    pub fn result(&self) -> core::result::Result<c_int, Errno> {
+        if self.result < 0 {
+            Err(Errno::from_i32(-self.result))
+        } else {
+            Ok(self.result)
+        }
+    }
+
+    /// Duration of call
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Request data before call
+    pub fn input(&self) -> &[u8] {
+        &self.input
+    }
+
+    /// Request data after call
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+}
+
+impl core::fmt::Display for IoctlRecord {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "#{} ", self.nr())?;
+        match self.result() {
+            Ok(value) => value.fmt(f)?,
+            Err(errno) => errno.fmt(f)?,
+        }
+        write!(f, " in {:?}", self.duration)
+    }
+}
+
+/// Sequence of recorded device calls
+///
+/// Session is serialized with serde, so any serde format can be used
+/// besides JSON files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Session {
+    pub(crate) records: Vec<IoctlRecord>,
+}
+
+impl Session {
+    /// Recorded calls
+    pub fn records(&self) -> &[IoctlRecord] {
+        &self.records
+    }
+
+    /// Save session to file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        self.write(&mut file)?;
+        file.flush()
+    }
+
+    /// Load session from file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Serialize session as JSON
+    pub fn write(&self, writer: impl Write) -> Result<()> {
+        serde_json::to_writer(writer, self).map_err(From::from)
+    }
+
+    /// Deserialize session from JSON
+    pub fn read(reader: impl Read) -> Result<Self> {
+        serde_json::from_reader(reader).map_err(From::from)
+    }
+}
+
+/// Data referenced by request data
+///
+/// Arrays of controls and planes is recorded in addition to request data.
+unsafe fn extra_data(request: Request, data: *mut c_void) -> Option<(*mut u8, usize)> {
+    if data.is_null() || calls::request_magic(request) != calls::MAGIC {
+        return None;
+    }
+
+    match calls::request_nr(request) {
+        calls::g_ext_ctrls::NR | calls::s_ext_ctrls::NR | calls::try_ext_ctrls::NR => {
+            let ctrls = &*(data as *const ExtControls);
+            (!ctrls.controls.is_null()).then(|| {
+                (
+                    ctrls.controls as _,
+                    ctrls.count as usize * core::mem::size_of::<ExtControl>(),
+                )
+            })
+        }
+        calls::query_buf::NR | calls::q_buf::NR | calls::dq_buf::NR | calls::prepare_buf::NR => {
+            let buffer = &*(data as *const Buffer);
+            (is_mplane(buffer) && !buffer.m.planes.is_null()).then(|| {
+                (
+                    buffer.m.planes as _,
+                    buffer.length as usize * core::mem::size_of::<Plane>(),
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+fn code(request: Request) -> u64 {
+    request as _
+}
+
+fn is_mplane(buffer: &Buffer) -> bool {
+    (buffer.type_ as u32) == (BufferType::VideoCaptureMplane as u32)
+        || (buffer.type_ as u32) == (BufferType::VideoOutputMplane as u32)
+}
+
+unsafe fn copy_data(data: *const c_void, size: usize) -> Vec<u8> {
+    if data.is_null() || size == 0 {
+        return Vec::new();
+    }
+    core::slice::from_raw_parts(data as *const u8, size).to_vec()
+}
+
+/// Recorder of device calls
+///
+/// Wraps backend of file, so calls of mock devices can be recorded too.
+/// Recording stops when recorder is dropped.
+pub struct Recorder {
+    backend: Arc<RecordBackend>,
+    previous: Option<Arc<dyn Backend>>,
+    file: OwnedFd,
+}
+
+impl Recorder {
+    /// Start recording calls of opened file
+    pub fn attach(fd: RawFd) -> Result<Self> {
+        let file = unsafe { BorrowedFd::borrow_raw(fd) }.try_clone_to_owned()?;
+        let previous = calls::registered_backend(fd);
+
+        let backend = Arc::new(RecordBackend {
+            inner: previous.clone().unwrap_or_else(|| Arc::new(Kernel)),
+            session: Mutex::new(Session::default()),
+        });
+
+        calls::register_backend(fd, backend.clone())?;
+
+        Ok(Self {
+            backend,
+            previous,
+            file,
+        })
+    }
+
+    /// Stop recording
+    ///
+    /// Recorded session is returned.
+    pub fn detach(self) -> Session {
+        self.session()
+    }
+
+    /// Copy of recorded session
+    pub fn session(&self) -> Session {
+        self.backend.session.lock().unwrap().clone()
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let fd = self.file.as_raw_fd();
+        let _ = match self.previous.take() {
+            Some(previous) => calls::register_backend(fd, previous),
+            None => calls::unregister_backend(fd),
+        };
+    }
+}
+
+struct RecordBackend {
+    inner: Arc<dyn Backend>,
+    session: Mutex<Session>,
+}
+
+impl Backend for RecordBackend {
+    unsafe fn ioctl(&self, fd: RawFd, request: Request, data: *mut c_void) -> nix::Result<c_int> {
+        let size = calls::request_size(request);
+        let extra = extra_data(request, data);
+
+        let input = copy_data(data, size);
+        let extra_input = extra
+            .map(|(extra, size)| copy_data(extra as _, size))
+            .unwrap_or_default();
+
+        let start = Instant::now();
+        let result = self.inner.ioctl(fd, request, data);
+        let duration = start.elapsed();
+
+        let output = copy_data(data, size);
+        let extra_output = extra
+            .map(|(extra, size)| copy_data(extra as _, size))
+            .unwrap_or_default();
+
+        self.session.lock().unwrap().records.push(IoctlRecord {
+            request: code(request),
+            result: match result {
+                Ok(value) => value,
+                Err(errno) => -(errno as i32),
+            },
+            duration,
+            input,
+            output,
+            extra_input,
+            extra_output,
+        });
+
+        result
+    }
+
+    fn mmap(&self, fd: RawFd, length: usize, offset: i64) -> nix::Result<*mut c_void> {
+        self.inner.mmap(fd, length, offset)
+    }
+}
+
+/// Device which replays recorded session
+///
+/// Calls is matched with records by request code and data. Calls which
+/// cannot be matched fails with [Errno::EIO].
+pub struct ReplayDevice {
+    backend: Arc<ReplayBackend>,
+    device: UnixStream,
+}
+
+impl ReplayDevice {
+    /// Create device from recorded session
+    pub fn new(session: Session) -> Result<Self> {
+        let (device, peer) = UnixStream::pair()?;
+        device.set_nonblocking(true)?;
+        // device stays readable, so waiting buffers never blocks
+        (&peer).write_all(&[0])?;
+
+        let used = vec![false; session.records.len()];
+        let backend = Arc::new(ReplayBackend {
+            records: session.records,
+            used: Mutex::new(used),
+            _peer: peer,
+        });
+
+        calls::register_backend(device.as_raw_fd(), backend.clone())?;
+
+        Ok(Self { backend, device })
+    }
+
+    /// Open device file
+    ///
+    /// The file is in non-blocking mode.
+    pub fn open(&self) -> Result<File> {
+        let device = self.device.try_clone()?;
+        Ok(File::from(OwnedFd::from(device)))
+    }
+
+    /// Number of records which is not replayed yet
+    pub fn remaining(&self) -> usize {
+        self.backend
+            .used
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|used| !**used)
+            .count()
+    }
+}
+
+impl Drop for ReplayDevice {
+    fn drop(&mut self) {
+        let _ = calls::unregister_backend(self.device.as_raw_fd());
+    }
+}
+
+struct ReplayBackend {
+    records: Vec<IoctlRecord>,
+    used: Mutex<Vec<bool>>,
+    _peer: UnixStream,
+}
+
+impl ReplayBackend {
+    fn find(&self, request: Request, input: &[u8]) -> Option<&IoctlRecord> {
+        let mut used = self.used.lock().unwrap();
+
+        let candidates = || {
+            self.records
+                .iter()
+                .enumerate()
+                .filter(|(index, record)| !used[*index] && record.request == code(request))
+        };
+
+        // prefer record with the same data, otherwise use first in order
+        let (index, record) = candidates()
+            .find(|(_, record)| record.input == input)
+            .or_else(|| candidates().next())?;
+
+        used[index] = true;
+        Some(record)
+    }
+}
+
+impl Backend for ReplayBackend {
+    unsafe fn ioctl(&self, _fd: RawFd, request: Request, data: *mut c_void) -> nix::Result<c_int> {
+        let input = copy_data(data, calls::request_size(request));
+
+        let record = self.find(request, &input).ok_or(Errno::EIO)?;
+
+        restore_output(request, data, record);
+
+        record.result()
+    }
+
+    fn mmap(&self, _fd: RawFd, length: usize, _offset: i64) -> nix::Result<*mut c_void> {
+        use nix::sys::mman::{mmap, MapFlags, ProtFlags};
+
+        unsafe {
+            mmap(
+                None,
+                core::num::NonZeroUsize::new(length).ok_or(Errno::EINVAL)?,
+                ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+                MapFlags::MAP_SHARED | MapFlags::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        }
+    }
+}
+
+/// Copy recorded data to request data
+///
+/// Fields which is not changed by recorded call keeps values of caller,
+/// so pointers to memory of caller stays valid.
+unsafe fn restore_output(request: Request, data: *mut c_void, record: &IoctlRecord) {
+    let size = calls::request_size(request);
+    if data.is_null() || record.output.len() != size || record.input.len() != size {
+        return;
+    }
+
+    let extra = extra_data(request, data);
+    let (input, output) = (&record.input[..], &record.output[..]);
+
+    match calls::request_nr(request) {
+        calls::g_ext_ctrls::NR | calls::s_ext_ctrls::NR | calls::try_ext_ctrls::NR => {
+            let ctrls = data as *mut ExtControls;
+            let controls = (*ctrls).controls;
+            copy_output(data, output);
+            keep(data, &mut (*ctrls).controls, controls, input, output);
+
+            if let Some((extra, size)) = extra {
+                let datas = extra as *mut ExtControl;
+                let count = size / core::mem::size_of::<ExtControl>();
+                let unions: Vec<_> = (0..count).map(|index| (*datas.add(index)).union_).collect();
+                copy_output(extra as _, &record.extra_output);
+                for (index, union_) in unions.into_iter().enumerate() {
+                    let field = &mut (*datas.add(index)).union_;
+                    keep(
+                        extra as _,
+                        field,
+                        union_,
+                        &record.extra_input,
+                        &record.extra_output,
+                    );
+                }
+            }
+        }
+        calls::query_buf::NR | calls::q_buf::NR | calls::dq_buf::NR | calls::prepare_buf::NR => {
+            let buffer = data as *mut Buffer;
+            let m = (*buffer).m;
+            copy_output(data, output);
+            keep(data, &mut (*buffer).m, m, input, output);
+
+            if let Some((extra, size)) = extra {
+                let planes = extra as *mut Plane;
+                let count = size / core::mem::size_of::<Plane>();
+                let ms: Vec<_> = (0..count).map(|index| (*planes.add(index)).m).collect();
+                copy_output(extra as _, &record.extra_output);
+                for (index, m) in ms.into_iter().enumerate() {
+                    let field = &mut (*planes.add(index)).m;
+                    keep(
+                        extra as _,
+                        field,
+                        m,
+                        &record.extra_input,
+                        &record.extra_output,
+                    );
+                }
+            }
+        }
+        _ => copy_output(data, output),
+    }
+}
+
+/// Restore value of field when recorded call is not changed it
+unsafe fn keep<T: Copy>(base: *mut c_void, field: *mut T, value: T, input: &[u8], output: &[u8]) {
+    let offset = field as usize - base as usize;
+    let range = offset..offset + core::mem::size_of::<T>();
+    if input.get(range.clone()) == output.get(range) {
+        field.write_unaligned(value);
+    }
+}
+
+unsafe fn copy_output(data: *mut c_void, output: &[u8]) {
+    core::ptr::copy_nonoverlapping(output.as_ptr(), data as *mut u8, output.len());
+}
@@ -0,0 +1,127 @@
+//! Logging of device calls
+//!
+//! Each ioctl is logged with `debug` level to `linux_video::ioctl` target.
+
+use crate::types::*;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use nix::libc::c_int;
+use std::time::Instant;
+
+/// Log target of device calls
+pub const TARGET: &str = "linux_video::ioctl";
+
+/// Description of ioctl data in log
+pub trait Describe {
+    /// Write description
+    fn describe(&self, f: &mut Formatter) -> FmtResult;
+}
+
+macro_rules! describe_impls {
+    (display $($type:ty),*) => {
+        $(
+            impl Describe for $type {
+                fn describe(&self, f: &mut Formatter) -> FmtResult {
+                    self.fmt(f)
+                }
+            }
+        )*
+    };
+
+    (debug $($type:ty),*) => {
+        $(
+            impl Describe for $type {
+                fn describe(&self, f: &mut Formatter) -> FmtResult {
+                    write!(f, "{self:?}")
+                }
+            }
+        )*
+    };
+
+    (opaque $($type:ty),*) => {
+        $(
+            impl Describe for $type {
+                fn describe(&self, f: &mut Formatter) -> FmtResult {
+                    "..".fmt(f)
+                }
+            }
+        )*
+    };
+}
+
+describe_impls!(display Buffer, Capability, FmtDesc, Format, FrmIvalEnum, FrmSizeEnum, QueryCtrl, QueryExtCtrl, StreamParm);
+
+describe_impls!(debug c_int, Audio, AudioOut, Control, Crop, CropCap, Edid, EncIdx, EventSubscription, ExportBuffer, FrameBuffer, Frequency, FrequencyBand, HwFreqSeek, Input, JpegCompression, Modulator, Output, Priority, RemoveBuffers, RequestBuffers, Selection, SlicedVbiCap, Standard, StdId, Tuner);
+
+describe_impls!(opaque DecoderCmd, DvTimings, DvTimingsCap, EncoderCmd, EnumDvTimings);
+
+impl Describe for () {
+    fn describe(&self, _f: &mut Formatter) -> FmtResult {
+        Ok(())
+    }
+}
+
+impl Describe for CreateBuffers {
+    fn describe(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "index: {}, count: {}, memory: {}",
+            self.index, self.count, self.memory
+        )
+    }
+}
+
+impl Describe for ExtControls {
+    fn describe(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "count: {}, error_idx: {}", self.count, self.error_idx)
+    }
+}
+
+impl Describe for Event {
+    fn describe(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "type: {}, id: {}, sequence: {}, pending: {}",
+            self.type_, self.id, self.sequence, self.pending
+        )
+    }
+}
+
+impl Describe for QueryMenu {
+    fn describe(&self, f: &mut Formatter) -> FmtResult {
+        let (id, index) = (self.id, self.index);
+        write!(f, "id: {id}, index: {index}")
+    }
+}
+
+struct Described<'a, T>(Option<&'a T>);
+
+impl<'a, T: Describe> Display for Described<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if let Some(data) = self.0 {
+            data.describe(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Make call and log it with result and duration
+pub fn traced<T: Describe>(
+    name: &'static str,
+    data: *const T,
+    call: impl FnOnce() -> nix::Result<c_int>,
+) -> nix::Result<c_int> {
+    let start = Instant::now();
+    let result = call();
+    let elapsed = start.elapsed();
+
+    if log::log_enabled!(target: TARGET, log::Level::Debug) {
+        // data stays valid during call
+        let data = Described(unsafe { data.as_ref() });
+        match result {
+            Ok(value) => log::debug!(target: TARGET, "{name}({data}) = {value} in {elapsed:?}"),
+            Err(errno) => log::debug!(target: TARGET, "{name}({data}) = {errno} in {elapsed:?}"),
+        }
+    }
+
+    result
+}
@@ -1,6 +1,7 @@
 mod consts;
 mod ctrlid;
 mod enums;
+mod fmtinfo;
 mod fourcc;
 mod stdid;
 mod structs;
@@ -8,6 +9,7 @@ mod structs;
 pub use consts::*;
 pub use ctrlid::*;
 pub use enums::*;
+pub use fmtinfo::*;
 pub use fourcc::*;
 pub use stdid::*;
 pub use structs::*;
@@ -1591,13 +1591,17 @@ impl BufferType {
 
 impl BufferFlag {
     /// Get timestamp type
-    pub fn timestamp(self) -> Timestamp {
-        unsafe { core::mem::transmute(self.bits & Self::TimestampMask.bits) }
+    ///
+    /// Returns raw bits when type is unknown.
+    pub fn timestamp(self) -> core::result::Result<Timestamp, u32> {
+        Timestamp::try_from(self.bits & Self::TimestampMask.bits)
     }
 
     /// Get source timestamp type
-    pub fn timestamp_src(self) -> TimestampSrc {
-        unsafe { core::mem::transmute(self.bits & Self::TimestampSrcMask.bits) }
+    ///
+    /// Returns raw bits when type is unknown.
+    pub fn timestamp_src(self) -> core::result::Result<TimestampSrc, u32> {
+        TimestampSrc::try_from(self.bits & Self::TimestampSrcMask.bits)
     }
 }
 
@@ -0,0 +1,509 @@
+use super::*;
+use getset::CopyGetters;
+
+enum_impl! {
+    /// Pixel encoding
+    enum PixelEncoding {
+        /// R'G'B' components
+        Rgb,
+        /// Y'CbCr components (including luma-only formats)
+        Yuv,
+        /// Raw sensor data with bayer color filter array
+        Bayer,
+        /// HSV components
+        Hsv,
+        /// Compressed bitstream
+        Compressed,
+        /// Non-color data (depth, radio, touch, metadata)
+        Other,
+    }
+
+    /// Pixel component
+    enum Component {
+        /// Red
+        Red,
+        /// Green
+        Green,
+        /// Blue
+        Blue,
+        /// Alpha
+        Alpha,
+        /// Padding (unused bits)
+        Padding,
+        /// Luma
+        Luma,
+        /// Blue-difference chroma
+        Cb,
+        /// Red-difference chroma
+        Cr,
+        /// Hue
+        Hue,
+        /// Saturation
+        Saturation,
+        /// Value
+        Value,
+        /// Depth
+        Depth,
+    }
+}
+
+/// Pixel format layout information
+///
+/// This is a counterpart of kernel's `struct v4l2_format_info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct FormatInfo {
+    /// Pixel format
+    #[getset(get_copy = "pub")]
+    fourcc: FourCc,
+
+    /// Pixel encoding
+    #[getset(get_copy = "pub")]
+    encoding: PixelEncoding,
+
+    /// Number of memory planes
+    #[getset(get_copy = "pub")]
+    mem_planes: u8,
+
+    /// Number of color planes
+    #[getset(get_copy = "pub")]
+    comp_planes: u8,
+
+    bpp: [u8; 4],
+
+    /// Significant bits of single component sample
+    #[getset(get_copy = "pub")]
+    depth: u8,
+
+    /// Horizontal chroma subsampling factor
+    #[getset(get_copy = "pub")]
+    hsub: u8,
+
+    /// Vertical chroma subsampling factor
+    #[getset(get_copy = "pub")]
+    vsub: u8,
+
+    /// Number of pixels in packing block of first plane
+    #[getset(get_copy = "pub")]
+    block_width: u8,
+
+    /// Number of bytes in packing block of first plane
+    #[getset(get_copy = "pub")]
+    block_bytes: u8,
+
+    /// Component order
+    ///
+    /// Components listed in memory order for byte-aligned formats, starting
+    /// from most significant bits for bit-packed formats, plane by plane for
+    /// planar formats and as first 2x2 block for bayer formats.
+    #[getset(get_copy = "pub")]
+    order: &'static [Component],
+}
+
+impl FormatInfo {
+    const fn new(
+        fourcc: FourCc,
+        encoding: PixelEncoding,
+        bpp: u8,
+        depth: u8,
+        order: &'static [Component],
+    ) -> Self {
+        Self {
+            fourcc,
+            encoding,
+            mem_planes: 1,
+            comp_planes: 1,
+            bpp: [bpp, 0, 0, 0],
+            depth,
+            hsub: 1,
+            vsub: 1,
+            block_width: 1,
+            block_bytes: 0,
+            order,
+        }
+    }
+
+    const fn planes(mut self, mem_planes: u8, bpp: &[u8]) -> Self {
+        self.mem_planes = mem_planes;
+        self.comp_planes = bpp.len() as _;
+        let mut plane = 0;
+        while plane < bpp.len() {
+            self.bpp[plane] = bpp[plane];
+            plane += 1;
+        }
+        self
+    }
+
+    const fn sub(mut self, hsub: u8, vsub: u8) -> Self {
+        self.hsub = hsub;
+        self.vsub = vsub;
+        self
+    }
+
+    const fn block(mut self, width: u8, bytes: u8) -> Self {
+        self.block_width = width;
+        self.block_bytes = bytes;
+        self
+    }
+
+    /// Bits per pixel of color plane
+    ///
+    /// For chroma planes of subsampled formats this is the number of bits
+    /// per subsampled pixel. Bit-packed formats report sample depth here,
+    /// the actual size is determined by packing block.
+    pub fn bpp(&self, plane: usize) -> u8 {
+        if plane < self.comp_planes as usize {
+            self.bpp[plane]
+        } else {
+            0
+        }
+    }
+
+    /// Format is compressed
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.encoding, PixelEncoding::Compressed)
+    }
+
+    /// Format is R'G'B'
+    pub fn is_rgb(&self) -> bool {
+        matches!(self.encoding, PixelEncoding::Rgb)
+    }
+
+    /// Format is Y'CbCr or luma-only
+    pub fn is_yuv(&self) -> bool {
+        matches!(self.encoding, PixelEncoding::Yuv)
+    }
+
+    /// Format is raw bayer
+    pub fn is_bayer(&self) -> bool {
+        matches!(self.encoding, PixelEncoding::Bayer)
+    }
+
+    /// Format is HSV
+    pub fn is_hsv(&self) -> bool {
+        matches!(self.encoding, PixelEncoding::Hsv)
+    }
+
+    /// Format has separate chroma planes
+    pub fn is_planar(&self) -> bool {
+        self.comp_planes > 1
+    }
+
+    /// Format has alpha channel
+    pub fn has_alpha(&self) -> bool {
+        self.order.contains(&Component::Alpha)
+    }
+
+    /// Width of color plane in pixels
+    pub fn plane_width(&self, plane: usize, width: u32) -> u32 {
+        if plane > 0 {
+            width.div_ceil(self.hsub as _)
+        } else {
+            width
+        }
+    }
+
+    /// Height of color plane in lines
+    pub fn plane_height(&self, plane: usize, height: u32) -> u32 {
+        if plane > 0 {
+            height.div_ceil(self.vsub as _)
+        } else {
+            height
+        }
+    }
+
+    /// Minimal number of bytes per line of color plane
+    pub fn min_bytes_per_line(&self, plane: usize, width: u32) -> u32 {
+        if plane == 0 && self.block_bytes > 0 {
+            width.div_ceil(self.block_width as _) * self.block_bytes as u32
+        } else {
+            (self.plane_width(plane, width) * self.bpp(plane) as u32).div_ceil(8)
+        }
+    }
+}
+
+impl core::fmt::Display for FormatInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fourcc.fmt(f)?;
+        ' '.fmt(f)?;
+        self.encoding.fmt(f)?;
+        if self.is_compressed() {
+            return Ok(());
+        }
+        " ".fmt(f)?;
+        for (index, component) in self.order.iter().enumerate() {
+            if index > 0 {
+                ','.fmt(f)?;
+            }
+            component.fmt(f)?;
+        }
+        " ".fmt(f)?;
+        for plane in 0..self.comp_planes as usize {
+            if plane > 0 {
+                '/'.fmt(f)?;
+            }
+            self.bpp[plane].fmt(f)?;
+        }
+        "bpp #".fmt(f)?;
+        self.mem_planes.fmt(f)?;
+        if self.hsub > 1 || self.vsub > 1 {
+            " sub:".fmt(f)?;
+            self.hsub.fmt(f)?;
+            'x'.fmt(f)?;
+            self.vsub.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FourCc {
+    /// Get pixel format layout information
+    ///
+    /// Returns [None] for vendor-specific and tiled formats which layout
+    /// cannot be described in generic way.
+    pub fn info(self) -> Option<FormatInfo> {
+        use Component::*;
+        use PixelEncoding::*;
+
+        const XRGB: &[Component] = &[Padding, Red, Green, Blue];
+        const ARGB: &[Component] = &[Alpha, Red, Green, Blue];
+        const RGBX: &[Component] = &[Red, Green, Blue, Padding];
+        const RGBA: &[Component] = &[Red, Green, Blue, Alpha];
+        const XBGR: &[Component] = &[Padding, Blue, Green, Red];
+        const ABGR: &[Component] = &[Alpha, Blue, Green, Red];
+        const BGRX: &[Component] = &[Blue, Green, Red, Padding];
+        const BGRA: &[Component] = &[Blue, Green, Red, Alpha];
+        const RGB: &[Component] = &[Red, Green, Blue];
+        const BGR: &[Component] = &[Blue, Green, Red];
+        const Y: &[Component] = &[Luma];
+        const YUV: &[Component] = &[Luma, Cb, Cr];
+        const YVU: &[Component] = &[Luma, Cr, Cb];
+        const XYUV: &[Component] = &[Padding, Luma, Cb, Cr];
+        const AYUV: &[Component] = &[Alpha, Luma, Cb, Cr];
+        const BGGR: &[Component] = &[Blue, Green, Green, Red];
+        const GBRG: &[Component] = &[Green, Blue, Red, Green];
+        const GRBG: &[Component] = &[Green, Red, Blue, Green];
+        const RGGB: &[Component] = &[Red, Green, Green, Blue];
+
+        let f = self;
+
+        Some(match self {
+            /* RGB formats (1 or 2 bytes per pixel) */
+            Self::Rgb332 => FormatInfo::new(f, Rgb, 8, 3, RGB),
+            Self::Rgb444 | Self::Xrgb444 => FormatInfo::new(f, Rgb, 16, 4, XRGB),
+            Self::Argb444 => FormatInfo::new(f, Rgb, 16, 4, ARGB),
+            Self::Rgba444 => FormatInfo::new(f, Rgb, 16, 4, RGBA),
+            Self::Rgbx444 => FormatInfo::new(f, Rgb, 16, 4, RGBX),
+            Self::Abgr444 => FormatInfo::new(f, Rgb, 16, 4, ABGR),
+            Self::Xbgr444 => FormatInfo::new(f, Rgb, 16, 4, XBGR),
+            Self::Bgra444 => FormatInfo::new(f, Rgb, 16, 4, BGRA),
+            Self::Bgrx444 => FormatInfo::new(f, Rgb, 16, 4, BGRX),
+            Self::Rgb555 | Self::Xrgb555 | Self::Rgb555x | Self::Xrgb555x => {
+                FormatInfo::new(f, Rgb, 16, 5, XRGB)
+            }
+            Self::Argb555 | Self::Argb555x => FormatInfo::new(f, Rgb, 16, 5, ARGB),
+            Self::Rgba555 => FormatInfo::new(f, Rgb, 16, 5, RGBA),
+            Self::Rgbx555 => FormatInfo::new(f, Rgb, 16, 5, RGBX),
+            Self::Abgr555 => FormatInfo::new(f, Rgb, 16, 5, ABGR),
+            Self::Xbgr555 => FormatInfo::new(f, Rgb, 16, 5, XBGR),
+            Self::Bgra555 => FormatInfo::new(f, Rgb, 16, 5, BGRA),
+            Self::Bgrx555 => FormatInfo::new(f, Rgb, 16, 5, BGRX),
+            Self::Rgb565 | Self::Rgb565x => FormatInfo::new(f, Rgb, 16, 6, RGB),
+
+            /* RGB formats (3 or 4 bytes per pixel) */
+            Self::Bgr666 => FormatInfo::new(f, Rgb, 32, 6, BGRX),
+            Self::Bgr24 => FormatInfo::new(f, Rgb, 24, 8, BGR),
+            Self::Rgb24 => FormatInfo::new(f, Rgb, 24, 8, RGB),
+            Self::Bgr32 | Self::Xbgr32 => FormatInfo::new(f, Rgb, 32, 8, BGRX),
+            Self::Abgr32 => FormatInfo::new(f, Rgb, 32, 8, BGRA),
+            Self::Bgra32 => FormatInfo::new(f, Rgb, 32, 8, ABGR),
+            Self::Bgrx32 => FormatInfo::new(f, Rgb, 32, 8, XBGR),
+            Self::Rgb32 | Self::Xrgb32 => FormatInfo::new(f, Rgb, 32, 8, XRGB),
+            Self::Rgba32 => FormatInfo::new(f, Rgb, 32, 8, RGBA),
+            Self::Rgbx32 => FormatInfo::new(f, Rgb, 32, 8, RGBX),
+            Self::Argb32 => FormatInfo::new(f, Rgb, 32, 8, ARGB),
+
+            /* Grey formats */
+            Self::Grey => FormatInfo::new(f, Yuv, 8, 8, Y),
+            Self::Y4 => FormatInfo::new(f, Yuv, 8, 4, Y),
+            Self::Y6 => FormatInfo::new(f, Yuv, 8, 6, Y),
+            Self::Y10 => FormatInfo::new(f, Yuv, 16, 10, Y),
+            Self::Y12 => FormatInfo::new(f, Yuv, 16, 12, Y),
+            Self::Y14 => FormatInfo::new(f, Yuv, 16, 14, Y),
+            Self::Y16 | Self::Y16be => FormatInfo::new(f, Yuv, 16, 16, Y),
+
+            /* Grey bit-packed formats */
+            Self::Y10bpack | Self::Y10p => FormatInfo::new(f, Yuv, 10, 10, Y).block(4, 5),
+            Self::Ipu3Y10 => FormatInfo::new(f, Yuv, 10, 10, Y).block(25, 32),
+
+            /* Luminance+Chrominance formats */
+            Self::Yuyv => FormatInfo::new(f, Yuv, 16, 8, &[Luma, Cb, Luma, Cr]).sub(2, 1),
+            Self::Yyuv => FormatInfo::new(f, Yuv, 16, 8, &[Luma, Luma, Cb, Cr]).sub(2, 1),
+            Self::Yvyu => FormatInfo::new(f, Yuv, 16, 8, &[Luma, Cr, Luma, Cb]).sub(2, 1),
+            Self::Uyvy => FormatInfo::new(f, Yuv, 16, 8, &[Cb, Luma, Cr, Luma]).sub(2, 1),
+            Self::Vyuy => FormatInfo::new(f, Yuv, 16, 8, &[Cr, Luma, Cb, Luma]).sub(2, 1),
+            Self::Y41p => FormatInfo::new(
+                f,
+                Yuv,
+                12,
+                8,
+                &[
+                    Cb, Luma, Cr, Luma, Cb, Luma, Cr, Luma, Luma, Luma, Luma, Luma,
+                ],
+            )
+            .sub(4, 1)
+            .block(8, 12),
+            Self::Yuv444 => FormatInfo::new(f, Yuv, 16, 4, XYUV),
+            Self::Yuv555 => FormatInfo::new(f, Yuv, 16, 5, XYUV),
+            Self::Yuv565 => FormatInfo::new(f, Yuv, 16, 6, YUV),
+            Self::Yuv24 => FormatInfo::new(f, Yuv, 24, 8, YUV),
+            Self::Yuv32 | Self::Ayuv32 => FormatInfo::new(f, Yuv, 32, 8, AYUV),
+            Self::Xyuv32 => FormatInfo::new(f, Yuv, 32, 8, XYUV),
+            Self::Vuya32 => FormatInfo::new(f, Yuv, 32, 8, &[Cr, Cb, Luma, Alpha]),
+            Self::Vuyx32 => FormatInfo::new(f, Yuv, 32, 8, &[Cr, Cb, Luma, Padding]),
+
+            /* two planes -- one Y, one Cr + Cb interleaved  */
+            Self::Nv12 => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(1, &[8, 16])
+                .sub(2, 2),
+            Self::Nv21 => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(1, &[8, 16])
+                .sub(2, 2),
+            Self::Nv16 => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(1, &[8, 16])
+                .sub(2, 1),
+            Self::Nv61 => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(1, &[8, 16])
+                .sub(2, 1),
+            Self::Nv24 => FormatInfo::new(f, Yuv, 8, 8, YUV).planes(1, &[8, 16]),
+            Self::Nv42 => FormatInfo::new(f, Yuv, 8, 8, YVU).planes(1, &[8, 16]),
+
+            /* two non contiguous planes - one Y, one Cr + Cb interleaved  */
+            Self::Nv12m => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(2, &[8, 16])
+                .sub(2, 2),
+            Self::Nv21m => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(2, &[8, 16])
+                .sub(2, 2),
+            Self::Nv16m => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(2, &[8, 16])
+                .sub(2, 1),
+            Self::Nv61m => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(2, &[8, 16])
+                .sub(2, 1),
+
+            /* three planes - Y Cb, Cr */
+            Self::Yuv410 => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(1, &[8, 8, 8])
+                .sub(4, 4),
+            Self::Yvu410 => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(1, &[8, 8, 8])
+                .sub(4, 4),
+            Self::Yuv411p => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(1, &[8, 8, 8])
+                .sub(4, 1),
+            Self::Yuv420 => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(1, &[8, 8, 8])
+                .sub(2, 2),
+            Self::Yvu420 => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(1, &[8, 8, 8])
+                .sub(2, 2),
+            Self::Yuv422p => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(1, &[8, 8, 8])
+                .sub(2, 1),
+
+            /* three non contiguous planes - Y, Cb, Cr */
+            Self::Yuv420m => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(3, &[8, 8, 8])
+                .sub(2, 2),
+            Self::Yvu420m => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(3, &[8, 8, 8])
+                .sub(2, 2),
+            Self::Yuv422m => FormatInfo::new(f, Yuv, 8, 8, YUV)
+                .planes(3, &[8, 8, 8])
+                .sub(2, 1),
+            Self::Yvu422m => FormatInfo::new(f, Yuv, 8, 8, YVU)
+                .planes(3, &[8, 8, 8])
+                .sub(2, 1),
+            Self::Yuv444m => FormatInfo::new(f, Yuv, 8, 8, YUV).planes(3, &[8, 8, 8]),
+            Self::Yvu444m => FormatInfo::new(f, Yuv, 8, 8, YVU).planes(3, &[8, 8, 8]),
+
+            /* Bayer formats */
+            Self::Sbggr8 => FormatInfo::new(f, Bayer, 8, 8, BGGR),
+            Self::Sgbrg8 => FormatInfo::new(f, Bayer, 8, 8, GBRG),
+            Self::Sgrbg8 => FormatInfo::new(f, Bayer, 8, 8, GRBG),
+            Self::Srggb8 => FormatInfo::new(f, Bayer, 8, 8, RGGB),
+            Self::Sbggr10 => FormatInfo::new(f, Bayer, 16, 10, BGGR),
+            Self::Sgbrg10 => FormatInfo::new(f, Bayer, 16, 10, GBRG),
+            Self::Sgrbg10 => FormatInfo::new(f, Bayer, 16, 10, GRBG),
+            Self::Srggb10 => FormatInfo::new(f, Bayer, 16, 10, RGGB),
+            Self::Sbggr10p => FormatInfo::new(f, Bayer, 10, 10, BGGR).block(4, 5),
+            Self::Sgbrg10p => FormatInfo::new(f, Bayer, 10, 10, GBRG).block(4, 5),
+            Self::Sgrbg10p => FormatInfo::new(f, Bayer, 10, 10, GRBG).block(4, 5),
+            Self::Srggb10p => FormatInfo::new(f, Bayer, 10, 10, RGGB).block(4, 5),
+            Self::Sbggr10alaw8 | Self::Sbggr10dpcm8 => FormatInfo::new(f, Bayer, 8, 10, BGGR),
+            Self::Sgbrg10alaw8 | Self::Sgbrg10dpcm8 => FormatInfo::new(f, Bayer, 8, 10, GBRG),
+            Self::Sgrbg10alaw8 | Self::Sgrbg10dpcm8 => FormatInfo::new(f, Bayer, 8, 10, GRBG),
+            Self::Srggb10alaw8 | Self::Srggb10dpcm8 => FormatInfo::new(f, Bayer, 8, 10, RGGB),
+            Self::Sbggr12 => FormatInfo::new(f, Bayer, 16, 12, BGGR),
+            Self::Sgbrg12 => FormatInfo::new(f, Bayer, 16, 12, GBRG),
+            Self::Sgrbg12 => FormatInfo::new(f, Bayer, 16, 12, GRBG),
+            Self::Srggb12 => FormatInfo::new(f, Bayer, 16, 12, RGGB),
+            Self::Sbggr12p => FormatInfo::new(f, Bayer, 12, 12, BGGR).block(2, 3),
+            Self::Sgbrg12p => FormatInfo::new(f, Bayer, 12, 12, GBRG).block(2, 3),
+            Self::Sgrbg12p => FormatInfo::new(f, Bayer, 12, 12, GRBG).block(2, 3),
+            Self::Srggb12p => FormatInfo::new(f, Bayer, 12, 12, RGGB).block(2, 3),
+            Self::Sbggr14 => FormatInfo::new(f, Bayer, 16, 14, BGGR),
+            Self::Sgbrg14 => FormatInfo::new(f, Bayer, 16, 14, GBRG),
+            Self::Sgrbg14 => FormatInfo::new(f, Bayer, 16, 14, GRBG),
+            Self::Srggb14 => FormatInfo::new(f, Bayer, 16, 14, RGGB),
+            Self::Sbggr14p => FormatInfo::new(f, Bayer, 14, 14, BGGR).block(4, 7),
+            Self::Sgbrg14p => FormatInfo::new(f, Bayer, 14, 14, GBRG).block(4, 7),
+            Self::Sgrbg14p => FormatInfo::new(f, Bayer, 14, 14, GRBG).block(4, 7),
+            Self::Srggb14p => FormatInfo::new(f, Bayer, 14, 14, RGGB).block(4, 7),
+            Self::Sbggr16 => FormatInfo::new(f, Bayer, 16, 16, BGGR),
+            Self::Sgbrg16 => FormatInfo::new(f, Bayer, 16, 16, GBRG),
+            Self::Sgrbg16 => FormatInfo::new(f, Bayer, 16, 16, GRBG),
+            Self::Srggb16 => FormatInfo::new(f, Bayer, 16, 16, RGGB),
+            Self::Ipu3Sbggr10 => FormatInfo::new(f, Bayer, 10, 10, BGGR).block(25, 32),
+            Self::Ipu3Sgbrg10 => FormatInfo::new(f, Bayer, 10, 10, GBRG).block(25, 32),
+            Self::Ipu3Sgrbg10 => FormatInfo::new(f, Bayer, 10, 10, GRBG).block(25, 32),
+            Self::Ipu3Srggb10 => FormatInfo::new(f, Bayer, 10, 10, RGGB).block(25, 32),
+
+            /* HSV formats */
+            Self::Hsv24 => FormatInfo::new(f, Hsv, 24, 8, &[Hue, Saturation, Value]),
+            Self::Hsv32 => FormatInfo::new(f, Hsv, 32, 8, &[Padding, Hue, Saturation, Value]),
+
+            /* Depth formats */
+            Self::Z16 => FormatInfo::new(f, Other, 16, 16, &[Depth]),
+
+            /* compressed formats */
+            Self::Mjpeg
+            | Self::Jpeg
+            | Self::Dv
+            | Self::Mpeg
+            | Self::H264
+            | Self::H264NoSc
+            | Self::H264Mvc
+            | Self::H263
+            | Self::Mpeg1
+            | Self::Mpeg2
+            | Self::Mpeg2Slice
+            | Self::Mpeg4
+            | Self::Xvid
+            | Self::Vc1AnnexG
+            | Self::Vc1AnnexL
+            | Self::Vp8
+            | Self::Vp8Frame
+            | Self::Vp9
+            | Self::Vp9Frame
+            | Self::Hevc
+            | Self::Fwht
+            | Self::FwhtStateless
+            | Self::H264Slice
+            | Self::Pjpg
+            | Self::Jpgl => FormatInfo::new(f, Compressed, 0, 0, &[]),
+
+            _ => return None,
+        })
+    }
+}
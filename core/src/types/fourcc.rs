@@ -1,16 +1,32 @@
 macro_rules! fourcc_impl {
     ($($(#[$($meta:meta)*])* $name:ident = $a:literal $b:literal $c:literal $d:literal $($opt:ident)*,)*) => {
-        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-        #[repr(u32)]
-        pub enum FourCc {
-            $($(#[$($meta)*])* $name = fourcc_impl!(@$($opt)*: $a $b $c $d),)*
-        }
-
+        /// Four character code of pixel format
+        ///
+        /// This is an open type: drivers may report codes which unknown for this crate,
+        /// so any code can be represented. Known codes are available as associated constants.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct FourCc(u32);
+
+        #[allow(non_upper_case_globals)]
         impl FourCc {
-            /// Slice of all variants
+            $(
+                $(#[$($meta)*])*
+                pub const $name: Self = Self(fourcc_impl!(@$($opt)*: $a $b $c $d));
+            )*
+
+            /// Slice of all known codes
             pub const ALL: &[Self] = &[
                 $(Self::$name,)*
             ];
+
+            /// Get name of known code
+            pub fn name(self) -> Option<&'static str> {
+                Some(match self {
+                    $(Self::$name => stringify!($name),)*
+                    _ => return None,
+                })
+            }
         }
     };
 
@@ -19,7 +35,7 @@ macro_rules! fourcc_impl {
     };
 
     (@be: $a:literal $b:literal $c:literal $d:literal) => {
-        fourcc_impl!(@: $a $b $c $d) | (1 << 31)
+        fourcc_impl!(@: $a $b $c $d) | FourCc::BIG_ENDIAN
     };
 }
 
@@ -559,7 +575,36 @@ impl FourCc {
     pub fn is_ycbcr(self) -> bool {
         matches!(
             self,
-            Self::Nv12
+            Self::Uv8
+                | Self::Yuyv
+                | Self::Yyuv
+                | Self::Yvyu
+                | Self::Uyvy
+                | Self::Vyuy
+                | Self::Y41p
+                | Self::Yuv444
+                | Self::Yuv555
+                | Self::Yuv565
+                | Self::Yuv24
+                | Self::Yuv32
+                | Self::Ayuv32
+                | Self::Xyuv32
+                | Self::Vuya32
+                | Self::Vuyx32
+                | Self::M420
+                | Self::Yuv410
+                | Self::Yvu410
+                | Self::Yuv411p
+                | Self::Yuv420
+                | Self::Yvu420
+                | Self::Yuv422p
+                | Self::Yuv420m
+                | Self::Yvu420m
+                | Self::Yuv422m
+                | Self::Yvu422m
+                | Self::Yuv444m
+                | Self::Yvu444m
+                | Self::Nv12
                 | Self::Nv21
                 | Self::Nv16
                 | Self::Nv61
@@ -584,51 +629,80 @@ impl FourCc {
     }
 }
 
-impl core::convert::TryFrom<u32> for FourCc {
-    type Error = u32;
+impl FourCc {
+    /// Flag which marks big-endian variant of format
+    pub const BIG_ENDIAN: u32 = 1 << 31;
 
-    fn try_from(fourcc: u32) -> core::result::Result<Self, Self::Error> {
-        for variant in Self::ALL {
-            if *variant as u32 == fourcc {
-                return Ok(*variant);
-            }
+    /// Create from numeric code
+    pub const fn new(code: u32) -> Self {
+        Self(code)
+    }
+
+    /// Create from characters
+    pub const fn from_bytes(bytes: [u8; 4]) -> Self {
+        Self(u32::from_le_bytes(bytes))
+    }
+
+    /// Get numeric code
+    pub const fn code(self) -> u32 {
+        self.0
+    }
+
+    /// Get characters of code (without big-endian flag)
+    pub const fn bytes(self) -> [u8; 4] {
+        (self.0 & !Self::BIG_ENDIAN).to_le_bytes()
+    }
+
+    /// Code has big-endian flag
+    pub const fn is_big_endian(self) -> bool {
+        self.0 & Self::BIG_ENDIAN != 0
+    }
+
+    /// Set or reset big-endian flag
+    pub const fn with_big_endian(self, big_endian: bool) -> Self {
+        if big_endian {
+            Self(self.0 | Self::BIG_ENDIAN)
+        } else {
+            Self(self.0 & !Self::BIG_ENDIAN)
         }
-        Err(fourcc)
+    }
+
+    /// Code is known for this crate
+    pub fn is_known(self) -> bool {
+        self.name().is_some()
     }
 }
 
-impl<'a> core::convert::TryFrom<&'a [u8; 4]> for FourCc {
-    type Error = &'a [u8; 4];
+impl From<u32> for FourCc {
+    fn from(code: u32) -> Self {
+        Self(code)
+    }
+}
 
-    fn try_from(fourcc: &'a [u8; 4]) -> core::result::Result<Self, Self::Error> {
-        ((fourcc[0] as u32)
-            | ((fourcc[1] as u32) << 8)
-            | ((fourcc[2] as u32) << 16)
-            | ((fourcc[3] as u32) << 24))
-            .try_into()
-            .map_err(|_| fourcc)
+impl From<FourCc> for u32 {
+    fn from(fourcc: FourCc) -> Self {
+        fourcc.0
     }
 }
 
-impl<'a> core::convert::TryFrom<&'a [u8]> for FourCc {
-    type Error = &'a [u8];
+impl From<[u8; 4]> for FourCc {
+    fn from(bytes: [u8; 4]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
 
-    fn try_from(fourcc: &'a [u8]) -> core::result::Result<Self, Self::Error> {
-        let array: &[u8; 4] = fourcc.try_into().map_err(|_| fourcc)?;
-        array.try_into().map_err(|_| fourcc)
+impl From<&[u8; 4]> for FourCc {
+    fn from(bytes: &[u8; 4]) -> Self {
+        Self::from_bytes(*bytes)
     }
 }
 
-impl core::convert::TryFrom<[u8; 4]> for FourCc {
-    type Error = [u8; 4];
+impl<'a> core::convert::TryFrom<&'a [u8]> for FourCc {
+    type Error = &'a [u8];
 
-    fn try_from(fourcc: [u8; 4]) -> core::result::Result<Self, Self::Error> {
-        ((fourcc[0] as u32)
-            | ((fourcc[1] as u32) << 8)
-            | ((fourcc[2] as u32) << 16)
-            | ((fourcc[3] as u32) << 24))
-            .try_into()
-            .map_err(|_| fourcc)
+    fn try_from(fourcc: &'a [u8]) -> core::result::Result<Self, Self::Error> {
+        let array: &[u8; 4] = fourcc.try_into().map_err(|_| fourcc)?;
+        Ok(array.into())
     }
 }
 
@@ -636,21 +710,40 @@ impl<'a> core::convert::TryFrom<&'a str> for FourCc {
     type Error = &'a str;
 
     fn try_from(fourcc: &'a str) -> core::result::Result<Self, Self::Error> {
-        fourcc.as_bytes().try_into().map_err(|_| fourcc)
+        fourcc.parse().map_err(|_| fourcc)
     }
 }
 
 impl core::str::FromStr for FourCc {
-    type Err = ();
+    type Err = crate::Error;
 
+    /// Parse from up to four characters
+    ///
+    /// Short codes padded by spaces, the `-BE` suffix sets big-endian flag.
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        src.try_into().map_err(|_| ())
+        let (src, big_endian) = if let Some(src) = src.strip_suffix("-BE") {
+            (src, true)
+        } else {
+            (src, false)
+        };
+
+        if src.is_empty()
+            || src.len() > 4
+            || !src.bytes().all(|c| c.is_ascii_graphic() || c == b' ')
+        {
+            return Err(crate::utils::invalid_input("Invalid four character code"));
+        }
+
+        let mut bytes = [b' '; 4];
+        bytes[..src.len()].copy_from_slice(src.as_bytes());
+
+        Ok(Self::from_bytes(bytes).with_big_endian(big_endian))
     }
 }
 
 impl AsRef<u32> for FourCc {
     fn as_ref(&self) -> &u32 {
-        unsafe { &*(self as *const Self as *const u32) }
+        &self.0
     }
 }
 
@@ -666,14 +759,30 @@ impl AsRef<[u8]> for FourCc {
     }
 }
 
-impl AsRef<str> for FourCc {
-    fn as_ref(&self) -> &str {
-        unsafe { core::str::from_utf8_unchecked(self.as_ref()) }
+impl core::fmt::Display for FourCc {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        for c in self.bytes() {
+            f.write_char(if c.is_ascii_graphic() || c == b' ' {
+                c as char
+            } else {
+                '.'
+            })?;
+        }
+        if self.is_big_endian() {
+            f.write_str("-BE")?;
+        }
+        Ok(())
     }
 }
 
-impl core::fmt::Display for FourCc {
+impl core::fmt::Debug for FourCc {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        f.write_str(self.as_ref())
+        if let Some(name) = self.name() {
+            f.write_str(name)
+        } else {
+            write!(f, "FourCc(\"{self}\")")
+        }
     }
 }
@@ -532,8 +532,7 @@ pub struct PixFormat {
     /// field, or both fields interlaced or sequentially stored in one buffer
     /// or alternating in separate buffers. Drivers return the actual field
     /// order selected. For more details on fields see Field Order.
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) field: Field,
+    pub(crate) field: u32,
 
     /// Distance in bytes between the leftmost pixels in two adjacent lines.
     ///
@@ -586,8 +585,7 @@ pub struct PixFormat {
     /// return another supported colorspace. The driver indicates that colorspace
     /// conversion is supported by setting the flag [FmtFlag::CscColorSpace] in the
     /// corresponding struct [FmtDesc] during enumeration. See [FmtFlag].
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) color_space: ColorSpace,
+    pub(crate) color_space: u32,
 
     /// This field indicates whether the remaining fields of the struct [PixFormat],
     /// also called the extended fields, are valid. When set to V4L2_PIX_FMT_PRIV_MAGIC,
@@ -615,7 +613,8 @@ pub struct PixFormat {
     #[getset(get_copy = "pub", set = "pub")]
     pub(crate) flags: PixFmtFlag,
 
-    pub(crate) union_: PixFormatUnion,
+    /// Y’CbCr or HSV encoding depending on pixel format
+    pub(crate) enc: u32,
 
     /// Quantization range, from enum [Quantization].
     ///
@@ -627,8 +626,7 @@ pub struct PixFormat {
     /// return another supported quantization. The driver indicates that quantization
     /// conversion is supported by setting the flag [FmtFlag::CscQuantization] in the
     /// corresponding struct [FmtDesc] during enumeration. See [FmtFlag].
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) quantization: Quantization,
+    pub(crate) quantization: u32,
 
     /// Transfer function, from enum [XferFunc].
     ///
@@ -640,29 +638,32 @@ pub struct PixFormat {
     /// it will return another supported transfer function. The driver indicates that
     /// [XferFunc] conversion is supported by setting the flag [FmtFlag::CscXferFunc]
     /// in the corresponding struct [FmtDesc] during enumeration. See [FmtFlag].
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) xfer_func: XferFunc,
+    pub(crate) xfer_func: u32,
 }
 
 impl From<FourCc> for PixFormat {
     fn from(pixel_format: FourCc) -> Self {
         let color_space = ColorSpace::from(pixel_format);
+        let enc = if pixel_format.is_hsv() {
+            HsvEncoding::E256 as u8
+        } else {
+            YcbcrEncoding::from(color_space) as u8
+        };
+        let quantization =
+            Quantization::new_default(pixel_format.is_rgb() || pixel_format.is_hsv(), color_space);
         PixFormat {
             width: 0,
             height: 0,
             pixel_format,
-            field: Field::None,
+            field: Field::None as _,
             bytes_per_line: 0,
             size_image: 0,
-            color_space,
+            color_space: color_space as _,
             priv_: 0,
             flags: PixFmtFlag::none(),
-            union_: PixFormatUnion::new_default(pixel_format, color_space),
-            quantization: Quantization::new_default(
-                pixel_format.is_rgb() || pixel_format.is_hsv(),
-                color_space,
-            ),
-            xfer_func: color_space.into(),
+            enc: enc as _,
+            quantization: quantization as _,
+            xfer_func: XferFunc::from(color_space) as _,
         }
     }
 }
@@ -679,9 +680,11 @@ impl PixFormat {
     /// formats. The driver indicates that [YcbcrEncoding] conversion is supported by
     /// setting the flag [FmtFlag::CscYcbcrEnc] in the corresponding struct [FmtDesc]
     /// during enumeration. See [FmtFlag].
-    pub fn ycbcr_enc(&self) -> Option<YcbcrEncoding> {
+    ///
+    /// Returns raw value when encoding is unknown.
+    pub fn ycbcr_enc(&self) -> Option<core::result::Result<YcbcrEncoding, u32>> {
         if self.pixel_format.is_ycbcr() {
-            Some(unsafe { self.union_.ycbcr_enc })
+            Some(try_from_u8(self.enc))
         } else {
             None
         }
@@ -690,7 +693,7 @@ impl PixFormat {
     /// Set Y’CbCr encoding, from enum [YcbcrEncoding]
     pub fn set_ycbcr_enc(&mut self, ycbcr_enc: YcbcrEncoding) {
         if self.pixel_format.is_ycbcr() {
-            self.union_.ycbcr_enc = ycbcr_enc;
+            self.enc = ycbcr_enc as _;
         }
     }
 
@@ -705,9 +708,11 @@ impl PixFormat {
     /// formats. The driver indicates that hsv_enc conversion is supported by setting
     /// the flag [FmtFlag::CscYcbcrEnc] in the corresponding struct [FmtDesc] during
     /// enumeration. See [FmtFlag].
-    pub fn hsv_enc(&self) -> Option<HsvEncoding> {
+    ///
+    /// Returns raw value when encoding is unknown.
+    pub fn hsv_enc(&self) -> Option<core::result::Result<HsvEncoding, u32>> {
         if self.pixel_format.is_hsv() {
-            Some(unsafe { self.union_.hsv_enc })
+            Some(try_from_u8(self.enc))
         } else {
             None
         }
@@ -716,30 +721,17 @@ impl PixFormat {
     /// Set HSV encoding, from enum [HsvEncoding]
     pub fn set_hsv_enc(&mut self, hsv_enc: HsvEncoding) {
         if self.pixel_format.is_hsv() {
-            self.union_.hsv_enc = hsv_enc;
+            self.enc = hsv_enc as _;
         }
     }
 }
 
-#[repr(C)]
-#[derive(Copy, Clone)]
-pub(crate) union PixFormatUnion {
-    pub(crate) ycbcr_enc: YcbcrEncoding,
-    pub(crate) hsv_enc: HsvEncoding,
-}
-
-impl PixFormatUnion {
-    fn new_default(pixfmt: FourCc, colorsp: ColorSpace) -> Self {
-        if pixfmt.is_hsv() {
-            Self {
-                hsv_enc: HsvEncoding::E256,
-            }
-        } else {
-            Self {
-                ycbcr_enc: colorsp.into(),
-            }
-        }
-    }
+/// Convert raw value of field into enum with byte representation
+pub(crate) fn try_from_u8<T: TryFrom<u8, Error = u8>>(raw: u32) -> core::result::Result<T, u32> {
+    u8::try_from(raw)
+        .ok()
+        .and_then(|raw| T::try_from(raw).ok())
+        .ok_or(raw)
 }
 
 /// Format description
@@ -816,8 +808,7 @@ pub struct FrmSizeEnum {
     pub(crate) pixel_format: FourCc,
 
     /// Frame size type
-    #[getset(get_copy = "pub")]
-    pub(crate) type_: FrmSizeType,
+    pub(crate) type_: u32,
 
     pub(crate) union_: FrmSizeEnumUnion,
 
@@ -872,8 +863,7 @@ pub struct FrmIvalEnum {
     pub(crate) height: u32,
 
     /// Frame interval type
-    #[getset(get_copy = "pub")]
-    pub(crate) type_: FrmIvalType,
+    pub(crate) type_: u32,
 
     pub(crate) union_: FrmIvalEnumUnion,
 
@@ -891,9 +881,7 @@ pub(crate) union FrmIvalEnumUnion {
 #[repr(C)]
 #[derive(Debug, Copy, Clone, CopyGetters, Setters)]
 pub struct TimeCode {
-    /// Type of time code
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) type_: TimeCodeType,
+    pub(crate) type_: u32,
 
     /// Time code flags
     #[getset(get_copy = "pub", set = "pub")]
@@ -978,9 +966,7 @@ pub struct Buffer {
     #[getset(get_copy = "pub")]
     pub(crate) flags: BufferFlag,
 
-    /// Buffer field
-    #[getset(get_copy = "pub")]
-    pub(crate) field: Field,
+    pub(crate) field: u32,
 
     /// Buffer time
     pub(crate) timestamp: TimeVal,
@@ -1332,9 +1318,7 @@ pub struct QueryCtrl {
     #[getset(get_copy = "pub")]
     pub(crate) id: u32,
 
-    /// Control type
-    #[getset(get_copy = "pub")]
-    pub(crate) type_: CtrlType,
+    pub(crate) type_: u32,
 
     pub(crate) name: [u8; 32],
 
@@ -1374,9 +1358,7 @@ pub struct QueryExtCtrl {
     #[getset(get_copy = "pub")]
     pub(crate) id: u32,
 
-    /// Control type
-    #[getset(get_copy = "pub")]
-    pub(crate) type_: CtrlType,
+    pub(crate) type_: u32,
 
     pub(crate) name: [u8; 32],
 
@@ -1748,12 +1730,10 @@ pub struct PixFormatMplane {
     pub(crate) pixel_format: FourCc,
 
     /// Format field
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) field: Field,
+    pub(crate) field: u32,
 
     /// Color space
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) color_space: ColorSpace,
+    pub(crate) color_space: u32,
 
     pub(crate) plane_fmt: [PlanePixFormat; VIDEO_MAX_PLANES],
 
@@ -1763,15 +1743,14 @@ pub struct PixFormatMplane {
     #[getset(get_copy = "pub", set = "pub")]
     pub(crate) flags: PixFmtFlag,
 
-    pub(crate) union_: PixFormatUnion,
+    /// Y’CbCr or HSV encoding depending on pixel format
+    pub(crate) enc: u8,
 
     /// Quantization
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) quantization: Quantization,
+    pub(crate) quantization: u8,
 
     /// Transfer function
-    #[getset(get_copy = "pub", set = "pub")]
-    pub(crate) xfer_func: XferFunc,
+    pub(crate) xfer_func: u8,
 
     pub(crate) reserved: [u8; 7],
 }
@@ -1848,14 +1827,14 @@ pub(crate) union StreamParmUnion {
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone)]
 pub struct EventVsync {
-    pub(crate) field: Field,
+    pub(crate) field: u8,
 }
 
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct EventCtrl {
     pub(crate) changes: EventCtrlChangeFlag,
-    pub(crate) type_: EventType,
+    pub(crate) type_: u32,
     pub(crate) union_: EventCtrlUnion,
     pub(crate) flags: CtrlFlag,
     pub(crate) minimum: i32,
@@ -1894,9 +1873,7 @@ pub struct EventMotionDet {
 #[repr(C)]
 #[derive(Copy, Clone, CopyGetters)]
 pub struct Event {
-    /// Event type
-    #[getset(get_copy = "pub")]
-    pub(crate) type_: EventType,
+    pub(crate) type_: u32,
     pub(crate) u: EventUnion,
     pub(crate) pending: u32,
     pub(crate) sequence: u32,
@@ -1971,3 +1948,12 @@ pub struct CreateBuffers {
     pub(crate) flags: BufferFlag,
     pub(crate) reserved: [u32; 6],
 }
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct RemoveBuffers {
+    pub(crate) index: u32,
+    pub(crate) count: u32,
+    pub(crate) type_: BufferType,
+    pub(crate) reserved: [u32; 13],
+}
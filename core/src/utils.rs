@@ -13,6 +13,11 @@ pub fn invalid_data(msg: &'static str) -> Error {
     Error::new(io::ErrorKind::InvalidData, msg)
 }
 
+#[inline(always)]
+pub fn resource_busy(msg: &'static str) -> Error {
+    Error::new(io::ErrorKind::ResourceBusy, msg)
+}
+
 #[inline(always)]
 fn is_null(src: &u8) -> bool {
     *src == 0
@@ -4,12 +4,17 @@
 
 pub use linux_video_core as types;
 use linux_video_core::private::*;
+pub use types::error::Error;
 use types::*;
 
 use std::{
     fs::File,
-    os::unix::io::{AsRawFd, RawFd},
+    io::{Read, Write},
+    ops::Range,
+    os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
 /// Video device
@@ -23,12 +28,67 @@ impl AsRawFd for Device {
     }
 }
 
+impl AsFd for Device {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
+
+/// Use already opened device file
+///
+/// I.e. the file of mock device for testing.
+impl From<File> for Device {
+    fn from(file: File) -> Self {
+        Device { file }
+    }
+}
+
+/// Device events readiness
+///
+/// Register with [mio::Interest::PRIORITY] to get notified about subscribed events.
+#[cfg(feature = "mio")]
+impl mio::event::Source for Device {
+    fn register(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &mio::Registry) -> Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
 impl Device {
     /// List video devices
     pub fn list() -> Result<Devices> {
         Devices::new()
     }
 
+    /// Discover video devices grouped by physical devices
+    ///
+    /// Information is collected from sysfs.
+    pub fn discover() -> Result<Vec<PhysicalDevice>> {
+        discover_devices()
+    }
+
+    /// Watch video devices adding and removing
+    pub fn monitor() -> Result<DeviceMonitor> {
+        DeviceMonitor::new()
+    }
+
     /// Open video device
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
         let file = open(path, false)?;
@@ -36,11 +96,38 @@ impl Device {
         Ok(Device { file })
     }
 
+    /// Open video device in non-blocking mode
+    ///
+    /// Intended for use with external event loops.
+    /// Streams created from such device never block on dequeueing buffers.
+    pub fn open_nonblocking(path: impl AsRef<Path>) -> Result<Self> {
+        let file = open(path, true)?;
+
+        Ok(Device { file })
+    }
+
     /// Get capabilities
     pub fn capabilities(&self) -> Result<Capability> {
         Internal::<Capability>::query(self.as_raw_fd()).map(Internal::into_inner)
     }
 
+    /// Get highest priority of device handles
+    pub fn priority(&self) -> Result<Priority> {
+        Internal::<Priority>::query(self.as_raw_fd()).map(Internal::into_inner)
+    }
+
+    /// Claim access priority
+    ///
+    /// Other handles cannot change settings while this one holds higher priority,
+    /// such attempts fails with [types::error::ErrorKind::Busy].
+    pub fn claim_priority(&self, priority: Priority) -> Result<PriorityGuard<'_>> {
+        Internal::from(priority).set(self.as_raw_fd())?;
+        Ok(PriorityGuard {
+            device: self,
+            priority,
+        })
+    }
+
     /// Get controls
     pub fn controls(&self, class: Option<CtrlClass>) -> Controls<'_> {
         let last_id = class.map(|c| c as _).unwrap_or_default();
@@ -64,7 +151,7 @@ impl Device {
         if control.is_menu() {
             Some(MenuItems {
                 device: self,
-                ctrl_type: control.type_(),
+                ctrl_type: control.type_().ok()?,
                 ctrl_id: control.id(),
                 index_iter: control.min() as _..=control.max() as _,
             })
@@ -159,6 +246,37 @@ impl Device {
     ) -> Result<Stream<Dir, Met>> {
         Stream::new(self.file.try_clone()?, type_, count)
     }
+
+    /// Create stream to read data using read I/O method
+    pub fn read_stream(&self, type_: ContentType) -> Result<ReadStream> {
+        ReadStream::new(self.file.try_clone()?, type_)
+    }
+
+    /// Create stream to write data using write I/O method
+    pub fn write_stream(&self, type_: ContentType) -> Result<WriteStream> {
+        WriteStream::new(self.file.try_clone()?, type_)
+    }
+}
+
+/// Claimed access priority
+///
+/// The priority is dropped back to default on release.
+pub struct PriorityGuard<'a> {
+    device: &'a Device,
+    priority: Priority,
+}
+
+impl PriorityGuard<'_> {
+    /// Get claimed priority
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+}
+
+impl Drop for PriorityGuard<'_> {
+    fn drop(&mut self) {
+        let _ = Internal::from(Priority::default()).set(self.device.as_raw_fd());
+    }
 }
 
 /// The interface to get available devices
@@ -191,6 +309,87 @@ impl Devices {
     }
 }
 
+/// The interface to watch devices adding and removing
+pub struct DeviceMonitor {
+    watcher: DeviceWatcher,
+    cancel: CancelHandle,
+}
+
+impl AsRawFd for DeviceMonitor {
+    fn as_raw_fd(&self) -> RawFd {
+        self.watcher.as_raw_fd()
+    }
+}
+
+impl DeviceMonitor {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            watcher: DeviceWatcher::new()?,
+            cancel: CancelHandle {
+                event: Arc::new(event_fd()?),
+            },
+        })
+    }
+
+    /// Get next device event
+    ///
+    /// Waits until device is added or removed or waiting is cancelled.
+    pub fn next_event(&mut self) -> Result<DeviceEvent> {
+        self.wait_next(None)
+    }
+
+    /// Get next device event with timeout
+    ///
+    /// Fails with [std::io::ErrorKind::TimedOut] when no devices is changed in time.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Result<DeviceEvent> {
+        self.wait_next(Some(timeout))
+    }
+
+    /// Try get next device event without waiting
+    pub fn try_next(&mut self) -> Result<Option<DeviceEvent>> {
+        match self.watcher.read() {
+            Ok(event) => Ok(Some(event)),
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Get handle to cancel waiting from other thread
+    ///
+    /// Cancelled monitor fails with [ErrorKind::Cancelled](types::error::ErrorKind::Cancelled).
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    fn wait_next(&mut self, timeout: Option<Duration>) -> Result<DeviceEvent> {
+        let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+
+        loop {
+            if let Some(event) = self.try_next()? {
+                return Ok(event);
+            }
+
+            let timeout = deadline
+                .map(|deadline| deadline.saturating_duration_since(std::time::Instant::now()));
+
+            wait_ready(
+                self.watcher.as_raw_fd(),
+                false,
+                self.cancel.event.as_raw_fd(),
+                timeout,
+            )?;
+        }
+    }
+}
+
+impl Iterator for DeviceMonitor {
+    type Item = Result<DeviceEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_event())
+    }
+}
+
 /// The interface to get device controls
 pub struct Controls<'i> {
     device: &'i Device,
@@ -360,10 +559,38 @@ impl<'i> FrmIvals<'i> {
     }
 }
 
+/// Handle to cancel waiting for stream data
+///
+/// Cancelled stream fails with [ErrorKind::Cancelled](types::error::ErrorKind::Cancelled)
+/// until it will be restarted.
+#[derive(Clone)]
+pub struct CancelHandle {
+    event: Arc<File>,
+}
+
+impl CancelHandle {
+    /// Wake up waiting stream and cancel further waiting
+    pub fn cancel(&self) -> Result<()> {
+        (&*self.event).write_all(&1u64.to_ne_bytes())
+    }
+
+    /// Reset cancellation
+    fn reset(&self) -> Result<()> {
+        let mut counter = [0u8; 8];
+        match (&*self.event).read(&mut counter) {
+            Err(error) if error.kind() != std::io::ErrorKind::WouldBlock => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Data I/O queue
 pub struct Stream<Dir, Met: Method> {
     file: File,
+    format: Format,
     queue: Internal<QueueData<Dir, Met>>,
+    hold_policy: HoldPolicy,
+    cancel: CancelHandle,
 }
 
 impl<Dir, Met: Method> Drop for Stream<Dir, Met> {
@@ -372,16 +599,727 @@ impl<Dir, Met: Method> Drop for Stream<Dir, Met> {
     }
 }
 
+impl<Dir, Met: Method> AsRawFd for Stream<Dir, Met> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+impl<Dir, Met: Method> AsFd for Stream<Dir, Met> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.file.as_fd()
+    }
+}
+
+/// Stream data readiness
+///
+/// Use [Stream::interest] to get interest which corresponds to stream direction.
+/// Capture stream should be started before waiting for readiness.
+#[cfg(feature = "mio")]
+impl<Dir, Met: Method> mio::event::Source for Stream<Dir, Met> {
+    fn register(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &mio::Registry) -> Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
 impl<Dir: Direction, Met: Method> Stream<Dir, Met> {
     fn new(file: File, type_: ContentType, count: usize) -> Result<Self> {
+        let mut format = Format::from(Dir::buffer_type(type_));
+        Internal::from(&mut format).get(file.as_raw_fd())?;
+
         let queue = Internal::<QueueData<Dir, Met>>::new(file.as_raw_fd(), type_, count as _)?;
 
-        Ok(Self { file, queue })
+        Ok(Self {
+            file,
+            format,
+            queue,
+            hold_policy: HoldPolicy::default(),
+            cancel: CancelHandle {
+                event: Arc::new(event_fd()?),
+            },
+        })
+    }
+
+    /// Get format of stream data
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Get behavior when all buffers is held by other threads
+    pub fn hold_policy(&self) -> HoldPolicy {
+        self.hold_policy
+    }
+
+    /// Set behavior when all buffers is held by other threads
+    pub fn set_hold_policy(&mut self, policy: HoldPolicy) {
+        self.hold_policy = policy;
+    }
+
+    /// Get number of buffers
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check that stream has no buffers
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Add buffers to stream
+    ///
+    /// Buffers size is calculated according to format which may differ from current
+    /// (i.e. to allocate larger buffers ahead of resolution switch).
+    /// Returns indexes of added buffers.
+    pub fn grow(&mut self, count: usize, format: &Format) -> Result<Range<u32>> {
+        self.queue.create(self.file.as_raw_fd(), count as _, format)
+    }
+
+    /// Remove buffers from stream
+    ///
+    /// Buffers should not be queued or held by user so stream usually should be stopped.
+    /// Requires kernel support of `VIDIOC_REMOVE_BUFS` (Linux 6.10).
+    pub fn remove_buffers(&mut self, indexes: Range<u32>) -> Result<()> {
+        self.queue.remove(self.file.as_raw_fd(), indexes)
+    }
+
+    /// Prepare capture buffers to cut queueing latency
+    ///
+    /// Returns number of prepared buffers.
+    pub fn prepare_buffers(&self) -> Result<usize> {
+        self.queue.prepare_buffers(self.file.as_raw_fd())
+    }
+
+    /// Start collecting frames statistics
+    ///
+    /// Nominal frame interval is taken from stream parameters when available.
+    /// Collected statistics will be reset.
+    pub fn enable_stats(&self) {
+        let mut param = StreamParm::from(self.format.type_());
+        let time_per_frame = Internal::from(&mut param)
+            .get(self.file.as_raw_fd())
+            .ok()
+            .and_then(|_| param.time_per_frame());
+        self.queue.enable_stats(time_per_frame);
+    }
+
+    /// Stop collecting frames statistics
+    pub fn disable_stats(&self) {
+        self.queue.disable_stats();
+    }
+
+    /// Get collected frames statistics
+    ///
+    /// Returns [None] when statistics is not enabled.
+    pub fn stats(&self) -> Option<StreamStats> {
+        self.queue.stats()
+    }
+
+    /// Get next frame with image format to write or read
+    ///
+    /// Only video streams with single memory plane is supported.
+    pub fn next_frame(&self) -> Result<FrameRef<Dir, Met>> {
+        let format = FrameFormat::new(&self.format)?;
+        Ok(FrameRef::new(self.next()?, format))
+    }
+
+    /// Get handle to cancel waiting from other thread
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Check that stream is started
+    pub fn is_started(&self) -> bool {
+        self.queue.is_on()
+    }
+
+    /// Start stream
+    ///
+    /// Stream also starts implicitly on getting next buffer.
+    /// Starting resets cancellation.
+    pub fn start(&self) -> Result<()> {
+        self.cancel.reset()?;
+        self.queue.start(self.file.as_raw_fd())
+    }
+
+    /// Stop stream
+    ///
+    /// All buffers which is not held by user will be dequeued.
+    pub fn stop(&self) -> Result<()> {
+        self.queue.stop(self.file.as_raw_fd())
+    }
+
+    /// Stop and start stream again
+    pub fn restart(&self) -> Result<()> {
+        self.stop()?;
+        self.start()
     }
 
     /// Get next frame to write or read
+    ///
+    /// Waits until buffer is available or waiting is cancelled.
     pub fn next(&self) -> Result<BufferRef<Dir, Met>> {
-        self.queue.next(self.file.as_raw_fd())
+        self.wait_next(None)
+    }
+
+    /// Get next frame to write or read with timeout
+    ///
+    /// Fails with [std::io::ErrorKind::TimedOut] when no buffer is available in time.
+    pub fn next_timeout(&self, timeout: Duration) -> Result<BufferRef<Dir, Met>> {
+        self.wait_next(Some(timeout))
+    }
+
+    /// Try get next frame to write or read without waiting
+    ///
+    /// Returns [None] when no buffer is available yet.
+    /// Stream starts implicitly like on [Stream::next].
+    pub fn try_next(&self) -> Result<Option<BufferRef<Dir, Met>>> {
+        let fd = self.file.as_raw_fd();
+
+        if let Some(buffer) = self.queue.prepare(fd)? {
+            return Ok(Some(buffer));
+        }
+
+        match wait_ready(
+            fd,
+            Dir::OUT,
+            self.cancel.event.as_raw_fd(),
+            Some(Duration::ZERO),
+        ) {
+            Err(error) if error.kind() == std::io::ErrorKind::TimedOut => return Ok(None),
+            result => result?,
+        }
+
+        match self.queue.next(fd) {
+            Ok(buffer) => Ok(Some(buffer)),
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Get interest to wait for stream readiness
+    ///
+    /// Capture streams is readable and output streams is writable.
+    #[cfg(feature = "mio")]
+    pub fn interest(&self) -> mio::Interest {
+        if Dir::OUT {
+            mio::Interest::WRITABLE
+        } else {
+            mio::Interest::READABLE
+        }
+    }
+
+    fn wait_next(&self, timeout: Option<Duration>) -> Result<BufferRef<Dir, Met>> {
+        let fd = self.file.as_raw_fd();
+
+        if let Some(buffer) = self.queue.prepare(fd)? {
+            return Ok(buffer);
+        }
+
+        wait_ready(fd, Dir::OUT, self.cancel.event.as_raw_fd(), timeout)?;
+
+        self.queue.next(fd)
+    }
+
+    /// Get next buffer which can be sent to other thread
+    ///
+    /// The buffer will be enqueued again after dropping.
+    /// Waiting for released buffer is cancelled by [CancelHandle].
+    pub fn next_shared(&self) -> Result<SharedBuffer<Dir, Met>> {
+        while !self.queue.reserve(self.hold_policy)? {
+            wait_readable(
+                self.queue.returns().as_raw_fd(),
+                Some(self.cancel.event.as_raw_fd()),
+                None,
+            )?;
+        }
+        Ok(self.queue.lend(self.next()?))
+    }
+}
+
+type EventHandler = Box<dyn FnMut(&ConnectionEvent) + Send>;
+
+/// Capture stream which reopens device after disconnection
+///
+/// Device is found again by [DeviceIdentity] and format, stream parameters
+/// and control values which was set through this wrapper is re-applied.
+pub struct ResilientCapture<Met: Method> {
+    // stream should be dropped before device
+    stream: Option<Stream<In, Met>>,
+    device: Option<Device>,
+    identity: DeviceIdentity,
+    type_: ContentType,
+    count: usize,
+    policy: ReconnectPolicy,
+    format: Format,
+    param: Option<StreamParm>,
+    controls: Vec<ValueData>,
+    handler: Option<EventHandler>,
+}
+
+impl<Met: Method> ResilientCapture<Met> {
+    /// Open device and create capture stream
+    ///
+    /// Current format and stream parameters of device will be remembered.
+    pub fn open(path: impl AsRef<Path>, type_: ContentType, count: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let identity = identify_device(path)?;
+        let device = Device::open(path)?;
+
+        let buffer_type = In::buffer_type(type_);
+        let format = device.format(buffer_type)?;
+        let param = device.param(buffer_type).ok();
+
+        let stream = device.stream(type_, count)?;
+
+        Ok(Self {
+            stream: Some(stream),
+            device: Some(device),
+            identity,
+            type_,
+            count,
+            policy: ReconnectPolicy::default(),
+            format,
+            param,
+            controls: Vec::new(),
+            handler: None,
+        })
+    }
+
+    /// Get identity of device
+    pub fn identity(&self) -> &DeviceIdentity {
+        &self.identity
+    }
+
+    /// Get reconnection behavior
+    pub fn policy(&self) -> ReconnectPolicy {
+        self.policy
+    }
+
+    /// Set reconnection behavior
+    pub fn set_policy(&mut self, policy: ReconnectPolicy) {
+        self.policy = policy;
+    }
+
+    /// Set handler of connection events
+    pub fn on_event(&mut self, handler: impl FnMut(&ConnectionEvent) + Send + 'static) {
+        self.handler = Some(Box::new(handler));
+    }
+
+    /// Check that device is connected
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Get device when connected
+    pub fn device(&self) -> Option<&Device> {
+        self.device.as_ref()
+    }
+
+    /// Get stream when connected
+    pub fn stream(&self) -> Option<&Stream<In, Met>> {
+        self.stream.as_ref()
+    }
+
+    /// Get remembered format
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Set format and remember it
+    ///
+    /// Stream will be recreated to apply format.
+    pub fn set_format(&mut self, fmt: &mut Format) -> Result<()> {
+        if let Some(device) = &self.device {
+            self.stream = None;
+            device.set_format(fmt)?;
+            self.stream = Some(device.stream(self.type_, self.count)?);
+        }
+        self.format = *fmt;
+        Ok(())
+    }
+
+    /// Set stream parameters and remember it
+    pub fn set_param(&mut self, param: &mut StreamParm) -> Result<()> {
+        if let Some(device) = &self.device {
+            device.set_param(param)?;
+        }
+        self.param = Some(*param);
+        Ok(())
+    }
+
+    /// Set control value and remember it
+    pub fn set_control<T: SetValue>(&mut self, value: &T) -> Result<()> {
+        if let Some(device) = &self.device {
+            device.set_control(value)?;
+        }
+        let data = value.copy_data();
+        let ids: Vec<u32> = data.ids().collect();
+        self.controls
+            .retain(|value| !value.ids().all(|id| ids.contains(&id)));
+        self.controls.push(data);
+        Ok(())
+    }
+
+    /// Get next frame
+    ///
+    /// Device is reopened when it is disconnected or stalled.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<BufferRef<In, Met>> {
+        loop {
+            if self.stream.is_none() {
+                self.connect()?;
+            }
+
+            let stream = self.stream.as_ref().unwrap();
+
+            let event = match stream.next_timeout(self.policy.stall_timeout) {
+                Err(error) if is_disconnected(&error) => ConnectionEvent::Disconnected,
+                Err(error) if error.kind() == std::io::ErrorKind::TimedOut => {
+                    ConnectionEvent::Stalled
+                }
+                result => return result,
+            };
+
+            self.disconnect();
+            self.emit(event);
+        }
+    }
+
+    /// Reopen device and apply configuration
+    pub fn reconnect(&mut self) -> Result<()> {
+        self.disconnect();
+        self.connect()
+    }
+
+    fn disconnect(&mut self) {
+        self.stream = None;
+        self.device = None;
+    }
+
+    fn connect(&mut self) -> Result<()> {
+        let started = std::time::Instant::now();
+
+        loop {
+            match self.try_connect() {
+                Ok(path) => {
+                    self.emit(ConnectionEvent::Reconnected(path));
+                    return Ok(());
+                }
+                Err(error)
+                    if is_disconnected(&error)
+                        || matches!(
+                            error.kind(),
+                            std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied
+                        ) => {}
+                Err(error) => return Err(error),
+            }
+
+            if let Some(max_wait) = self.policy.max_wait {
+                if started.elapsed() >= max_wait {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "Device not reappeared",
+                    ));
+                }
+            }
+
+            std::thread::sleep(self.policy.retry_interval);
+        }
+    }
+
+    fn try_connect(&mut self) -> Result<PathBuf> {
+        let path = find_device(&self.identity)?
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Device not found"))?;
+
+        let device = Device::open(&path)?;
+
+        device.set_format(&mut self.format.clone())?;
+        if let Some(mut param) = self.param {
+            device.set_param(&mut param)?;
+        }
+        for value in &self.controls {
+            value.set(device.as_raw_fd())?;
+        }
+
+        self.stream = Some(device.stream(self.type_, self.count)?);
+        self.device = Some(device);
+
+        Ok(path)
+    }
+
+    fn emit(&mut self, event: ConnectionEvent) {
+        if let Some(handler) = &mut self.handler {
+            handler(&event);
+        }
+    }
+}
+
+/// Group of capture streams which frames is received together
+///
+/// Frames of streams is paired according to [SyncMode].
+/// Frames which have no pair is skipped and counted in [SyncStats].
+pub struct SyncGroup<Met: Method> {
+    streams: Vec<Stream<In, Met>>,
+    matcher: FrameMatcher,
+}
+
+impl<Met: Method> SyncGroup<Met> {
+    /// Create group of streams
+    ///
+    /// Streams is subscribed to frame sync events when [SyncMode::FrameSync] is used.
+    pub fn new(streams: Vec<Stream<In, Met>>, mode: SyncMode) -> Result<Self> {
+        if streams.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "No streams to synchronize",
+            ));
+        }
+
+        if mode == SyncMode::FrameSync {
+            let subscription =
+                EventSubscription::new(EventType::FrameSync, 0, EventSubFlag::none());
+            for stream in &streams {
+                Internal::from(&subscription).subscribe(stream.file.as_raw_fd())?;
+            }
+        }
+
+        let matcher = FrameMatcher::new(mode, streams.len());
+
+        Ok(Self { streams, matcher })
+    }
+
+    /// Get streams of group
+    pub fn streams(&self) -> &[Stream<In, Met>] {
+        &self.streams
+    }
+
+    /// Get frames pairing mode
+    pub fn mode(&self) -> SyncMode {
+        self.matcher.mode()
+    }
+
+    /// Get synchronization statistics
+    pub fn stats(&self) -> &SyncStats {
+        self.matcher.stats()
+    }
+
+    /// Start all streams
+    pub fn start(&mut self) -> Result<()> {
+        self.matcher.reset();
+        for stream in &self.streams {
+            stream.start()?;
+        }
+        Ok(())
+    }
+
+    /// Stop all streams
+    pub fn stop(&mut self) -> Result<()> {
+        for stream in &self.streams {
+            stream.stop()?;
+        }
+        Ok(())
+    }
+
+    /// Get next aligned frames
+    ///
+    /// Frames is ordered in the same way as streams.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Vec<BufferRef<In, Met>>> {
+        self.wait_next(None)
+    }
+
+    /// Get next aligned frames with timeout
+    ///
+    /// Timeout is applied when waiting for each frame.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Result<Vec<BufferRef<In, Met>>> {
+        self.wait_next(Some(timeout))
+    }
+
+    fn wait_next(&mut self, timeout: Option<Duration>) -> Result<Vec<BufferRef<In, Met>>> {
+        let mut frames: Vec<Option<BufferRef<In, Met>>> =
+            self.streams.iter().map(|_| None).collect();
+
+        loop {
+            for (index, stream) in self.streams.iter().enumerate() {
+                if frames[index].is_none() {
+                    let frame = stream.wait_next(timeout)?;
+                    self.matcher.receive(index, &frame.lock());
+                    frames[index] = Some(frame);
+                }
+            }
+
+            for (index, stream) in self.streams.iter().enumerate() {
+                let fd = stream.file.as_raw_fd();
+                while self.matcher.needs_event(index) {
+                    wait_event(fd, Some(stream.cancel.event.as_raw_fd()), timeout)?;
+                    self.matcher
+                        .event(index, &Internal::<Event>::dequeue(fd)?.into_inner());
+                }
+            }
+
+            let buffers: Vec<Buffer> = frames.iter().flatten().map(|frame| *frame.lock()).collect();
+
+            let stale = self.matcher.check(&buffers);
+
+            if stale.is_empty() {
+                return Ok(frames.into_iter().flatten().collect());
+            }
+
+            for index in stale {
+                frames[index] = None;
+            }
+        }
+    }
+
+    /// Take streams back
+    pub fn into_streams(self) -> Vec<Stream<In, Met>> {
+        self.streams
+    }
+}
+
+/// Data input using read I/O method
+///
+/// Each read operation does not cross frame boundary.
+pub struct ReadStream {
+    file: File,
+    format: Format,
+    frame: FrameBuf,
+}
+
+impl ReadStream {
+    fn new(file: File, type_: ContentType) -> Result<Self> {
+        let (format, size) = read_write_format::<In>(file.as_raw_fd(), type_)?;
+
+        Ok(Self {
+            file,
+            format,
+            frame: FrameBuf::new(size),
+        })
+    }
+
+    /// Get format of stream data
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Get maximum size of frame in bytes
+    pub fn frame_size(&self) -> usize {
+        self.frame.size()
+    }
+
+    /// Read next entire frame
+    ///
+    /// Pending data of previous frame will be discarded.
+    /// When output is less than frame the rest of frame will be discarded too.
+    pub fn read_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.frame.clear();
+        if buf.len() >= self.frame.size() {
+            (&self.file).read(buf)
+        } else {
+            let file = &self.file;
+            self.frame.fill(|data| (&*file).read(data))?;
+            let len = self.frame.take(buf);
+            self.frame.clear();
+            Ok(len)
+        }
+    }
+}
+
+impl Read for ReadStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.frame.is_empty() {
+            if buf.len() >= self.frame.size() {
+                return (&self.file).read(buf);
+            }
+            let file = &self.file;
+            self.frame.fill(|data| (&*file).read(data))?;
+        }
+        Ok(self.frame.take(buf))
+    }
+}
+
+/// Data output using write I/O method
+///
+/// Data is written by entire frames.
+/// Flushing of incomplete frame fails and such frame is discarded on dropping.
+pub struct WriteStream {
+    file: File,
+    format: Format,
+    frame: FrameBuf,
+}
+
+impl WriteStream {
+    fn new(file: File, type_: ContentType) -> Result<Self> {
+        let (format, size) = read_write_format::<Out>(file.as_raw_fd(), type_)?;
+
+        Ok(Self {
+            file,
+            format,
+            frame: FrameBuf::new(size),
+        })
+    }
+
+    /// Get format of stream data
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Get maximum size of frame in bytes
+    pub fn frame_size(&self) -> usize {
+        self.frame.size()
+    }
+
+    /// Write entire frame
+    ///
+    /// Complete frame written by parts will be written before.
+    /// Fails with [std::io::ErrorKind::InvalidInput] when previous frame is incomplete.
+    /// Data which exceeds frame size is ignored.
+    pub fn write_frame(&mut self, buf: &[u8]) -> Result<usize> {
+        self.flush()?;
+        let len = buf.len().min(self.frame.size());
+        (&self.file).write(&buf[..len])
+    }
+}
+
+impl Write for WriteStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let len = self.frame.put(buf);
+        if self.frame.is_full() {
+            self.flush()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let file = &self.file;
+        self.frame.flush(|data| (&*file).write(data))
+    }
+}
+
+impl Drop for WriteStream {
+    fn drop(&mut self) {
+        // Incomplete frame is discarded
+        if self.frame.is_full() {
+            let _ = self.flush();
+        }
     }
 }
 
@@ -0,0 +1,50 @@
+use linux_video::{types::*, Device};
+use std::time::Duration;
+
+#[test]
+fn remove_buffers_layout() {
+    // struct v4l2_remove_buffers
+    assert_eq!(core::mem::size_of::<RemoveBuffers>(), 64);
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn grow_while_streaming() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let mut stream = dev.stream::<In, Mmap>(ContentType::Video, 2).unwrap();
+    let count = stream.len();
+
+    stream.next_timeout(Duration::from_secs(1)).unwrap();
+
+    let format = *stream.format();
+    let indexes = stream.grow(2, &format).unwrap();
+    assert_eq!(indexes.len(), 2);
+    assert_eq!(stream.len(), count + 2);
+    assert_eq!(stream.prepare_buffers().unwrap(), 2);
+
+    for _ in 0..8 {
+        stream.next_timeout(Duration::from_secs(1)).unwrap();
+    }
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn remove_stopped() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let mut stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+    let format = *stream.format();
+    let indexes = stream.grow(2, &format).unwrap();
+
+    stream.next_timeout(Duration::from_secs(1)).unwrap();
+    stream.stop().unwrap();
+
+    stream.remove_buffers(indexes).unwrap();
+    assert_eq!(stream.len(), 4);
+
+    stream.restart().unwrap();
+    stream.next_timeout(Duration::from_secs(1)).unwrap();
+}
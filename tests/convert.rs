@@ -0,0 +1,90 @@
+use linux_video::types::{convert::*, *};
+
+fn pix_format(fourcc: FourCc, width: u32, height: u32, align: u32) -> PixFormat {
+    let mut fmt = Format::from(BufferType::VideoCapture);
+    let pixfmt = fmt.try_mut::<PixFormat>().unwrap();
+    PlaneLayout::compute(fourcc, width, height, align)
+        .unwrap()
+        .fill_pix_format(pixfmt);
+    *pixfmt
+}
+
+fn assert_near(a: &[u8], b: &[u8], tolerance: u8) {
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(b) {
+        assert!(a.abs_diff(*b) <= tolerance, "{a:?} != {b:?}");
+    }
+}
+
+#[test]
+fn supported_formats() {
+    assert!(is_supported(FourCc::Yuyv));
+    assert!(is_supported(FourCc::Nv12));
+    assert!(is_supported(FourCc::Rgb565));
+    assert!(!is_supported(FourCc::Mjpeg));
+}
+
+#[test]
+fn yuyv_to_rgb() {
+    let mut yuyv_fmt = pix_format(FourCc::Yuyv, 2, 1, 1);
+    yuyv_fmt.set_quantization(Quantization::LimRange);
+    yuyv_fmt.set_ycbcr_enc(YcbcrEncoding::E601);
+    let rgb_fmt = pix_format(FourCc::Rgb24, 2, 1, 1);
+
+    // BT.601 limited range red
+    let yuyv = [81, 90, 81, 240];
+    let mut rgb = [0; 6];
+    convert(yuyv, &yuyv_fmt, &mut rgb, &rgb_fmt).unwrap();
+    assert_near(&rgb, &[255, 0, 0, 255, 0, 0], 2);
+
+    // full range white
+    yuyv_fmt.set_quantization(Quantization::FullRange);
+    convert([255, 128, 255, 128], &yuyv_fmt, &mut rgb, &rgb_fmt).unwrap();
+    assert_eq!(rgb, [255; 6]);
+}
+
+#[test]
+fn rgb_round_trip() {
+    let rgb_fmt = pix_format(FourCc::Bgr24, 4, 4, 1);
+    let bgr: Vec<u8> = (0..4 * 4).flat_map(|_| [40, 120, 200]).collect();
+
+    for fourcc in [FourCc::Yuyv, FourCc::Uyvy, FourCc::Nv12, FourCc::Yvu420] {
+        let fmt = pix_format(fourcc, 4, 4, 1);
+        let mut yuv = vec![0; fmt.size_image() as usize];
+        convert(&bgr, &rgb_fmt, &mut yuv, &fmt).unwrap();
+
+        let mut out = vec![0; bgr.len()];
+        convert(&yuv, &fmt, &mut out, &rgb_fmt).unwrap();
+        assert_near(&out, &bgr, 3);
+    }
+}
+
+#[test]
+fn strided_input() {
+    let mut nv12_fmt = pix_format(FourCc::Nv12, 2, 2, 1);
+    nv12_fmt.set_bytes_per_line(8);
+    nv12_fmt.set_quantization(Quantization::FullRange);
+
+    #[rustfmt::skip]
+    let nv12 = [
+        10, 20, 0, 0, 0, 0, 0, 0,
+        30, 40, 0, 0, 0, 0, 0, 0,
+        128, 128, 0, 0, 0, 0, 0, 0,
+    ];
+
+    let mut grey_fmt = pix_format(FourCc::Grey, 2, 2, 1);
+    grey_fmt.set_quantization(Quantization::FullRange);
+    let mut grey = [0; 4];
+    convert(nv12, &nv12_fmt, &mut grey, &grey_fmt).unwrap();
+    assert_eq!(grey, [10, 20, 30, 40]);
+
+    let rgb_fmt = pix_format(FourCc::Rgb565, 2, 2, 1);
+    let mut rgb = [0; 8];
+    convert(nv12, &nv12_fmt, &mut rgb, &rgb_fmt).unwrap();
+    assert_eq!(
+        u16::from_le_bytes([rgb[6], rgb[7]]),
+        (5 << 11) | (10 << 5) | 5
+    );
+
+    assert!(convert([0; 4], &nv12_fmt, &mut grey, &grey_fmt).is_err());
+}
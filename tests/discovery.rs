@@ -0,0 +1,117 @@
+use linux_video::types::{
+    private::{group_device_infos, read_device_infos},
+    BufferType,
+};
+use std::{fs, os::unix::fs::symlink, path::Path};
+
+fn write(path: impl AsRef<Path>, data: &str) {
+    let path = path.as_ref();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, data).unwrap();
+}
+
+fn link(target: &str, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    symlink(target, path).unwrap();
+}
+
+#[test]
+fn discover_sysfs() {
+    let root = std::env::temp_dir().join(format!("linux-video-discovery-{}", std::process::id()));
+    let sys = root.join("sys");
+    let dev = root.join("dev");
+
+    let usb = sys.join("devices/pci0000:00/usb1/1-1");
+    write(usb.join("idVendor"), "046d\n");
+    write(usb.join("idProduct"), "085b\n");
+    write(usb.join("serial"), "ABC123\n");
+    write(usb.join("product"), "C925e\n");
+    link(
+        "../../../../bus/usb/drivers/uvcvideo",
+        usb.join("1-1:1.0/driver"),
+    );
+
+    for (node, index) in [("video0", "0"), ("video1", "1")] {
+        let node_dir = usb.join("1-1:1.0/video4linux").join(node);
+        write(node_dir.join("name"), "C925e\n");
+        write(node_dir.join("index"), index);
+        link("../..", node_dir.join("device"));
+        link(
+            &format!("../../devices/pci0000:00/usb1/1-1/1-1:1.0/video4linux/{node}"),
+            sys.join("class/video4linux").join(node),
+        );
+        write(dev.join(node), "");
+    }
+
+    let vivid = sys.join("devices/platform/vivid.0/video4linux/video10");
+    write(vivid.join("name"), "vivid-000-vid-cap\n");
+    write(vivid.join("index"), "0\n");
+    link("../..", vivid.join("device"));
+    link(
+        "../../devices/platform/vivid.0/video4linux/video10",
+        sys.join("class/video4linux/video10"),
+    );
+    write(dev.join("video10"), "");
+
+    link(
+        "../../video0",
+        dev.join("v4l/by-id/usb-046d_C925e_ABC123-video-index0"),
+    );
+
+    let infos = read_device_infos(&sys, &dev).unwrap();
+    assert_eq!(infos.len(), 3);
+
+    let info = &infos[0];
+    assert_eq!(info.path(), dev.join("video0"));
+    assert_eq!(info.name(), "C925e");
+    assert_eq!(info.index(), 0);
+    assert_eq!(info.driver(), Some("uvcvideo"));
+    assert_eq!(
+        info.by_id(),
+        [dev.join("v4l/by-id/usb-046d_C925e_ABC123-video-index0")]
+    );
+    assert!(info.by_path().is_empty());
+    assert!(info.capability().is_none());
+    assert!(!info.supports(BufferType::VideoCapture));
+
+    let usb_info = info.usb().unwrap();
+    assert_eq!(usb_info.vendor_id(), 0x046d);
+    assert_eq!(usb_info.product_id(), 0x085b);
+    assert_eq!(usb_info.serial(), Some("ABC123"));
+    assert_eq!(usb_info.product(), Some("C925e"));
+    assert_eq!(usb_info.manufacturer(), None);
+
+    assert_eq!(infos[2].path(), dev.join("video10"));
+    assert!(infos[2].usb().is_none());
+
+    let devices = group_device_infos(infos);
+    assert_eq!(devices.len(), 2);
+
+    assert_eq!(devices[0].serial(), Some("ABC123"));
+    assert_eq!(devices[0].nodes().len(), 2);
+    assert_eq!(devices[0].nodes()[1].index(), 1);
+
+    assert_eq!(devices[1].serial(), None);
+    assert_eq!(
+        devices[1].sys_path(),
+        fs::canonicalize(sys.join("devices/platform/vivid.0")).unwrap()
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn discover_vivid() {
+    let devices = linux_video::Device::discover().unwrap();
+
+    let node = devices
+        .iter()
+        .flat_map(|device| device.nodes())
+        .find(|node| node.path() == Path::new(env!("VIVID_WEBCAM")))
+        .unwrap();
+
+    assert_eq!(node.driver(), Some("vivid"));
+    assert!(node.supports(BufferType::VideoCapture));
+}
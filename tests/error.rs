@@ -0,0 +1,72 @@
+use linux_video::{
+    types::{
+        error::{ErrorKind, Subject},
+        mock::*,
+        *,
+    },
+    Device, Error,
+};
+
+fn open() -> (MockDevice, Device) {
+    let mock = MockDevice::new().unwrap();
+    let dev = Device::from(mock.open().unwrap());
+    (mock, dev)
+}
+
+#[test]
+fn error_unsupported_control() {
+    let (_mock, dev) = open();
+
+    let error = dev.control(CtrlId::Gamma).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+    let error = Error::from(error);
+    assert_eq!(error.kind(), ErrorKind::Unsupported);
+    assert_eq!(error.call_name(), Some("query_ext_ctrl"));
+    assert_eq!(error.subject(), Subject::Control(CtrlId::Gamma as u32));
+}
+
+#[test]
+fn error_busy_format() {
+    let (_mock, dev) = open();
+
+    let mut fmt = dev.format(BufferType::VideoCapture).unwrap();
+    let _stream = dev.stream::<In, Mmap>(ContentType::Video, 2).unwrap();
+
+    let error = dev.set_format(&mut fmt).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::ResourceBusy);
+
+    let error = Error::from(error);
+    assert_eq!(error.kind(), ErrorKind::Busy);
+    assert_eq!(error.call_name(), Some("s_fmt"));
+    assert_eq!(error.subject(), Subject::Format(BufferType::VideoCapture));
+
+    // converts back without losing details
+    let error = Error::from(std::io::Error::from(error));
+    assert_eq!(error.kind(), ErrorKind::Busy);
+    assert!(error
+        .to_string()
+        .starts_with("s_fmt (format of VideoCapture): device is busy"));
+}
+
+#[test]
+fn error_keeps_errno() {
+    let (_mock, dev) = open();
+
+    let error = dev.claim_priority(Priority::Unset).err().unwrap();
+    let errno = error.raw_os_error().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+    let error = Error::from(error);
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+    assert_eq!(error.errno().map(|errno| errno as i32), Some(errno));
+    assert_eq!(std::io::Error::from(error).raw_os_error(), Some(errno));
+}
+
+#[test]
+fn error_other_io() {
+    let error = Error::from(std::io::Error::from(std::io::ErrorKind::WouldBlock));
+    assert_eq!(error.kind(), ErrorKind::WouldBlock);
+    assert_eq!(error.call_name(), None);
+    assert_eq!(error.subject(), Subject::Device);
+}
@@ -0,0 +1,68 @@
+use linux_video::types::*;
+
+#[test]
+fn known_fourcc() {
+    let fourcc: FourCc = "YUYV".parse().unwrap();
+    assert_eq!(fourcc, FourCc::Yuyv);
+    assert_eq!(fourcc.name(), Some("Yuyv"));
+    assert_eq!(fourcc.to_string(), "YUYV");
+    assert_eq!(format!("{fourcc:?}"), "Yuyv");
+
+    let fourcc: FourCc = "Y10".parse().unwrap();
+    assert_eq!(fourcc, FourCc::Y10);
+    assert_eq!(fourcc.to_string(), "Y10 ");
+}
+
+#[test]
+fn unknown_fourcc() {
+    let fourcc = FourCc::from(b"ZZ42");
+    assert!(!fourcc.is_known());
+    assert_eq!(fourcc.to_string(), "ZZ42");
+    assert_eq!(format!("{fourcc:?}"), "FourCc(\"ZZ42\")");
+    assert_eq!(u32::from(fourcc), u32::from_le_bytes(*b"ZZ42"));
+
+    assert_eq!(FourCc::new(0x0102_4142).to_string(), "BA..");
+    assert!("TOOLONG".parse::<FourCc>().is_err());
+    assert!("".parse::<FourCc>().is_err());
+}
+
+#[test]
+fn big_endian_fourcc() {
+    assert!(FourCc::Argb555x.is_big_endian());
+    assert_eq!(FourCc::Argb555x.to_string(), "AR15-BE");
+    assert_eq!("AR15-BE".parse::<FourCc>().unwrap(), FourCc::Argb555x);
+    assert_eq!(FourCc::Argb555x.with_big_endian(false), FourCc::Argb555);
+}
+
+#[test]
+fn pix_format_fields() {
+    // same layout as kernel struct with 32-bit colorimetry fields
+    assert_eq!(core::mem::size_of::<PixFormat>(), 48);
+
+    let mut pixfmt = PixFormat::from(FourCc::Yuyv);
+    pixfmt
+        .set_field(Field::Interlaced)
+        .set_quantization(Quantization::LimRange)
+        .set_xfer_func(XferFunc::F709);
+    pixfmt.set_ycbcr_enc(YcbcrEncoding::E601);
+
+    assert_eq!(pixfmt.field(), Ok(Field::Interlaced));
+    assert_eq!(pixfmt.color_space(), Ok(ColorSpace::Rec709));
+    assert_eq!(pixfmt.quantization(), Ok(Quantization::LimRange));
+    assert_eq!(pixfmt.xfer_func(), Ok(XferFunc::F709));
+    assert_eq!(pixfmt.ycbcr_enc(), Some(Ok(YcbcrEncoding::E601)));
+    assert_eq!(pixfmt.hsv_enc(), None);
+}
+
+#[test]
+fn vbi_format_display() {
+    // formats is displayed by traced calls
+    for type_ in [
+        BufferType::VbiCapture,
+        BufferType::VbiOutput,
+        BufferType::SlicedVbiCapture,
+        BufferType::SlicedVbiOutput,
+    ] {
+        assert!(!Format::from(type_).to_string().is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+use linux_video::types::*;
+
+fn frame_format(fourcc: FourCc, width: u32, height: u32, align: u32) -> FrameFormat {
+    let mut fmt = Format::from(BufferType::VideoCapture);
+    let pixfmt = fmt.try_mut::<PixFormat>().unwrap();
+    PlaneLayout::compute(fourcc, width, height, align)
+        .unwrap()
+        .fill_pix_format(pixfmt);
+    FrameFormat::new(&fmt).unwrap()
+}
+
+#[test]
+fn owned_frame_is_send() {
+    fn is_send<T: Send>() {}
+    is_send::<OwnedFrame>();
+}
+
+#[test]
+fn packed_rows() {
+    // 3x2 RGB24 with lines padded to 16 bytes
+    let format = frame_format(FourCc::Rgb24, 3, 2, 16);
+    assert_eq!(format.num_planes(), 1);
+    assert_eq!(format.pix_format().bytes_per_line(), 16);
+
+    let data = (0..32).collect::<Vec<u8>>();
+    let frame = OwnedFrame::new(data, format);
+
+    let rows = frame.rows(0).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(
+        rows.collect::<Vec<_>>(),
+        [
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8][..],
+            &[16, 17, 18, 19, 20, 21, 22, 23, 24]
+        ]
+    );
+
+    assert_eq!(frame.pixel(0, 0), Some(&[0, 1, 2][..]));
+    assert_eq!(frame.pixel(2, 1), Some(&[22, 23, 24][..]));
+    assert_eq!(frame.pixel(3, 0), None);
+    assert!(frame.rows(1).is_none());
+}
+
+#[test]
+fn planar_rows() {
+    // 4x2 NV12: Y plane 4x2, interleaved CbCr plane 4x1
+    let format = frame_format(FourCc::Nv12, 4, 2, 1);
+    assert_eq!(format.num_planes(), 2);
+
+    let data = (0..12).collect::<Vec<u8>>();
+    let frame = OwnedFrame::new(data, format);
+
+    assert_eq!(frame.plane(0), Some(&[0, 1, 2, 3, 4, 5, 6, 7][..]));
+    assert_eq!(
+        frame.rows(1).unwrap().collect::<Vec<_>>(),
+        [&[8, 9, 10, 11][..]]
+    );
+    assert_eq!(frame.pixel(1, 1), Some(&[5][..]));
+}
+
+#[test]
+fn truncated_data() {
+    let format = frame_format(FourCc::Yuyv, 2, 2, 8);
+    // last line padding is missing
+    let frame = OwnedFrame::new(vec![0; 12], format);
+    assert_eq!(frame.rows(0).unwrap().count(), 2);
+
+    let frame = OwnedFrame::new(vec![0; 11], format);
+    assert!(frame.rows(0).is_none());
+}
+
+#[test]
+fn compressed_format() {
+    let format = FrameFormat::from(PixFormat::from(FourCc::Mjpeg));
+    assert!(format.layout().is_none());
+
+    let frame = OwnedFrame::new(vec![0xff, 0xd8], format);
+    assert!(frame.rows(0).is_none());
+    assert!(frame.pixel(0, 0).is_none());
+    assert_eq!(frame.data().len(), 2);
+}
+
+#[test]
+fn mplane_format() {
+    let mut fmt = Format::from(BufferType::VideoCaptureMplane);
+    let pixfmt = fmt.try_mut::<PixFormatMplane>().unwrap();
+    PlaneLayout::compute(FourCc::Nv12, 4, 2, 1)
+        .unwrap()
+        .fill_pix_format_mplane(pixfmt);
+    let format = FrameFormat::new(&fmt).unwrap();
+    assert_eq!(format.num_planes(), 2);
+    assert_eq!(format.pix_format().size_image(), 12);
+
+    // color planes in separate memory planes
+    let pixfmt = fmt.try_mut::<PixFormatMplane>().unwrap();
+    PlaneLayout::compute(FourCc::Nv12m, 4, 2, 1)
+        .unwrap()
+        .fill_pix_format_mplane(pixfmt);
+    let error = FrameFormat::new(&fmt).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
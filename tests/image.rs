@@ -0,0 +1,59 @@
+#![cfg(feature = "image")]
+
+use linux_video::types::{convert::*, *};
+
+fn pix_format(fourcc: FourCc, width: u32, height: u32, align: u32) -> PixFormat {
+    let mut pixfmt = PixFormat::from(fourcc);
+    PlaneLayout::compute(fourcc, width, height, align)
+        .unwrap()
+        .fill_pix_format(&mut pixfmt);
+    pixfmt
+}
+
+#[test]
+fn bgr_to_image() {
+    let fmt = pix_format(FourCc::Bgr24, 2, 1, 8);
+    let data = [1, 2, 3, 4, 5, 6, 0, 0];
+    let image = to_image(data, &fmt).unwrap().into_rgb8();
+    assert_eq!(image.dimensions(), (2, 1));
+    assert_eq!(image.as_raw(), &[3, 2, 1, 6, 5, 4]);
+}
+
+#[test]
+fn luma_to_image() {
+    let fmt = pix_format(FourCc::Grey, 3, 2, 4);
+    let data = [1, 2, 3, 0, 4, 5, 6, 0];
+    let image = to_image(data, &fmt).unwrap();
+    assert_eq!(image.as_luma8().unwrap().as_raw(), &[1, 2, 3, 4, 5, 6]);
+
+    let fmt = pix_format(FourCc::Y16be, 2, 1, 1);
+    let data = [0x12, 0x34, 0xab, 0xcd];
+    let image = to_image(data, &fmt).unwrap();
+    assert_eq!(image.as_luma16().unwrap().as_raw(), &[0x1234, 0xabcd]);
+}
+
+#[test]
+fn yuv_image_roundtrip() {
+    let mut image = image::RgbImage::new(4, 2);
+    for pixel in image.pixels_mut() {
+        *pixel = image::Rgb([200, 100, 50]);
+    }
+
+    let fmt = pix_format(FourCc::Yuyv, 4, 2, 1);
+    let mut data = vec![0; fmt.size_image() as usize];
+    from_rgb_image(&image, &mut data, &fmt).unwrap();
+
+    let frame = OwnedFrame::new(data, fmt.into());
+    let result = frame.to_image().unwrap().into_rgb8();
+    for (a, b) in result.as_raw().iter().zip(image.as_raw()) {
+        assert!(a.abs_diff(*b) <= 2, "{a} != {b}");
+    }
+}
+
+#[test]
+fn size_mismatch() {
+    let image = image::RgbImage::new(2, 2);
+    let fmt = pix_format(FourCc::Rgb24, 4, 2, 1);
+    let mut data = vec![0; fmt.size_image() as usize];
+    assert!(from_rgb_image(&image, &mut data, &fmt).is_err());
+}
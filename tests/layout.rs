@@ -0,0 +1,92 @@
+use linux_video::types::*;
+
+#[test]
+fn format_info() {
+    let info = FourCc::Yuyv.info().unwrap();
+    assert!(info.is_yuv());
+    assert_eq!(info.comp_planes(), 1);
+    assert_eq!(info.bpp(0), 16);
+    assert_eq!((info.hsub(), info.vsub()), (2, 1));
+
+    let info = FourCc::Nv12m.info().unwrap();
+    assert_eq!(info.mem_planes(), 2);
+    assert_eq!(info.comp_planes(), 2);
+
+    let info = FourCc::Srggb10p.info().unwrap();
+    assert!(info.is_bayer());
+    assert_eq!(info.depth(), 10);
+    assert_eq!(
+        info.order(),
+        &[
+            Component::Red,
+            Component::Green,
+            Component::Green,
+            Component::Blue
+        ]
+    );
+
+    assert!(FourCc::Mjpeg.info().unwrap().is_compressed());
+    assert!(FourCc::Konica420.info().is_none());
+}
+
+#[test]
+fn packed_layout() {
+    let layout = PlaneLayout::compute(FourCc::Rgb24, 640, 480, 1).unwrap();
+    assert_eq!(layout.bytes_per_line(0), 3 * 640);
+    assert_eq!(layout.size_image(), 3 * 640 * 480);
+
+    let layout = PlaneLayout::compute(FourCc::Yuyv, 641, 480, 64).unwrap();
+    assert_eq!(layout.bytes_per_line(0), 1344);
+
+    let layout = PlaneLayout::compute(FourCc::Srggb10p, 640, 480, 1).unwrap();
+    assert_eq!(layout.bytes_per_line(0), 800);
+
+    let layout = PlaneLayout::compute(FourCc::Ipu3Srggb10, 100, 4, 1).unwrap();
+    assert_eq!(layout.bytes_per_line(0), 128);
+
+    assert!(PlaneLayout::compute(FourCc::H264, 640, 480, 1).is_none());
+}
+
+#[test]
+fn planar_layout() {
+    let layout = PlaneLayout::compute(FourCc::Yuv420, 640, 480, 1).unwrap();
+    assert_eq!(layout.num_planes(), 3);
+    assert_eq!(layout.bytes_per_line(1), 320);
+    assert_eq!(layout.plane_offset(1), 640 * 480);
+    assert_eq!(layout.plane_offset(2), 640 * 480 + 320 * 240);
+    assert_eq!(layout.size_image(), 640 * 480 * 3 / 2);
+
+    let layout = PlaneLayout::compute(FourCc::Nv12, 641, 481, 1).unwrap();
+    assert_eq!(layout.bytes_per_line(0), 642);
+    assert_eq!(layout.bytes_per_line(1), 642);
+    assert_eq!(layout.plane_size(1), 642 * 241);
+
+    let layout = PlaneLayout::compute(FourCc::Yuv420m, 640, 480, 128).unwrap();
+    assert_eq!(layout.num_mem_planes(), 3);
+    assert_eq!(layout.bytes_per_line(0), 640);
+    assert_eq!(layout.bytes_per_line(2), 320);
+    assert_eq!(layout.plane_offset(2), 0);
+    assert_eq!(layout.mem_plane_size(1), 320 * 240);
+}
+
+#[test]
+fn fill_format() {
+    let layout = PlaneLayout::compute(FourCc::Nv12, 1280, 720, 64).unwrap();
+
+    let mut fmt = Format::from(BufferType::VideoOutput);
+    let pixfmt = fmt.try_mut::<PixFormat>().unwrap();
+    layout.fill_pix_format(pixfmt);
+    assert_eq!(pixfmt.pixel_format(), FourCc::Nv12);
+    assert_eq!(pixfmt.bytes_per_line(), 1280);
+    assert_eq!(pixfmt.size_image(), 1280 * 720 * 3 / 2);
+
+    let layout = PlaneLayout::compute(FourCc::Nv12m, 1280, 720, 64).unwrap();
+
+    let mut fmt = Format::from(BufferType::VideoOutputMplane);
+    let pixfmt = fmt.try_mut::<PixFormatMplane>().unwrap();
+    layout.fill_pix_format_mplane(pixfmt);
+    let planes = pixfmt.plane_fmt();
+    assert_eq!(planes.len(), 2);
+    assert_eq!(planes[1].bytes_per_line(), 1280);
+    assert_eq!(planes[1].size_image(), 1280 * 360);
+}
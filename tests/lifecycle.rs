@@ -0,0 +1,56 @@
+use linux_video::{
+    types::{error::ErrorKind, *},
+    CancelHandle, Device,
+};
+use std::{thread, time::Duration};
+
+#[test]
+fn cancel_handle_is_send() {
+    fn is_send_sync<T: Send + Sync>() {}
+    is_send_sync::<CancelHandle>();
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn start_stop_restart() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+    assert!(!stream.is_started());
+
+    stream.start().unwrap();
+    assert!(stream.is_started());
+    stream.next_timeout(Duration::from_secs(1)).unwrap();
+
+    stream.stop().unwrap();
+    assert!(!stream.is_started());
+
+    stream.restart().unwrap();
+    assert!(stream.is_started());
+    for _ in 0..5 {
+        stream.next_timeout(Duration::from_secs(1)).unwrap();
+    }
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn cancel_waiting() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+
+    let cancel = stream.cancel_handle();
+    cancel.cancel().unwrap();
+    let error = stream.next().err().unwrap();
+    assert_eq!(ErrorKind::of(&error), ErrorKind::Cancelled);
+
+    stream.restart().unwrap();
+    stream.next().unwrap();
+
+    let worker = thread::spawn(move || cancel.cancel().unwrap());
+    worker.join().unwrap();
+    let error = stream.next_timeout(Duration::from_secs(1)).err().unwrap();
+    assert_eq!(ErrorKind::of(&error), ErrorKind::Cancelled);
+}
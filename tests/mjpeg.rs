@@ -0,0 +1,94 @@
+use linux_video::types::mjpeg::*;
+
+fn encode_jpeg(width: u32, height: u32, quality: u8) -> Vec<u8> {
+    let pixels: Vec<u8> = (0..width * height)
+        .flat_map(|i| [(i % width * 8) as u8, 128, (i / width * 8) as u8])
+        .collect();
+    let mut data = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut data, quality)
+        .encode(&pixels, width, height, image::ColorType::Rgb8)
+        .unwrap();
+    data
+}
+
+/// Remove DHT segments like UVC cameras do
+fn strip_dht(data: &[u8]) -> Vec<u8> {
+    let mut out = data[..2].to_vec();
+    let mut offset = 2;
+    loop {
+        let marker = data[offset + 1];
+        let length = u16::from_be_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        if marker == 0xda {
+            out.extend_from_slice(&data[offset..]);
+            return out;
+        }
+        if marker != 0xc4 {
+            out.extend_from_slice(&data[offset..offset + 2 + length]);
+        }
+        offset += 2 + length;
+    }
+}
+
+#[test]
+fn frame_info() {
+    let data = encode_jpeg(32, 16, 75);
+    let info = FrameInfo::parse(&data).unwrap();
+    assert_eq!((info.width(), info.height()), (32, 16));
+    assert_eq!(info.components(), 3);
+    assert!(info.has_dht());
+    assert_eq!(info.size(), data.len());
+    assert!(info.quality().unwrap().abs_diff(75) <= 1);
+
+    let mut padded = data.clone();
+    padded.resize(data.len() + 100, 0);
+    assert_eq!(FrameInfo::parse(&padded).unwrap().size(), data.len());
+}
+
+#[test]
+fn truncated_frame() {
+    let data = encode_jpeg(32, 16, 90);
+    assert!(FrameInfo::parse(&data[..data.len() / 2]).is_err());
+    assert!(FrameInfo::parse(&data[2..]).is_err());
+    assert!(with_dht(&data[..data.len() - 1]).is_err());
+}
+
+#[test]
+fn missing_dht() {
+    let data = encode_jpeg(32, 16, 90);
+    let stripped = strip_dht(&data);
+    assert!(!FrameInfo::parse(&stripped).unwrap().has_dht());
+
+    let fixed = with_dht(&stripped).unwrap();
+    assert!(FrameInfo::parse(&fixed).unwrap().has_dht());
+
+    let image = image::load_from_memory(&fixed).unwrap();
+    assert_eq!((image.width(), image.height()), (32, 16));
+
+    assert!(matches!(
+        with_dht(&data).unwrap(),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[cfg(feature = "mjpeg")]
+#[test]
+fn decode_frame() {
+    use linux_video::types::FourCc;
+
+    let data = strip_dht(&encode_jpeg(32, 16, 95));
+
+    let mut output = Vec::new();
+    let fmt = decode(&data, FourCc::Rgb24, &mut output).unwrap();
+    assert_eq!(fmt.bytes_per_line(), 32 * 3);
+    assert_eq!(output.len(), 32 * 16 * 3);
+    assert!(output[3 * 33 + 1].abs_diff(128) <= 8);
+
+    let fmt = decode(&data, FourCc::Yuyv, &mut output).unwrap();
+    assert_eq!(fmt.pixel_format(), FourCc::Yuyv);
+    assert_eq!(output.len(), 32 * 16 * 2);
+
+    let fmt = decode(&data, FourCc::Yuv420, &mut output).unwrap();
+    assert_eq!(fmt.size_image() as usize, output.len());
+
+    assert!(decode(&data, FourCc::Nv12, &mut output).is_err());
+}
@@ -0,0 +1,230 @@
+use linux_video::{
+    types::{mock::*, private::Internal, *},
+    Device,
+};
+use std::os::unix::io::AsRawFd;
+
+fn open(config: MockConfig) -> (MockDevice, Device) {
+    let mock = MockDevice::with_config(config).unwrap();
+    let dev = Device::from(mock.open().unwrap());
+    (mock, dev)
+}
+
+fn set_format(dev: &Device, type_: BufferType, fourcc: FourCc, width: u32, height: u32) -> Format {
+    let mut fmt = Format::from(type_);
+    let pixfmt = fmt.try_mut::<PixFormat>().unwrap();
+    pixfmt.set_pixel_format(fourcc);
+    pixfmt.set_width(width);
+    pixfmt.set_height(height);
+    dev.set_format(&mut fmt).unwrap();
+    fmt
+}
+
+#[test]
+fn mock_capabilities() {
+    let (_mock, dev) = open(MockConfig::default());
+
+    let caps = dev.capabilities().unwrap();
+
+    assert_eq!(caps.driver(), "mock");
+    assert_eq!(caps.card(), "Mock Camera");
+    assert_eq!(caps.bus(), "platform:mock");
+    assert!(BufferType::VideoCapture.is_supported(caps.device_capabilities()));
+    assert!(!BufferType::VideoOutput.is_supported(caps.device_capabilities()));
+}
+
+#[test]
+fn mock_formats() {
+    let (_mock, dev) = open(MockConfig::default());
+
+    let mut fmts = dev.formats(BufferType::VideoCapture);
+    let mut fourccs = Vec::new();
+    while let Some(fmt) = fmts.fetch_next().unwrap() {
+        fourccs.push(fmt.pixel_format());
+    }
+    assert_eq!(fourccs, [FourCc::Yuyv, FourCc::Rgb24]);
+
+    let mut sizes = dev.sizes(FourCc::Yuyv);
+    let mut count = 0;
+    while let Some(size) = sizes.fetch_next().unwrap() {
+        assert_eq!(size.type_(), Ok(FrmSizeType::Discrete));
+        count += 1;
+    }
+    assert_eq!(count, 2);
+
+    let mut intervals = dev.intervals(FourCc::Yuyv, 1280, 720);
+    let mut count = 0;
+    while let Some(interval) = intervals.fetch_next().unwrap() {
+        assert_eq!(interval.type_(), Ok(FrmIvalType::Discrete));
+        count += 1;
+    }
+    assert_eq!(count, 2);
+
+    let fmt = dev.format(BufferType::VideoCapture).unwrap();
+    let pixfmt = fmt.try_ref::<PixFormat>().unwrap();
+    assert_eq!(pixfmt.pixel_format(), FourCc::Yuyv);
+    assert_eq!((pixfmt.width(), pixfmt.height()), (640, 480));
+
+    // nearest size is chosen
+    let mut fmt = Format::from(BufferType::VideoCapture);
+    let pixfmt = fmt.try_mut::<PixFormat>().unwrap();
+    pixfmt.set_pixel_format(FourCc::Rgb24);
+    pixfmt.set_width(300);
+    pixfmt.set_height(200);
+    dev.try_format(&mut fmt).unwrap();
+    let pixfmt = fmt.try_ref::<PixFormat>().unwrap();
+    assert_eq!((pixfmt.width(), pixfmt.height()), (320, 240));
+    assert_eq!(pixfmt.bytes_per_line(), 320 * 3);
+    assert_eq!(pixfmt.size_image(), 320 * 240 * 3);
+
+    // try does not change current format
+    let fmt = dev.format(BufferType::VideoCapture).unwrap();
+    assert_eq!(
+        fmt.try_ref::<PixFormat>().unwrap().pixel_format(),
+        FourCc::Yuyv
+    );
+
+    let mut param = dev.param(BufferType::VideoCapture).unwrap();
+    let mut interval = Fract::default();
+    interval.set_numerator(1);
+    interval.set_denominator(14);
+    let parm = param.try_mut::<CaptureParm>().unwrap();
+    parm.set_time_per_frame(interval);
+    dev.set_param(&mut param).unwrap();
+    let param = dev.param(BufferType::VideoCapture).unwrap();
+    let interval = param.try_ref::<CaptureParm>().unwrap().time_per_frame();
+    assert_eq!((interval.numerator(), interval.denominator()), (1, 15));
+}
+
+#[test]
+fn mock_controls() {
+    let (mock, dev) = open(MockConfig::default());
+
+    let mut ctrls = dev.controls(None);
+    let mut names = Vec::new();
+    while let Some(ctrl) = ctrls.fetch_next().unwrap() {
+        names.push(ctrl.name().to_string());
+    }
+    assert_eq!(
+        names,
+        [
+            "Brightness",
+            "Contrast",
+            "White Balance, Automatic",
+            "Power Line Frequency"
+        ]
+    );
+
+    let ctrl = dev.control(CtrlId::PowerLineFrequency).unwrap();
+    assert_eq!(ctrl.type_(), Ok(CtrlType::Menu));
+    let mut items = dev.control_items(&ctrl).unwrap();
+    let mut names = Vec::new();
+    while let Some(item) = items.fetch_next().unwrap() {
+        names.push(item.name().unwrap().to_string());
+    }
+    assert_eq!(names, ["Disabled", "50 Hz", "60 Hz", "Auto"]);
+
+    let ctrl = dev.control(CtrlId::Brightness).unwrap();
+    let mut value = Value::from(&ctrl);
+    dev.get_control(&mut value).unwrap();
+    assert_eq!(*value.try_ref::<i32>().unwrap(), 128);
+
+    *value.try_mut::<i32>().unwrap() = 300;
+    dev.set_control(&value).unwrap();
+    assert_eq!(mock.control(CtrlId::Brightness), Some(255));
+
+    let ctrl = dev.control(CtrlId::PowerLineFrequency).unwrap();
+    let mut value = Value::from(&ctrl);
+    *value.try_mut::<u32>().unwrap() = 4;
+    assert!(dev.set_control(&value).is_err());
+
+    assert!(dev.control(CtrlId::Gamma).is_err());
+}
+
+#[test]
+fn mock_capture() {
+    let (mock, dev) = open(MockConfig::default());
+
+    let fmt = set_format(&dev, BufferType::VideoCapture, FourCc::Rgb24, 640, 480);
+    let pixfmt = *fmt.try_ref::<PixFormat>().unwrap();
+
+    {
+        let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+
+        for i in 0..8 {
+            let buffer = stream.next().unwrap();
+            let buffer = buffer.lock();
+
+            assert_eq!(buffer.sequence(), i);
+            let data: &[u8] = buffer.as_ref();
+            assert_eq!(data, test_pattern(&pixfmt, i));
+        }
+    }
+
+    assert!(mock.frames() >= 8);
+
+    // format cannot be changed while buffers is allocated
+    let stream = dev.stream::<In, UserPtr>(ContentType::Video, 2).unwrap();
+    let mut fmt = fmt;
+    assert!(dev.set_format(&mut fmt).is_err());
+
+    let buffer = stream.next().unwrap();
+    let buffer = buffer.lock();
+    let data: &[u8] = buffer.as_ref();
+    assert_eq!(data, test_pattern(&pixfmt, 0));
+}
+
+#[test]
+fn mock_output() {
+    let (mock, dev) = open(MockConfig::output());
+
+    let fmt = set_format(&dev, BufferType::VideoOutput, FourCc::Rgb24, 320, 240);
+    let size = fmt.try_ref::<PixFormat>().unwrap().size_image() as usize;
+
+    let stream = dev.stream::<Out, Mmap>(ContentType::Video, 2).unwrap();
+
+    for i in 0..4u8 {
+        let buffer = stream.next().unwrap();
+        let mut buffer = buffer.lock();
+        buffer.set_len(size);
+        let data: &mut [u8] = buffer.as_mut();
+        data.fill(i);
+    }
+    let _ = stream.next().unwrap();
+
+    assert!(mock.frames() >= 3);
+    let last = mock.last_output().unwrap();
+    assert_eq!(last.len(), size);
+    assert!(last.iter().all(|byte| *byte == last[0]));
+}
+
+#[test]
+fn mock_events() {
+    let (mock, dev) = open(MockConfig::default());
+    let fd = dev.as_raw_fd();
+
+    let subscription = EventSubscription::new(
+        EventType::Ctrl,
+        CtrlId::Contrast.into(),
+        EventSubFlag::SendInitial,
+    );
+    Internal::from(&subscription).subscribe(fd).unwrap();
+
+    let event = Internal::<Event>::dequeue(fd).unwrap().into_inner();
+    assert_eq!(event.type_(), Ok(EventType::Ctrl));
+    assert_eq!(event.id(), CtrlId::Contrast as u32);
+
+    // no events pending
+    assert!(Internal::<Event>::dequeue(fd).is_err());
+
+    mock.set_control(CtrlId::Contrast, 10).unwrap();
+
+    let event = Internal::<Event>::dequeue(fd).unwrap().into_inner();
+    let ctrl = event.data::<EventCtrl>().unwrap();
+    assert_eq!(ctrl.type_(), Ok(CtrlType::Integer));
+    assert_eq!(mock.control(CtrlId::Contrast), Some(10));
+
+    Internal::from(&subscription).unsubscribe(fd).unwrap();
+    mock.set_control(CtrlId::Contrast, 20).unwrap();
+    assert!(Internal::<Event>::dequeue(fd).is_err());
+}
@@ -0,0 +1,32 @@
+use linux_video::{types::*, Device};
+use std::{io::ErrorKind, path::Path, time::Duration};
+
+#[test]
+fn device_event() {
+    let event = DeviceEvent::Added("/dev/video0".into());
+    assert!(event.is_added());
+    assert_eq!(event.path(), Path::new("/dev/video0"));
+    assert_eq!(event.to_string(), "added /dev/video0");
+
+    let event = DeviceEvent::Removed("/dev/video1".into());
+    assert!(event.is_removed());
+    assert_eq!(event.to_string(), "removed /dev/video1");
+}
+
+#[test]
+fn monitor_timeout() {
+    let mut monitor = Device::monitor().unwrap();
+
+    let error = monitor.next_timeout(Duration::from_millis(10)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::TimedOut);
+}
+
+#[test]
+fn monitor_cancel() {
+    let mut monitor = Device::monitor().unwrap();
+
+    monitor.cancel_handle().cancel().unwrap();
+
+    let error = monitor.next_event().unwrap_err();
+    assert_eq!(error::ErrorKind::of(&error), error::ErrorKind::Cancelled);
+}
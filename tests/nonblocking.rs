@@ -0,0 +1,58 @@
+use linux_video::{types::*, Device, Stream};
+use std::os::unix::io::{AsFd, AsRawFd};
+
+#[test]
+fn stream_fd() {
+    fn is_fd<T: AsRawFd + AsFd>() {}
+
+    is_fd::<Device>();
+    is_fd::<Stream<In, Mmap>>();
+    is_fd::<Stream<Out, UserPtr>>();
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn try_next_capture() {
+    let dev = Device::open_nonblocking(env!("VIVID_WEBCAM")).unwrap();
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+
+    let mut count = 0;
+    while count < 4 {
+        if stream.try_next().unwrap().is_some() {
+            count += 1;
+        } else {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+}
+
+#[cfg(feature = "mio")]
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn mio_capture() {
+    use mio::{Events, Poll, Token};
+
+    let dev = Device::open_nonblocking(env!("VIVID_WEBCAM")).unwrap();
+    let mut stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+    stream.start().unwrap();
+
+    let mut poll = Poll::new().unwrap();
+    let interest = stream.interest();
+    poll.registry()
+        .register(&mut stream, Token(0), interest)
+        .unwrap();
+
+    let mut events = Events::with_capacity(4);
+    let mut count = 0;
+    while count < 4 {
+        poll.poll(&mut events, None).unwrap();
+        for event in &events {
+            assert_eq!(event.token(), Token(0));
+            while stream.try_next().unwrap().is_some() {
+                count += 1;
+            }
+        }
+    }
+
+    poll.registry().deregister(&mut stream).unwrap();
+}
@@ -0,0 +1,46 @@
+use linux_video::{
+    types::{error::ErrorKind, mock::*, *},
+    Device, Error,
+};
+
+#[test]
+fn priority_guard() {
+    let mock = MockDevice::new().unwrap();
+    let dev = Device::from(mock.open().unwrap());
+
+    assert_eq!(dev.priority().unwrap(), Priority::Interactive);
+
+    {
+        let guard = dev.claim_priority(Priority::Record).unwrap();
+        assert_eq!(guard.priority(), Priority::Record);
+        assert_eq!(dev.priority().unwrap(), Priority::Record);
+    }
+
+    // released back to default
+    assert_eq!(dev.priority().unwrap(), Priority::Interactive);
+
+    let error = dev.claim_priority(Priority::Unset).err().unwrap();
+    assert_eq!(Error::from(error).kind(), ErrorKind::InvalidArgument);
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn priority_busy() {
+    let recorder = Device::open(env!("VIVID_WEBCAM")).unwrap();
+    let monitor = Device::open(env!("VIVID_WEBCAM")).unwrap();
+
+    let mut fmt = monitor.format(BufferType::VideoCapture).unwrap();
+
+    {
+        let _guard = recorder.claim_priority(Priority::Record).unwrap();
+        assert_eq!(monitor.priority().unwrap(), Priority::Record);
+
+        let error = Error::from(monitor.set_format(&mut fmt).err().unwrap());
+        assert_eq!(error.kind(), ErrorKind::Busy);
+        assert_eq!(error.call_name(), Some("s_fmt"));
+
+        recorder.set_format(&mut fmt).unwrap();
+    }
+
+    monitor.set_format(&mut fmt).unwrap();
+}
@@ -0,0 +1,132 @@
+use linux_video::types::{raw::*, *};
+
+fn pix_format(fourcc: FourCc, width: u32, height: u32) -> PixFormat {
+    let mut pixfmt = PixFormat::from(fourcc);
+    PlaneLayout::compute(fourcc, width, height, 1)
+        .unwrap()
+        .fill_pix_format(&mut pixfmt);
+    pixfmt
+}
+
+#[test]
+fn cfa_pattern() {
+    assert_eq!(
+        CfaPattern::from_fourcc(FourCc::Srggb10p),
+        Some(CfaPattern::Rggb)
+    );
+    assert_eq!(
+        CfaPattern::from_fourcc(FourCc::Sgbrg8),
+        Some(CfaPattern::Gbrg)
+    );
+    assert_eq!(
+        CfaPattern::from_fourcc(FourCc::Ipu3Sgrbg10),
+        Some(CfaPattern::Grbg)
+    );
+    assert_eq!(CfaPattern::from_fourcc(FourCc::Yuyv), None);
+    assert_eq!(CfaPattern::Bggr.channel(1, 1), 0);
+}
+
+#[test]
+fn unpack_mipi() {
+    let fmt = pix_format(FourCc::Srggb10p, 4, 1);
+    let image = RawImage::unpack([0x01, 0x02, 0x03, 0xff, 0b11_10_01_00], &fmt).unwrap();
+    assert_eq!(image.samples(), &[0x004, 0x009, 0x00e, 0x3ff]);
+    assert_eq!(image.depth(), 10);
+
+    let fmt = pix_format(FourCc::Sbggr12p, 2, 1);
+    let image = RawImage::unpack([0xab, 0x12, 0x5c], &fmt).unwrap();
+    assert_eq!(image.samples(), &[0xabc, 0x125]);
+
+    let fmt = pix_format(FourCc::Sgbrg14p, 4, 1);
+    let low: u32 = 0x3f | (0x01 << 6) | (0x20 << 12) | (0x15 << 18);
+    let [l0, l1, l2, _] = low.to_le_bytes();
+    let image = RawImage::unpack([0xff, 0x00, 0x80, 0x01, l0, l1, l2], &fmt).unwrap();
+    assert_eq!(
+        image.samples(),
+        &[0x3fff, 0x0001, (0x80 << 6) | 0x20, (0x01 << 6) | 0x15]
+    );
+
+    let fmt = pix_format(FourCc::Srggb16, 2, 1);
+    let image = RawImage::unpack([0x34, 0x12, 0xff, 0xff], &fmt).unwrap();
+    assert_eq!(image.samples(), &[0x1234, 0xffff]);
+}
+
+#[test]
+fn unpack_ipu3() {
+    let samples: Vec<u16> = (0..25).map(|i| i * 40 + 3).collect();
+    let mut data = [0u8; 32];
+    for (i, sample) in samples.iter().enumerate() {
+        for bit in 0..10 {
+            if sample & (1 << bit) != 0 {
+                let bit = i * 10 + bit;
+                data[bit / 8] |= 1 << (bit % 8);
+            }
+        }
+    }
+
+    let fmt = pix_format(FourCc::Ipu3Srggb10, 25, 1);
+    let image = RawImage::unpack(data, &fmt).unwrap();
+    assert_eq!(image.samples(), &samples[..]);
+}
+
+#[test]
+fn unpack_compressed() {
+    // the first pair is PCM, the rest is DPCM relative to the same color
+    let fmt = pix_format(FourCc::Srggb10dpcm8, 4, 1);
+    let image = RawImage::unpack([0x80 | 0x10, 0x80 | 0x20, 0x05, 0x20 | 0x03], &fmt).unwrap();
+    assert_eq!(image.samples(), &[0x84, 0x104, 0x89, 0x101]);
+
+    let fmt = pix_format(FourCc::Sbggr10alaw8, 4, 1);
+    let image = RawImage::unpack([0x00, 0x1f, 0x20, 0xff], &fmt).unwrap();
+    assert_eq!(image.samples(), &[0, 31, 32, 0x3ff]);
+}
+
+#[test]
+fn process_flat() {
+    // flat gray sensor with black level
+    let image = RawImage::new(8, 8, 10, CfaPattern::Rggb, vec![64 + 480; 64]).unwrap();
+    let mut processing = Processing::default();
+    processing
+        .set_black_level(64)
+        .set_white_level(Some(1024))
+        .set_srgb(false);
+
+    for method in [Demosaic::Bilinear, Demosaic::Malvar] {
+        processing.set_method(method);
+        let rgb = image.process(&processing);
+        assert_eq!(rgb.len(), 8 * 8 * 3);
+        assert!(rgb.iter().all(|value| value.abs_diff(128) <= 1));
+    }
+
+    // white balance and color correction
+    processing.set_gains([2.0, 1.0, 0.5]);
+    let rgb = image.process(&processing);
+    assert_eq!(&rgb[..3], &[255, 128, 64]);
+
+    processing.set_ccm([[0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0]]);
+    let rgb = image.process(&processing);
+    assert_eq!(&rgb[..3], &[64, 128, 255]);
+}
+
+#[test]
+fn process_colors() {
+    // pure red scene on RGGB sensor
+    let samples = (0..16 * 16)
+        .map(|i| {
+            if i % 2 == 0 && i / 16 % 2 == 0 {
+                255
+            } else {
+                0
+            }
+        })
+        .collect();
+    let image = RawImage::new(16, 16, 8, CfaPattern::Rggb, samples).unwrap();
+
+    for method in [Demosaic::Bilinear, Demosaic::Malvar] {
+        let mut processing = Processing::default();
+        processing.set_method(method);
+        let rgb = image.process(&processing);
+        let center = (8 * 16 + 8) * 3;
+        assert_eq!(&rgb[center..center + 3], &[255, 0, 0]);
+    }
+}
@@ -0,0 +1,105 @@
+use linux_video::{
+    types::{private::FrameBuf, *},
+    Device,
+};
+use std::io::Read;
+
+#[test]
+fn frame_size() {
+    let mut fmt = Format::from(BufferType::VideoCapture);
+    PlaneLayout::compute(FourCc::Yuyv, 4, 2, 1)
+        .unwrap()
+        .fill_pix_format(fmt.try_mut::<PixFormat>().unwrap());
+    assert_eq!(fmt.frame_size(), Some(16));
+
+    let fmt = Format::from(BufferType::VideoOverlay);
+    assert_eq!(fmt.frame_size(), None);
+}
+
+#[test]
+fn frame_buf_read() {
+    let mut frame = FrameBuf::new(5);
+    assert!(frame.is_empty());
+
+    frame
+        .fill(|data| {
+            data[..3].copy_from_slice(&[1, 2, 3]);
+            Ok(3)
+        })
+        .unwrap();
+
+    let mut out = [0; 2];
+    assert_eq!(frame.take(&mut out), 2);
+    assert_eq!(out, [1, 2]);
+    // read does not cross frame boundary
+    assert_eq!(frame.take(&mut out), 1);
+    assert_eq!(out[0], 3);
+    assert!(frame.is_empty());
+}
+
+#[test]
+fn frame_buf_write() {
+    let mut frame = FrameBuf::new(4);
+
+    assert_eq!(frame.put(&[1, 2, 3]), 3);
+    assert!(!frame.is_full());
+    assert_eq!(frame.put(&[4, 5]), 1);
+    assert!(frame.is_full());
+
+    let mut written = Vec::new();
+    frame
+        .flush(|data| {
+            written.extend_from_slice(data);
+            Ok(data.len())
+        })
+        .unwrap();
+    assert_eq!(written, [1, 2, 3, 4]);
+    assert!(frame.is_empty());
+}
+
+#[test]
+fn frame_buf_write_incomplete() {
+    let mut frame = FrameBuf::new(4);
+
+    assert_eq!(frame.put(&[1, 2]), 2);
+    let error = frame
+        .flush(|_| panic!("Incomplete frame must not be written"))
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(!frame.is_empty());
+
+    assert_eq!(frame.set(&[3, 4, 5]), 3);
+    assert!(frame.is_full());
+
+    let mut written = Vec::new();
+    frame
+        .flush(|data| {
+            written.extend_from_slice(data);
+            Ok(data.len())
+        })
+        .unwrap();
+    assert_eq!(written, [3, 4, 5]);
+    assert!(frame.is_empty());
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn read_webcam() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let mut stream = dev.read_stream(ContentType::Video).unwrap();
+    let size = stream.frame_size();
+    assert!(size > 0);
+
+    let mut frame = vec![0; size];
+    for _ in 0..3 {
+        let len = stream.read_frame(&mut frame).unwrap();
+        assert!(len > 0 && len <= size);
+    }
+
+    // partial reads keeps frame boundaries
+    let mut part = vec![0; size / 2 + 1];
+    assert_eq!(stream.read(&mut part).unwrap(), part.len());
+    assert_eq!(stream.read(&mut part).unwrap(), size - part.len());
+}
@@ -0,0 +1,118 @@
+use linux_video::{
+    types::{private::read_device_infos, *},
+    ResilientCapture,
+};
+use std::{
+    fs,
+    os::unix::fs::symlink,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+fn write(path: impl AsRef<Path>, data: &str) {
+    let path = path.as_ref();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, data).unwrap();
+}
+
+fn link(target: &str, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    symlink(target, path).unwrap();
+}
+
+#[test]
+fn usb_identity() {
+    let root = std::env::temp_dir().join(format!("linux-video-reconnect-{}", std::process::id()));
+    let sys = root.join("sys");
+    let dev = root.join("dev");
+
+    for (port, serial, node) in [("1-1", "ABC123", "video0"), ("1-2", "DEF456", "video2")] {
+        let usb = sys.join("devices/usb1").join(port);
+        write(usb.join("idVendor"), "046d\n");
+        write(usb.join("idProduct"), "085b\n");
+        write(usb.join("serial"), serial);
+
+        let node_dir = usb.join(format!("{port}:1.0/video4linux/{node}"));
+        write(node_dir.join("index"), "0\n");
+        link("../..", node_dir.join("device"));
+        link(
+            &format!("../../devices/usb1/{port}/{port}:1.0/video4linux/{node}"),
+            sys.join("class/video4linux").join(node),
+        );
+    }
+
+    let infos = read_device_infos(&sys, &dev).unwrap();
+    assert_eq!(infos.len(), 2);
+
+    let identity = DeviceIdentity::new(&infos[1]);
+    assert_eq!(
+        identity,
+        DeviceIdentity::Usb {
+            vendor_id: 0x046d,
+            product_id: 0x085b,
+            serial: "DEF456".into(),
+            index: 0,
+        }
+    );
+    assert_eq!(identity.to_string(), "usb 046d:085b #DEF456 node 0");
+
+    assert!(!identity.matches(&infos[0]));
+    assert!(identity.matches(&infos[1]));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn reconnect_policy() {
+    let policy = ReconnectPolicy::default();
+    assert!(policy.stall_timeout > policy.retry_interval);
+    assert_eq!(policy.max_wait, None);
+
+    assert_eq!(ConnectionEvent::Stalled.to_string(), "stalled");
+    assert_eq!(
+        ConnectionEvent::Reconnected("/dev/video0".into()).to_string(),
+        "reconnected /dev/video0"
+    );
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn resilient_capture() {
+    let mut capture =
+        ResilientCapture::<Mmap>::open(env!("VIVID_WEBCAM"), ContentType::Video, 4).unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    capture.on_event({
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event.clone())
+    });
+
+    let ctrl = capture
+        .device()
+        .unwrap()
+        .control(CtrlId::Brightness)
+        .unwrap();
+    let mut value = Value::from(&ctrl);
+    *value.try_mut::<i32>().unwrap() = 100;
+    capture.set_control(&value).unwrap();
+
+    for _ in 0..2 {
+        capture.next().unwrap();
+    }
+
+    capture.reconnect().unwrap();
+    assert!(capture.is_connected());
+    assert!(matches!(
+        events.lock().unwrap().as_slice(),
+        [ConnectionEvent::Reconnected(_)]
+    ));
+
+    *value.try_mut::<i32>().unwrap() = 0;
+    capture.device().unwrap().get_control(&mut value).unwrap();
+    assert_eq!(*value.try_ref::<i32>().unwrap(), 100);
+
+    for _ in 0..2 {
+        capture.next().unwrap();
+    }
+}
@@ -0,0 +1,78 @@
+use linux_video::{
+    types::{calls, mock::*, record::*, *},
+    Device,
+};
+use std::os::unix::io::AsRawFd;
+
+fn run(dev: &Device) {
+    let caps = dev.capabilities().unwrap();
+    assert_eq!(caps.driver(), "mock");
+
+    let mut fmt = Format::from(BufferType::VideoCapture);
+    let pixfmt = fmt.try_mut::<PixFormat>().unwrap();
+    pixfmt.set_pixel_format(FourCc::Rgb24);
+    pixfmt.set_width(300);
+    pixfmt.set_height(200);
+    dev.set_format(&mut fmt).unwrap();
+    let pixfmt = fmt.try_ref::<PixFormat>().unwrap();
+    assert_eq!((pixfmt.width(), pixfmt.height()), (320, 240));
+
+    let ctrl = dev.control(CtrlId::Brightness).unwrap();
+    let mut value = Value::from(&ctrl);
+    dev.get_control(&mut value).unwrap();
+    assert_eq!(*value.try_ref::<i32>().unwrap(), 128);
+    *value.try_mut::<i32>().unwrap() = 64;
+    dev.set_control(&value).unwrap();
+    dev.get_control(&mut value).unwrap();
+    assert_eq!(*value.try_ref::<i32>().unwrap(), 64);
+
+    assert!(dev.control(CtrlId::Gamma).is_err());
+
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 2).unwrap();
+    for i in 0..4 {
+        let buffer = stream.next().unwrap();
+        let buffer = buffer.lock();
+        assert_eq!(buffer.sequence(), i);
+    }
+}
+
+#[test]
+fn record_replay() {
+    let path = std::env::temp_dir().join(format!("linux-video-{}.session", std::process::id()));
+
+    {
+        let mock = MockDevice::new().unwrap();
+        let dev = Device::from(mock.open().unwrap());
+
+        let recorder = Recorder::attach(dev.as_raw_fd()).unwrap();
+        run(&dev);
+        let session = recorder.detach();
+
+        assert!(!session.records().is_empty());
+        session.save(&path).unwrap();
+    }
+
+    let session = Session::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let replay = ReplayDevice::new(session).unwrap();
+    let dev = Device::from(replay.open().unwrap());
+
+    run(&dev);
+
+    // unknown calls fails
+    assert!(dev.format(BufferType::VideoOutput).is_err());
+}
+
+#[test]
+fn record_detach() {
+    let file = std::fs::File::open("/dev/null").unwrap();
+    let fd = file.as_raw_fd();
+
+    let recorder = Recorder::attach(fd).unwrap();
+    assert!(calls::registered_backend(fd).is_some());
+    drop(recorder);
+
+    // kernel calls is used again without registered backend
+    assert!(calls::registered_backend(fd).is_none());
+}
@@ -0,0 +1,101 @@
+use linux_video::{
+    types::{error::ErrorKind, mock::*, *},
+    Device,
+};
+use std::{sync::mpsc, thread, time::Duration};
+
+#[test]
+fn shared_buffer_is_send() {
+    fn is_send<T: Send>() {}
+    is_send::<SharedBuffer<In, Mmap>>();
+    is_send::<SharedBuffer<Out, UserPtr>>();
+    is_send::<BufferReturns>();
+}
+
+#[test]
+fn hold_policy_default() {
+    assert_eq!(HoldPolicy::default(), HoldPolicy::Block);
+}
+
+#[test]
+fn wait_released() {
+    let mock = MockDevice::new().unwrap();
+    let dev = Device::from(mock.open().unwrap());
+
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 2).unwrap();
+    let mut held = (0..2)
+        .map(|_| stream.next_shared().unwrap())
+        .collect::<Vec<_>>();
+
+    let worker = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        held.pop();
+        held
+    });
+    let next = stream.next_shared().unwrap();
+    assert!(next.lock().is_some());
+    let held = worker.join().unwrap();
+
+    // all buffers is held again
+    let cancel = stream.cancel_handle();
+    let worker = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        cancel.cancel().unwrap();
+    });
+    let error = stream.next_shared().err().unwrap();
+    assert_eq!(ErrorKind::of(&error), ErrorKind::Cancelled);
+    worker.join().unwrap();
+    drop(held);
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn capture_to_workers() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let mut stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+    stream.set_hold_policy(HoldPolicy::Block);
+
+    let (sender, receiver) = mpsc::sync_channel::<SharedBuffer<In, Mmap>>(2);
+
+    let worker = thread::spawn(move || {
+        let mut count = 0;
+        for buffer in receiver {
+            let data = buffer.lock().unwrap();
+            assert!(!data.is_empty());
+            count += 1;
+        }
+        count
+    });
+
+    for _ in 0..10 {
+        sender.send(stream.next_shared().unwrap()).unwrap();
+    }
+    drop(sender);
+
+    assert_eq!(worker.join().unwrap(), 10);
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn capture_drop_oldest() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let mut stream = dev.stream::<In, Mmap>(ContentType::Video, 2).unwrap();
+
+    stream.set_hold_policy(HoldPolicy::Error);
+    let held = (0..2)
+        .map(|_| stream.next_shared().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stream.next_shared().err().unwrap().kind(),
+        std::io::ErrorKind::ResourceBusy
+    );
+
+    stream.set_hold_policy(HoldPolicy::DropOldest);
+    let _next = stream.next_shared().unwrap();
+    assert!(held[0].is_dropped());
+    assert!(held[0].lock().is_none());
+}
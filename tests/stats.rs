@@ -0,0 +1,63 @@
+use linux_video::{types::*, Device};
+use std::time::Duration;
+
+#[test]
+fn rolling_average() {
+    let mut avg = RollingAverage::new(3);
+    assert_eq!(avg.average(), None);
+
+    for ms in [10, 20, 30, 40] {
+        avg.add(Duration::from_millis(ms));
+    }
+    assert_eq!(avg.len(), 3);
+    assert_eq!(avg.average(), Some(Duration::from_millis(30)));
+    assert_eq!(avg.max(), Some(Duration::from_millis(40)));
+}
+
+#[test]
+fn histogram() {
+    let mut hist = Histogram::new(Duration::from_millis(1), 4);
+    hist.add(Duration::from_micros(500));
+    hist.add(Duration::from_micros(2500));
+    hist.add(Duration::from_micros(2900));
+    hist.add(Duration::from_millis(10));
+
+    assert_eq!(hist.bins(), [1, 0, 2, 0]);
+    assert_eq!(hist.overflow(), 1);
+    assert_eq!(hist.count(), 4);
+}
+
+#[test]
+fn time_per_frame() {
+    let mut parm = StreamParm::from(BufferType::VideoCapture);
+    assert_eq!(parm.time_per_frame(), None);
+
+    let capture = parm.try_mut::<CaptureParm>().unwrap();
+    let mut fract = capture.time_per_frame();
+    fract.set_numerator(1);
+    fract.set_denominator(25);
+    capture.set_time_per_frame(fract);
+    assert_eq!(parm.time_per_frame(), Some(Duration::from_millis(40)));
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn capture_stats() {
+    let dev = env!("VIVID_WEBCAM");
+    let dev = Device::open(dev).unwrap();
+
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+    assert!(stream.stats().is_none());
+
+    stream.enable_stats();
+    for _ in 0..10 {
+        stream.next().unwrap();
+    }
+
+    let stats = stream.stats().unwrap();
+    println!("{stats}");
+    assert_eq!(stats.frames(), 10);
+    assert_eq!(stats.errors(), 0);
+    assert!(stats.interval().average().is_some());
+    assert!(stats.latency().average().is_some());
+}
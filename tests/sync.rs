@@ -0,0 +1,113 @@
+use linux_video::{
+    types::{
+        private::{FrameMatcher, Internal},
+        *,
+    },
+    Device, SyncGroup,
+};
+use std::time::Duration;
+
+fn frame(sequence: u32, timestamp: Duration) -> Buffer {
+    let mut buffer =
+        Internal::<Buffer>::new(BufferType::VideoCapture, Memory::Mmap, 0).into_inner();
+    buffer.set_sequence(sequence);
+    buffer.set_timestamp(timestamp);
+    buffer
+}
+
+#[test]
+fn empty_group() {
+    let result = SyncGroup::<Mmap>::new(Vec::new(), SyncMode::default());
+    assert_eq!(
+        result.err().unwrap().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn default_mode() {
+    assert_eq!(
+        SyncMode::default(),
+        SyncMode::Timestamp(Duration::from_millis(5))
+    );
+}
+
+#[test]
+fn match_timestamps() {
+    let mut matcher = FrameMatcher::new(SyncMode::Timestamp(Duration::from_millis(5)), 2);
+    let ms = Duration::from_millis;
+
+    // inside tolerance
+    assert!(matcher
+        .check(&[frame(0, ms(100)), frame(0, ms(104))])
+        .is_empty());
+    // the first stream lags behind
+    assert_eq!(matcher.check(&[frame(1, ms(133)), frame(1, ms(140))]), [0]);
+    assert!(matcher
+        .check(&[frame(2, ms(138)), frame(1, ms(140))])
+        .is_empty());
+
+    assert_eq!(matcher.stats().groups(), 2);
+    assert_eq!(matcher.stats().unmatched(), [1, 0]);
+}
+
+#[test]
+fn match_frame_sync() {
+    let mut matcher = FrameMatcher::new(SyncMode::FrameSync, 2);
+    let at = Duration::default();
+
+    assert!(matcher.needs_event(0));
+    matcher.event(0, &Internal::<Event>::new(0, EventFrameSync::new(10)));
+    matcher.event(1, &Internal::<Event>::new(0, EventFrameSync::new(3)));
+    assert!(!matcher.needs_event(0) && !matcher.needs_event(1));
+
+    // sequences is compared relative to offsets
+    assert!(matcher.check(&[frame(10, at), frame(3, at)]).is_empty());
+    assert_eq!(matcher.check(&[frame(11, at), frame(5, at)]), [0]);
+    assert!(matcher.check(&[frame(12, at), frame(5, at)]).is_empty());
+
+    assert_eq!(matcher.stats().groups(), 2);
+    assert_eq!(matcher.stats().unmatched(), [1, 0]);
+}
+
+#[test]
+fn match_dropped() {
+    let mut matcher = FrameMatcher::new(SyncMode::FrameSync, 2);
+    let at = Duration::default();
+
+    for sequence in [0, 1, 4, 5] {
+        matcher.receive(0, &frame(sequence, at));
+    }
+    // wraps around without overflow
+    for sequence in [u32::MAX - 1, u32::MAX, 1] {
+        matcher.receive(1, &frame(sequence, at));
+    }
+
+    assert_eq!(matcher.stats().dropped(), [2, 1]);
+
+    // restarted stream is not counted
+    matcher.reset();
+    matcher.receive(0, &frame(0, at));
+    assert_eq!(matcher.stats().dropped(), [2, 1]);
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn single_stream_group() {
+    let dev = Device::open(env!("VIVID_WEBCAM")).unwrap();
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+
+    let mut group = SyncGroup::new(vec![stream], SyncMode::default()).unwrap();
+    group.start().unwrap();
+
+    for _ in 0..4 {
+        let frames = group.next_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(frames.len(), 1);
+    }
+
+    assert_eq!(group.stats().groups(), 4);
+    assert_eq!(group.stats().unmatched(), [0]);
+
+    group.stop().unwrap();
+    assert_eq!(group.into_streams().len(), 1);
+}
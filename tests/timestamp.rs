@@ -0,0 +1,58 @@
+use linux_video::{types::*, Device};
+use std::time::{Duration, Instant, SystemTime};
+
+#[test]
+fn timestamp_kind() {
+    let kind = TimestampKind::from(BufferFlag::from(0x2000 | 0x10000));
+    assert_eq!(kind.clock, Timestamp::Monotonic);
+    assert_eq!(kind.source, TimestampSrc::Soe);
+    assert!(kind.is_monotonic());
+
+    let kind = TimestampKind::from(BufferFlag::from(0x4000));
+    assert_eq!(kind.clock, Timestamp::Copy);
+    assert_eq!(kind.source, TimestampSrc::Eof);
+    assert!(!kind.is_monotonic());
+}
+
+#[test]
+fn clock_sync() {
+    let sync = ClockSync::now();
+    let now = monotonic_time();
+
+    let instant = sync.to_instant(now);
+    assert!(instant >= Instant::now() - Duration::from_secs(1));
+    assert!(instant <= Instant::now());
+
+    let past = now - Duration::from_millis(100);
+    assert_eq!(
+        sync.to_instant(now) - sync.to_instant(past),
+        Duration::from_millis(100)
+    );
+    assert_eq!(sync.to_monotonic(sync.to_instant(past)), past);
+
+    let since_epoch = |time: SystemTime| time.duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    let system = since_epoch(sync.to_system_time(now));
+    let diff = since_epoch(SystemTime::now()).abs_diff(system);
+    assert!(diff < Duration::from_secs(1));
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[test]
+fn capture_timestamp() {
+    let dev = Device::open(env!("VIVID_WEBCAM")).unwrap();
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 2).unwrap();
+
+    let buffer = stream.next().unwrap();
+    let buffer = buffer.lock();
+
+    let kind = buffer.timestamp_kind();
+    if kind.is_monotonic() {
+        let instant = buffer.instant().unwrap();
+        assert!(instant <= Instant::now());
+        let system = buffer.system_time().unwrap();
+        assert!(system <= SystemTime::now());
+    } else {
+        assert!(buffer.instant().is_none());
+        assert!(buffer.system_time().is_none());
+    }
+}
@@ -4,16 +4,24 @@
 
 use std::{
     fs::File,
-    io,
+    future::Future,
+    io::{self, Read, Write},
+    ops::Range,
     os::unix::io::{AsRawFd, RawFd},
     path::{Path, PathBuf},
+    pin::Pin,
+    task::{ready, Context, Poll},
 };
 
 pub use linux_video_core as types;
 use linux_video_core::private::*;
+pub use types::error::Error;
 use types::*;
 
-use tokio::{io::unix::AsyncFd, task::spawn_blocking};
+use tokio::{
+    io::{unix::AsyncFd, AsyncRead, AsyncWrite, Interest, ReadBuf},
+    task::spawn_blocking,
+};
 
 async fn asyncify<F, T>(f: F) -> Result<T>
 where
@@ -22,7 +30,37 @@ where
 {
     match spawn_blocking(f).await {
         Ok(res) => res,
-        Err(_) => Err(Error::new(io::ErrorKind::Other, "background task failed")),
+        Err(_) => Err(io::Error::other("background task failed")),
+    }
+}
+
+/// Pending background query
+struct Query<T>(Option<Pin<Box<dyn Future<Output = Result<T>> + Send>>>);
+
+impl<T> Default for Query<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Query<T> {
+    fn is_idle(&self) -> bool {
+        self.0.is_none()
+    }
+
+    fn start(&mut self, future: impl Future<Output = Result<T>> + Send + 'static) {
+        self.0 = Some(Box::pin(future));
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Result<T>> {
+        let result = ready!(self
+            .0
+            .as_mut()
+            .expect("Query is not started")
+            .as_mut()
+            .poll(cx));
+        self.0 = None;
+        Poll::Ready(result)
     }
 }
 
@@ -37,12 +75,35 @@ impl AsRawFd for Device {
     }
 }
 
+/// Use already opened device file
+///
+/// The file should be opened in non-blocking mode.
+impl From<File> for Device {
+    fn from(file: File) -> Self {
+        Device { file }
+    }
+}
+
 impl Device {
     /// List video devices
     pub async fn list() -> Result<Devices> {
         Devices::new().await
     }
 
+    /// Discover video devices grouped by physical devices
+    ///
+    /// Information is collected from sysfs.
+    pub async fn discover() -> Result<Vec<PhysicalDevice>> {
+        asyncify(discover_devices).await
+    }
+
+    /// Watch video devices adding and removing
+    ///
+    /// Should be called within runtime context.
+    pub fn monitor() -> Result<DeviceMonitor> {
+        DeviceMonitor::new()
+    }
+
     /// Open video device
     pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref().to_owned();
@@ -58,6 +119,25 @@ impl Device {
         asyncify(move || Internal::<Capability>::query(fd).map(Internal::into_inner)).await
     }
 
+    /// Get highest priority of device handles
+    pub async fn priority(&self) -> Result<Priority> {
+        let fd = self.as_raw_fd();
+        asyncify(move || Internal::<Priority>::query(fd).map(Internal::into_inner)).await
+    }
+
+    /// Claim access priority
+    ///
+    /// Other handles cannot change settings while this one holds higher priority,
+    /// such attempts fails with [types::error::ErrorKind::Busy].
+    pub async fn claim_priority(&self, priority: Priority) -> Result<PriorityGuard<'_>> {
+        let fd = self.as_raw_fd();
+        asyncify(move || Internal::from(priority).set(fd)).await?;
+        Ok(PriorityGuard {
+            device: self,
+            priority,
+        })
+    }
+
     /// Get controls
     pub fn controls(&self, class: Option<CtrlClass>) -> Controls<'_> {
         let last_id = class.map(|c| c as _).unwrap_or_default();
@@ -66,6 +146,7 @@ impl Device {
             device: self,
             class,
             last_id,
+            query: Query::default(),
         }
     }
 
@@ -83,9 +164,10 @@ impl Device {
         if control.is_menu() {
             Some(MenuItems {
                 device: self,
-                ctrl_type: control.type_(),
+                ctrl_type: control.type_().ok()?,
                 ctrl_id: control.id(),
                 index_iter: control.min() as _..=control.max() as _,
+                query: Query::default(),
             })
         } else {
             None
@@ -93,15 +175,53 @@ impl Device {
     }
 
     /// Get control value
+    ///
+    /// Values is copied to get it in background.
     pub async fn get_control<T: GetValue>(&self, value: &mut T) -> Result<()> {
-        //let fd = self.as_raw_fd();
-        //asyncify(move || value.get(fd)).await
-        value.get(self.as_raw_fd())
+        let fd = self.as_raw_fd();
+        let mut data = value.copy_data();
+        let data = asyncify(move || {
+            data.get(fd)?;
+            Ok(data)
+        })
+        .await?;
+        value.update_data(&data);
+        Ok(())
     }
 
     /// Set control value
+    ///
+    /// Values is copied to set it in background.
     pub async fn set_control<T: SetValue>(&self, value: &T) -> Result<()> {
-        value.set(self.as_raw_fd())
+        let fd = self.as_raw_fd();
+        let data = value.copy_data();
+        asyncify(move || data.set(fd)).await
+    }
+
+    /// Subscribe to events
+    pub fn subscribe_event(&self, subscription: &EventSubscription) -> Result<()> {
+        Internal::from(subscription).subscribe(self.as_raw_fd())
+    }
+
+    /// Unsubscribe from events
+    pub fn unsubscribe_event(&self, subscription: &EventSubscription) -> Result<()> {
+        Internal::from(subscription).unsubscribe(self.as_raw_fd())
+    }
+
+    /// Wait for next subscribed event
+    pub async fn next_event(&self) -> Result<Event> {
+        // cloned file is owned by registration so descriptor stays valid
+        let ready =
+            unsafe { AsyncFd::register_with_interest(self.file.try_clone()?, Interest::PRIORITY) }?;
+        loop {
+            match Internal::<Event>::dequeue(self.as_raw_fd()) {
+                Ok(event) => return Ok(event.into_inner()),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    ready.ready(Interest::PRIORITY).await?.clear_ready();
+                }
+                Err(error) => return Err(error),
+            }
+        }
     }
 
     /// Get supported formats
@@ -110,6 +230,7 @@ impl Device {
             device: self,
             type_,
             index: 0,
+            query: Query::default(),
         }
     }
 
@@ -161,6 +282,7 @@ impl Device {
             device: self,
             pixel_format,
             index: 0,
+            query: Query::default(),
         }
     }
 
@@ -172,6 +294,7 @@ impl Device {
             width,
             height,
             index: 0,
+            query: Query::default(),
         }
     }
 
@@ -213,36 +336,134 @@ impl Device {
     ) -> Result<Stream<Dir, Met>> {
         Stream::new(self.file.try_clone()?, type_, count)
     }
+
+    /// Create stream to read data using read I/O method
+    pub fn read_stream(&self, type_: ContentType) -> Result<ReadStream> {
+        ReadStream::new(self.file.try_clone()?, type_)
+    }
+
+    /// Create stream to write data using write I/O method
+    pub fn write_stream(&self, type_: ContentType) -> Result<WriteStream> {
+        WriteStream::new(self.file.try_clone()?, type_)
+    }
+}
+
+/// Claimed access priority
+///
+/// The priority is dropped back to default on release.
+pub struct PriorityGuard<'a> {
+    device: &'a Device,
+    priority: Priority,
+}
+
+impl PriorityGuard<'_> {
+    /// Get claimed priority
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+}
+
+impl Drop for PriorityGuard<'_> {
+    fn drop(&mut self) {
+        let _ = Internal::from(Priority::default()).set(self.device.as_raw_fd());
+    }
 }
 
 /// The interface to get available devices
 pub struct Devices {
     reader: tokio::fs::ReadDir,
+    query: Query<Option<PathBuf>>,
 }
 
 impl Devices {
     async fn new() -> Result<Self> {
-        tokio::fs::read_dir("/dev")
-            .await
-            .map(|reader| Devices { reader })
+        tokio::fs::read_dir("/dev").await.map(|reader| Devices {
+            reader,
+            query: Query::default(),
+        })
     }
 
     /// Get path of the next device
     pub async fn fetch_next(&mut self) -> Result<Option<PathBuf>> {
+        core::future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<PathBuf>>> {
         use std::os::unix::fs::FileTypeExt;
 
-        while let Some(entry) = self.reader.next_entry().await? {
-            if let Some(file_name) = entry.file_name().to_str() {
-                if check_dev_name(file_name).is_some() {
-                    let file_type = entry.file_type().await?;
-                    if file_type.is_char_device() {
-                        return Ok(Some(entry.path()));
+        loop {
+            if self.query.is_idle() {
+                let Some(entry) = ready!(self.reader.poll_next_entry(cx))? else {
+                    return Poll::Ready(Ok(None));
+                };
+
+                if let Some(file_name) = entry.file_name().to_str() {
+                    if check_dev_name(file_name).is_some() {
+                        self.query.start(async move {
+                            let file_type = entry.file_type().await?;
+                            Ok(file_type.is_char_device().then(|| entry.path()))
+                        });
                     }
                 }
+
+                continue;
+            }
+
+            if let Some(path) = ready!(self.query.poll(cx))? {
+                return Poll::Ready(Ok(Some(path)));
+            }
+        }
+    }
+}
+
+/// The interface to watch devices adding and removing
+pub struct DeviceMonitor {
+    /// Should be deregistered before closing watcher
+    ready: AsyncFd<FdWrapper>,
+    watcher: DeviceWatcher,
+}
+
+impl DeviceMonitor {
+    fn new() -> Result<Self> {
+        let watcher = DeviceWatcher::new()?;
+
+        // registration is dropped before watcher so descriptor stays valid
+        let ready = unsafe {
+            AsyncFd::register_with_interest(
+                FdWrapper {
+                    fd: watcher.as_raw_fd(),
+                },
+                Interest::READABLE,
+            )
+        }?;
+
+        Ok(Self { ready, watcher })
+    }
+
+    /// Get next device event
+    pub async fn next_event(&mut self) -> Result<DeviceEvent> {
+        core::future::poll_fn(|cx| self.poll_event(cx)).await
+    }
+
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Result<DeviceEvent>> {
+        loop {
+            match self.watcher.read() {
+                Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
+                    let mut guard = ready!(self.ready.poll_read_ready(cx))?;
+                    // readiness is cleared before next try so no events will be lost
+                    guard.clear_ready();
+                }
+                result => return Poll::Ready(result),
             }
         }
+    }
+}
 
-        Ok(None)
+impl futures_core::Stream for DeviceMonitor {
+    type Item = Result<DeviceEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_event(cx).map(Some)
     }
 }
 
@@ -251,35 +472,44 @@ pub struct Controls<'i> {
     device: &'i Device,
     class: Option<CtrlClass>,
     last_id: u32,
+    query: Query<Option<Internal<QueryExtCtrl>>>,
 }
 
 impl<'i> Controls<'i> {
     /// Get next control
     pub async fn fetch_next(&mut self) -> Result<Option<Control>> {
+        core::future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<Control>>> {
         if self.last_id == u32::MAX {
-            return Ok(None);
+            return Poll::Ready(Ok(None));
         }
 
-        let fd = self.device.as_raw_fd();
-        let id = self.last_id;
+        if self.query.is_idle() {
+            let fd = self.device.as_raw_fd();
+            let id = self.last_id;
 
-        if let Some(ctrl) =
-            asyncify(move || Internal::<QueryExtCtrl>::query_next_fallback(fd, id)).await?
-        {
+            self.query.start(asyncify(move || {
+                Internal::<QueryExtCtrl>::query_next_fallback(fd, id)
+            }));
+        }
+
+        if let Some(ctrl) = ready!(self.query.poll(cx))? {
             if self
                 .class
                 .map(|class| class.fast_match(ctrl.id()))
                 .unwrap_or(true)
             {
                 self.last_id = ctrl.id();
-                Ok(Some(Control { ctrl }))
+                Poll::Ready(Ok(Some(Control { ctrl })))
             } else {
                 self.last_id = u32::MAX;
-                Ok(None)
+                Poll::Ready(Ok(None))
             }
         } else {
             self.last_id = u32::MAX;
-            Ok(None)
+            Poll::Ready(Ok(None))
         }
     }
 }
@@ -315,22 +545,35 @@ pub struct MenuItems<'i> {
     ctrl_type: CtrlType,
     ctrl_id: u32,
     index_iter: core::ops::RangeInclusive<u32>,
+    query: Query<Option<Internal<MenuItem>>>,
 }
 
 impl<'i> MenuItems<'i> {
     /// Get next menu control item
     pub async fn fetch_next(&mut self) -> Result<Option<MenuItem>> {
-        let fd = self.device.as_raw_fd();
-        let type_ = self.ctrl_type;
-        let id = self.ctrl_id;
-        for index in &mut self.index_iter {
-            if let Some(item) =
-                asyncify(move || Internal::<MenuItem>::query(fd, type_, id, index)).await?
-            {
-                return Ok(Some(item.into_inner()));
+        core::future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<MenuItem>>> {
+        loop {
+            if self.query.is_idle() {
+                let Some(index) = self.index_iter.next() else {
+                    return Poll::Ready(Ok(None));
+                };
+
+                let fd = self.device.as_raw_fd();
+                let type_ = self.ctrl_type;
+                let id = self.ctrl_id;
+
+                self.query.start(asyncify(move || {
+                    Internal::<MenuItem>::query(fd, type_, id, index)
+                }));
+            }
+
+            if let Some(item) = ready!(self.query.poll(cx))? {
+                return Poll::Ready(Ok(Some(item.into_inner())));
             }
         }
-        Ok(None)
     }
 }
 
@@ -339,25 +582,36 @@ pub struct FmtDescs<'i> {
     device: &'i Device,
     type_: BufferType,
     index: u32,
+    query: Query<Option<Internal<FmtDesc>>>,
 }
 
 impl<'i> FmtDescs<'i> {
     /// Fetch next format description
     pub async fn fetch_next(&mut self) -> Result<Option<FmtDesc>> {
+        core::future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<FmtDesc>>> {
         if self.index == u32::MAX {
-            return Ok(None);
+            return Poll::Ready(Ok(None));
         }
 
-        let fd = self.device.as_raw_fd();
-        let index = self.index;
-        let type_ = self.type_;
+        if self.query.is_idle() {
+            let fd = self.device.as_raw_fd();
+            let index = self.index;
+            let type_ = self.type_;
+
+            self.query.start(asyncify(move || {
+                Internal::<FmtDesc>::query(fd, index, type_)
+            }));
+        }
 
-        if let Some(desc) = asyncify(move || Internal::<FmtDesc>::query(fd, index, type_)).await? {
+        if let Some(desc) = ready!(self.query.poll(cx))? {
             self.index += 1;
-            Ok(Some(desc.into_inner()))
+            Poll::Ready(Ok(Some(desc.into_inner())))
         } else {
             self.index = u32::MAX;
-            Ok(None)
+            Poll::Ready(Ok(None))
         }
     }
 }
@@ -367,27 +621,36 @@ pub struct FrmSizes<'i> {
     device: &'i Device,
     pixel_format: FourCc,
     index: u32,
+    query: Query<Option<Internal<FrmSizeEnum>>>,
 }
 
 impl<'i> FrmSizes<'i> {
     /// Get next frame size value
     pub async fn fetch_next(&mut self) -> Result<Option<FrmSizeEnum>> {
+        core::future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<FrmSizeEnum>>> {
         if self.index == u32::MAX {
-            return Ok(None);
+            return Poll::Ready(Ok(None));
         }
 
-        let fd = self.device.as_raw_fd();
-        let index = self.index;
-        let pixfmt = self.pixel_format;
+        if self.query.is_idle() {
+            let fd = self.device.as_raw_fd();
+            let index = self.index;
+            let pixfmt = self.pixel_format;
+
+            self.query.start(asyncify(move || {
+                Internal::<FrmSizeEnum>::query(fd, index, pixfmt)
+            }));
+        }
 
-        if let Some(size) =
-            asyncify(move || Internal::<FrmSizeEnum>::query(fd, index, pixfmt)).await?
-        {
+        if let Some(size) = ready!(self.query.poll(cx))? {
             self.index += 1;
-            Ok(Some(size.into_inner()))
+            Poll::Ready(Ok(Some(size.into_inner())))
         } else {
             self.index = u32::MAX;
-            Ok(None)
+            Poll::Ready(Ok(None))
         }
     }
 }
@@ -399,38 +662,75 @@ pub struct FrmIvals<'i> {
     width: u32,
     height: u32,
     index: u32,
+    query: Query<Option<Internal<FrmIvalEnum>>>,
 }
 
 impl<'i> FrmIvals<'i> {
     /// Get next frame interval value
     pub async fn fetch_next(&mut self) -> Result<Option<FrmIvalEnum>> {
+        core::future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    fn poll_fetch(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<FrmIvalEnum>>> {
         if self.index == u32::MAX {
-            return Ok(None);
+            return Poll::Ready(Ok(None));
         }
 
-        let fd = self.device.as_raw_fd();
-        let index = self.index;
-        let pixfmt = self.pixel_format;
-        let width = self.width;
-        let height = self.height;
+        if self.query.is_idle() {
+            let fd = self.device.as_raw_fd();
+            let index = self.index;
+            let pixfmt = self.pixel_format;
+            let width = self.width;
+            let height = self.height;
+
+            self.query.start(asyncify(move || {
+                Internal::<FrmIvalEnum>::query(fd, index, pixfmt, width, height)
+            }));
+        }
 
-        if let Some(ival) =
-            asyncify(move || Internal::<FrmIvalEnum>::query(fd, index, pixfmt, width, height))
-                .await?
-        {
+        if let Some(ival) = ready!(self.query.poll(cx))? {
             self.index += 1;
-            Ok(Some(ival.into_inner()))
+            Poll::Ready(Ok(Some(ival.into_inner())))
         } else {
             self.index = u32::MAX;
-            Ok(None)
+            Poll::Ready(Ok(None))
         }
     }
 }
 
+macro_rules! stream_impls {
+    ($($type:ident $(<$($type_params:lifetime),*>)* => $item_type:ident,)*) => {
+        $(
+            impl $(<$($type_params),*>)* futures_core::Stream for $type $(<$($type_params),*>)* {
+                type Item = Result<$item_type>;
+
+                fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                    self.get_mut().poll_fetch(cx).map(Result::transpose)
+                }
+            }
+        )*
+    };
+}
+
+stream_impls! {
+    Devices => PathBuf,
+    Controls<'i> => Control,
+    MenuItems<'i> => MenuItem,
+    FmtDescs<'i> => FmtDesc,
+    FrmSizes<'i> => FrmSizeEnum,
+    FrmIvals<'i> => FrmIvalEnum,
+}
+
 /// Data I/O queue
 pub struct Stream<Dir, Met: Method> {
+    /// Should be deregistered before closing file
+    ready: AsyncFd<FdWrapper>,
     file: File,
+    format: Format,
     queue: Internal<QueueData<Dir, Met>>,
+    hold_policy: HoldPolicy,
+    /// Buffer reserved by sink
+    pending: Option<BufferRef<Dir, Met>>,
 }
 
 impl<Dir, Met: Method> Drop for Stream<Dir, Met> {
@@ -451,32 +751,490 @@ impl AsRawFd for FdWrapper {
 
 impl<Dir: Direction, Met: Method> Stream<Dir, Met> {
     fn new(file: File, type_: ContentType, count: usize) -> Result<Self> {
+        let mut format = Format::from(Dir::buffer_type(type_));
+        Internal::from(&mut format).get(file.as_raw_fd())?;
+
         let queue = Internal::<QueueData<Dir, Met>>::new(file.as_raw_fd(), type_, count as _)?;
 
-        Ok(Self { file, queue })
+        let interest = if Dir::IN {
+            Interest::READABLE
+        } else {
+            Interest::WRITABLE
+        };
+        // events is used by synchronized capture
+        // registration is dropped before file so descriptor stays valid
+        let ready = unsafe {
+            AsyncFd::register_with_interest(
+                FdWrapper {
+                    fd: file.as_raw_fd(),
+                },
+                interest.add(Interest::PRIORITY),
+            )
+        }?;
+
+        Ok(Self {
+            ready,
+            file,
+            format,
+            queue,
+            hold_policy: HoldPolicy::default(),
+            pending: None,
+        })
+    }
+
+    /// Get format of stream data
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Get behavior when all buffers is held by other threads
+    pub fn hold_policy(&self) -> HoldPolicy {
+        self.hold_policy
+    }
+
+    /// Set behavior when all buffers is held by other threads
+    pub fn set_hold_policy(&mut self, policy: HoldPolicy) {
+        self.hold_policy = policy;
+    }
+
+    /// Check that stream is started
+    pub fn is_started(&self) -> bool {
+        self.queue.is_on()
+    }
+
+    /// Start stream
+    ///
+    /// Stream also starts implicitly on getting next buffer.
+    pub fn start(&self) -> Result<()> {
+        self.queue.start(self.file.as_raw_fd())
+    }
+
+    /// Stop stream
+    ///
+    /// All buffers which is not held by user will be dequeued.
+    pub fn stop(&self) -> Result<()> {
+        self.queue.stop(self.file.as_raw_fd())
+    }
+
+    /// Stop and start stream again
+    pub fn restart(&self) -> Result<()> {
+        self.stop()?;
+        self.start()
+    }
+
+    /// Get number of buffers
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check that stream has no buffers
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Add buffers to stream
+    ///
+    /// Buffers size is calculated according to format which may differ from current
+    /// (i.e. to allocate larger buffers ahead of resolution switch).
+    /// Returns indexes of added buffers.
+    pub fn grow(&mut self, count: usize, format: &Format) -> Result<Range<u32>> {
+        self.queue.create(self.file.as_raw_fd(), count as _, format)
+    }
+
+    /// Remove buffers from stream
+    ///
+    /// Buffers should not be queued or held by user so stream usually should be stopped.
+    /// Requires kernel support of `VIDIOC_REMOVE_BUFS` (Linux 6.10).
+    pub fn remove_buffers(&mut self, indexes: Range<u32>) -> Result<()> {
+        self.queue.remove(self.file.as_raw_fd(), indexes)
+    }
+
+    /// Prepare capture buffers to cut queueing latency
+    ///
+    /// Returns number of prepared buffers.
+    pub fn prepare_buffers(&self) -> Result<usize> {
+        self.queue.prepare_buffers(self.file.as_raw_fd())
+    }
+
+    /// Start collecting frames statistics
+    ///
+    /// Nominal frame interval is taken from stream parameters when available.
+    /// Collected statistics will be reset.
+    pub fn enable_stats(&self) {
+        let mut param = StreamParm::from(self.format.type_());
+        let time_per_frame = Internal::from(&mut param)
+            .get(self.file.as_raw_fd())
+            .ok()
+            .and_then(|_| param.time_per_frame());
+        self.queue.enable_stats(time_per_frame);
+    }
+
+    /// Stop collecting frames statistics
+    pub fn disable_stats(&self) {
+        self.queue.disable_stats();
+    }
+
+    /// Get collected frames statistics
+    ///
+    /// Returns [None] when statistics is not enabled.
+    pub fn stats(&self) -> Option<StreamStats> {
+        self.queue.stats()
+    }
+
+    /// Get next frame with image format to write or read
+    ///
+    /// Only video streams with single memory plane is supported.
+    pub async fn next_frame(&self) -> Result<FrameRef<Dir, Met>> {
+        let format = FrameFormat::new(&self.format)?;
+        Ok(FrameRef::new(self.next().await?, format))
+    }
+
+    /// Get next buffer which can be sent to other thread
+    ///
+    /// The buffer will be enqueued again after dropping.
+    /// Dropping of pending future takes no released buffer.
+    pub async fn next_shared(&self) -> Result<SharedBuffer<Dir, Met>> {
+        while !self.queue.reserve(self.hold_policy)? {
+            let returns = self.queue.returns();
+            let returned = core::future::poll_fn(|cx| returns.poll_recv(cx)).await?;
+            self.queue.restore(returned);
+        }
+        Ok(self.queue.lend(self.next().await?))
     }
 
     /// Get next frame to write or read
     pub async fn next(&self) -> Result<BufferRef<Dir, Met>> {
+        core::future::poll_fn(|cx| self.poll_next_buffer(cx)).await
+    }
+
+    fn poll_next_buffer(&self, cx: &mut Context<'_>) -> Poll<Result<BufferRef<Dir, Met>>> {
         let fd = self.file.as_raw_fd();
 
         loop {
             match self.queue.next(fd) {
-                Ok(buffer) => break Ok(buffer),
                 Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
-                    let async_fd = AsyncFd::new(FdWrapper { fd })?;
+                    let mut guard = ready!(if Dir::IN {
+                        self.ready.poll_read_ready(cx)
+                    } else {
+                        self.ready.poll_write_ready(cx)
+                    })?;
+                    // readiness is cleared before next try so no events will be lost
+                    guard.clear_ready();
+                }
+                result => return Poll::Ready(result),
+            }
+        }
+    }
+}
+
+impl<Met: Method> futures_core::Stream for Stream<In, Met> {
+    type Item = Result<BufferRef<In, Met>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_buffer(cx).map(Some)
+    }
+}
+
+impl<Met: Method, T: AsRef<[u8]>> futures_sink::Sink<T> for Stream<Out, Met> {
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        if this.pending.is_none() {
+            this.pending = Some(ready!(this.poll_next_buffer(cx))?);
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<()> {
+        let buffer = self
+            .get_mut()
+            .pending
+            .take()
+            .ok_or_else(|| io::Error::other("Sink is not ready"))?;
+        let data = item.as_ref();
+        let mut buffer = buffer.lock();
+
+        if data.len() > buffer.capacity() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Data exceeds buffer capacity",
+            ));
+        }
+
+        buffer.set_len(data.len());
+        buffer.as_mut().copy_from_slice(data);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(self.queue.flush(self.file.as_raw_fd()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(self.queue.flush(self.file.as_raw_fd()))
+    }
+}
+
+/// Group of capture streams which frames is received together
+///
+/// Frames of streams is paired according to [SyncMode].
+/// Frames which have no pair is skipped and counted in [SyncStats].
+pub struct SyncGroup<Met: Method> {
+    streams: Vec<Stream<In, Met>>,
+    matcher: FrameMatcher,
+}
+
+impl<Met: Method> SyncGroup<Met> {
+    /// Create group of streams
+    ///
+    /// Streams is subscribed to frame sync events when [SyncMode::FrameSync] is used.
+    pub fn new(streams: Vec<Stream<In, Met>>, mode: SyncMode) -> Result<Self> {
+        if streams.is_empty() {
+            return Err(io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "No streams to synchronize",
+            ));
+        }
+
+        if mode == SyncMode::FrameSync {
+            let subscription =
+                EventSubscription::new(EventType::FrameSync, 0, EventSubFlag::none());
+            for stream in &streams {
+                Internal::from(&subscription).subscribe(stream.file.as_raw_fd())?;
+            }
+        }
+
+        let matcher = FrameMatcher::new(mode, streams.len());
+
+        Ok(Self { streams, matcher })
+    }
+
+    /// Get streams of group
+    pub fn streams(&self) -> &[Stream<In, Met>] {
+        &self.streams
+    }
+
+    /// Get frames pairing mode
+    pub fn mode(&self) -> SyncMode {
+        self.matcher.mode()
+    }
+
+    /// Get synchronization statistics
+    pub fn stats(&self) -> &SyncStats {
+        self.matcher.stats()
+    }
+
+    /// Start all streams
+    pub fn start(&mut self) -> Result<()> {
+        self.matcher.reset();
+        for stream in &self.streams {
+            stream.start()?;
+        }
+        Ok(())
+    }
+
+    /// Stop all streams
+    pub fn stop(&mut self) -> Result<()> {
+        for stream in &self.streams {
+            stream.stop()?;
+        }
+        Ok(())
+    }
 
-                    let _ = core::future::poll_fn(|cx| {
-                        if Dir::IN {
-                            async_fd.poll_read_ready(cx)
-                        } else {
-                            async_fd.poll_write_ready(cx)
+    /// Get next aligned frames
+    ///
+    /// Frames is ordered in the same way as streams.
+    pub async fn next(&mut self) -> Result<Vec<BufferRef<In, Met>>> {
+        let mut frames: Vec<Option<BufferRef<In, Met>>> =
+            self.streams.iter().map(|_| None).collect();
+
+        loop {
+            for (index, stream) in self.streams.iter().enumerate() {
+                if frames[index].is_none() {
+                    let frame = stream.next().await?;
+                    self.matcher.receive(index, &frame.lock());
+                    frames[index] = Some(frame);
+                }
+            }
+
+            for (index, stream) in self.streams.iter().enumerate() {
+                let fd = stream.file.as_raw_fd();
+                while self.matcher.needs_event(index) {
+                    match Internal::<Event>::dequeue(fd) {
+                        Ok(event) => self.matcher.event(index, &event),
+                        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                            stream.ready.ready(Interest::PRIORITY).await?.clear_ready();
                         }
-                    })
-                    .await?;
+                        Err(error) => return Err(error),
+                    }
                 }
-                Err(error) => break Err(error),
+            }
+
+            let buffers: Vec<Buffer> = frames.iter().flatten().map(|frame| *frame.lock()).collect();
+
+            let stale = self.matcher.check(&buffers);
+
+            if stale.is_empty() {
+                return Ok(frames.into_iter().flatten().collect());
+            }
+
+            for index in stale {
+                frames[index] = None;
+            }
+        }
+    }
+
+    /// Take streams back
+    pub fn into_streams(self) -> Vec<Stream<In, Met>> {
+        self.streams
+    }
+}
+
+/// Data input using read I/O method
+///
+/// Each read operation does not cross frame boundary.
+pub struct ReadStream {
+    file: AsyncFd<File>,
+    format: Format,
+    frame: FrameBuf,
+}
+
+impl ReadStream {
+    fn new(file: File, type_: ContentType) -> Result<Self> {
+        let (format, size) = read_write_format::<In>(file.as_raw_fd(), type_)?;
+
+        Ok(Self {
+            // file is owned by registration so descriptor stays valid
+            file: unsafe { AsyncFd::register(file) }?,
+            format,
+            frame: FrameBuf::new(size),
+        })
+    }
+
+    /// Get format of stream data
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Get maximum size of frame in bytes
+    pub fn frame_size(&self) -> usize {
+        self.frame.size()
+    }
+
+    /// Read next entire frame
+    ///
+    /// Pending data of previous frame will be discarded.
+    /// When output is less than frame the rest of frame will be discarded too.
+    pub async fn read_frame(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.frame.clear();
+        core::future::poll_fn(|cx| self.poll_fill(cx)).await?;
+        let len = self.frame.take(buf);
+        self.frame.clear();
+        Ok(len)
+    }
+
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            let mut guard = ready!(self.file.poll_read_ready(cx))?;
+            let frame = &mut self.frame;
+            if let Ok(result) = guard.try_io(|file| frame.fill(|data| file.get_ref().read(data))) {
+                return Poll::Ready(result);
             }
         }
     }
 }
+
+impl AsyncRead for ReadStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        if this.frame.is_empty() {
+            ready!(this.poll_fill(cx))?;
+        }
+        let len = this.frame.take(buf.initialize_unfilled());
+        buf.advance(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Data output using write I/O method
+///
+/// Data is written by entire frames.
+/// Pending data should be flushed before dropping.
+/// Flushing of incomplete frame fails.
+pub struct WriteStream {
+    file: AsyncFd<File>,
+    format: Format,
+    frame: FrameBuf,
+}
+
+impl WriteStream {
+    fn new(file: File, type_: ContentType) -> Result<Self> {
+        let (format, size) = read_write_format::<Out>(file.as_raw_fd(), type_)?;
+
+        Ok(Self {
+            // file is owned by registration so descriptor stays valid
+            file: unsafe { AsyncFd::register(file) }?,
+            format,
+            frame: FrameBuf::new(size),
+        })
+    }
+
+    /// Get format of stream data
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// Get maximum size of frame in bytes
+    pub fn frame_size(&self) -> usize {
+        self.frame.size()
+    }
+
+    /// Write entire frame
+    ///
+    /// Complete frame written by parts will be written before.
+    /// Fails with [std::io::ErrorKind::InvalidInput] when previous frame is incomplete.
+    /// Data which exceeds frame size is ignored.
+    pub async fn write_frame(&mut self, buf: &[u8]) -> Result<usize> {
+        core::future::poll_fn(|cx| self.poll_flush_frame(cx)).await?;
+        let len = self.frame.set(buf);
+        core::future::poll_fn(|cx| self.poll_flush_frame(cx)).await?;
+        Ok(len)
+    }
+
+    fn poll_flush_frame(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.frame.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+        loop {
+            let mut guard = ready!(self.file.poll_write_ready(cx))?;
+            let frame = &mut self.frame;
+            if let Ok(result) = guard.try_io(|file| frame.flush(|data| file.get_ref().write(data)))
+            {
+                return Poll::Ready(result);
+            }
+        }
+    }
+}
+
+impl AsyncWrite for WriteStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        if this.frame.is_full() {
+            ready!(this.poll_flush_frame(cx))?;
+        }
+        Poll::Ready(Ok(this.frame.put(buf)))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_flush_frame(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
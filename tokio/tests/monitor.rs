@@ -0,0 +1,17 @@
+use tokio_linux_video::{Device, DeviceMonitor};
+
+#[test]
+fn monitor_traits() {
+    fn is_stream<T: futures_core::Stream>() {}
+
+    is_stream::<DeviceMonitor>();
+}
+
+#[tokio::test]
+async fn monitor_pending() {
+    let mut monitor = Device::monitor().unwrap();
+
+    let result =
+        tokio::time::timeout(std::time::Duration::from_millis(10), monitor.next_event()).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,49 @@
+use futures_util::{SinkExt, StreamExt};
+use tokio_linux_video::{types::*, Device, FmtDescs, Stream};
+
+#[test]
+fn stream_traits() {
+    fn is_stream<T: futures_core::Stream>() {}
+    fn is_sink<T: futures_sink::Sink<Vec<u8>>>() {}
+
+    is_stream::<Stream<In, Mmap>>();
+    is_stream::<FmtDescs<'static>>();
+    is_sink::<Stream<Out, Mmap>>();
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[tokio::test]
+async fn capture_stream() {
+    let dev = Device::open(env!("VIVID_WEBCAM")).await.unwrap();
+
+    let fmts = dev
+        .formats(BufferType::VideoCapture)
+        .collect::<Vec<_>>()
+        .await;
+    assert!(!fmts.is_empty());
+    assert!(fmts.iter().all(Result::is_ok));
+
+    let stream = dev.stream::<In, Mmap>(ContentType::Video, 4).unwrap();
+
+    let sequences = stream
+        .take(4)
+        .map(|buffer| buffer.unwrap().lock().sequence())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(sequences.len(), 4);
+}
+
+#[cfg_attr(not(feature = "test-vivid"), ignore)]
+#[tokio::test]
+async fn output_sink() {
+    let dev = Device::open(env!("VIVID_HDMI_OUT")).await.unwrap();
+    let mut stream = dev.stream::<Out, Mmap>(ContentType::Video, 4).unwrap();
+
+    let size = stream.format().try_ref::<PixFormat>().unwrap().size_image() as usize;
+
+    for _ in 0..6 {
+        stream.send(vec![0u8; size]).await.unwrap();
+    }
+
+    assert!(stream.is_started());
+}